@@ -0,0 +1,105 @@
+//! Benchmarks comparing `map!` and `set!` with the standard library's
+//! constructors across literal sizes.
+//!
+//! Run with `cargo bench --features map,set`.
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use smacro::{map, set};
+use std::collections::{HashMap, HashSet};
+use std::hint::black_box;
+
+/// Benchmarks every way of building a map and a set from the same literal.
+macro_rules! bench_size {
+    ($c:expr, $($n:literal,)+) => {{
+        let size = [$($n),+].len();
+
+        let mut group = $c.benchmark_group("map");
+        group.bench_function(BenchmarkId::new("map!", size), |b| {
+            b.iter(|| map![$(black_box($n) => black_box($n)),+])
+        });
+        group.bench_function(BenchmarkId::new("new_insert", size), |b| {
+            b.iter(|| {
+                let mut map = HashMap::new();
+                $(map.insert(black_box($n), black_box($n));)+
+                map
+            })
+        });
+        group.bench_function(BenchmarkId::new("with_capacity_insert", size), |b| {
+            b.iter(|| {
+                let mut map = HashMap::with_capacity(size);
+                $(map.insert(black_box($n), black_box($n));)+
+                map
+            })
+        });
+        group.bench_function(BenchmarkId::new("from_array", size), |b| {
+            b.iter(|| HashMap::from([$((black_box($n), black_box($n))),+]))
+        });
+        group.bench_function(BenchmarkId::new("from_iter", size), |b| {
+            b.iter(|| HashMap::<_, _>::from_iter([$((black_box($n), black_box($n))),+]))
+        });
+        group.finish();
+
+        let mut group = $c.benchmark_group("set");
+        group.bench_function(BenchmarkId::new("set!", size), |b| {
+            b.iter(|| set!($(black_box($n)),+))
+        });
+        group.bench_function(BenchmarkId::new("new_insert", size), |b| {
+            b.iter(|| {
+                let mut set = HashSet::new();
+                $(set.insert(black_box($n));)+
+                set
+            })
+        });
+        group.bench_function(BenchmarkId::new("with_capacity_insert", size), |b| {
+            b.iter(|| {
+                let mut set = HashSet::with_capacity(size);
+                $(set.insert(black_box($n));)+
+                set
+            })
+        });
+        group.bench_function(BenchmarkId::new("from_array", size), |b| {
+            b.iter(|| HashSet::from([$(black_box($n)),+]))
+        });
+        group.bench_function(BenchmarkId::new("from_iter", size), |b| {
+            b.iter(|| HashSet::<_>::from_iter([$(black_box($n)),+]))
+        });
+        group.finish();
+    }};
+}
+
+fn collections(c: &mut Criterion) {
+    bench_size!(
+        c,
+        0, 1, 2, 3,
+    );
+    bench_size!(
+        c,
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+    );
+    bench_size!(
+        c,
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22,
+        23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43,
+        44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63,
+    );
+    bench_size!(
+        c,
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22,
+        23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43,
+        44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64,
+        65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85,
+        86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99, 100, 101, 102, 103, 104,
+        105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 115, 116, 117, 118, 119, 120, 121,
+        122, 123, 124, 125, 126, 127, 128, 129, 130, 131, 132, 133, 134, 135, 136, 137, 138,
+        139, 140, 141, 142, 143, 144, 145, 146, 147, 148, 149, 150, 151, 152, 153, 154, 155,
+        156, 157, 158, 159, 160, 161, 162, 163, 164, 165, 166, 167, 168, 169, 170, 171, 172,
+        173, 174, 175, 176, 177, 178, 179, 180, 181, 182, 183, 184, 185, 186, 187, 188, 189,
+        190, 191, 192, 193, 194, 195, 196, 197, 198, 199, 200, 201, 202, 203, 204, 205, 206,
+        207, 208, 209, 210, 211, 212, 213, 214, 215, 216, 217, 218, 219, 220, 221, 222, 223,
+        224, 225, 226, 227, 228, 229, 230, 231, 232, 233, 234, 235, 236, 237, 238, 239, 240,
+        241, 242, 243, 244, 245, 246, 247, 248, 249, 250, 251, 252, 253, 254, 255,
+    );
+}
+
+criterion_group!(benches, collections);
+criterion_main!(benches);
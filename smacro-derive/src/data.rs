@@ -0,0 +1,169 @@
+//! Literal decoding and data file parsing for the `include_map!`,
+//! `toml_map!` and `yaml_map!` macros.
+//!
+//! TOML and YAML documents are parsed at compile time only to report syntax
+//! errors. The generated code parses them again at runtime into the values
+//! of the matching crate.
+
+/// Returns the value of a string literal from its body, without its quotes.
+///
+/// `raw` tells whether the literal was a raw string, in which case the body
+/// is already the value.
+pub fn unescape(body: &str, raw: bool) -> Result<String, String> {
+    if raw {
+        return Ok(body.to_string());
+    }
+
+    let mut value = String::with_capacity(body.len());
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => value.push('\n'),
+            Some('r') => value.push('\r'),
+            Some('t') => value.push('\t'),
+            Some('0') => value.push('\0'),
+            Some(c @ ('\\' | '\'' | '"')) => value.push(c),
+            Some('x') => {
+                let hex: String = chars.by_ref().take(2).collect();
+                let byte = u8::from_str_radix(&hex, 16).map_err(|_| "invalid `\\x` escape")?;
+                value.push(char::from(byte));
+            }
+            Some('u') => {
+                let hex: String = chars
+                    .by_ref()
+                    .skip_while(|&c| c == '{')
+                    .take_while(|&c| c != '}')
+                    .collect();
+                let c = u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or("invalid `\\u` escape")?;
+                value.push(c);
+            }
+            // A line continuation skips the newline and leading whitespace
+            Some('\n') => while chars.next_if(|c| c.is_whitespace()).is_some() {},
+            _ => return Err("invalid escape sequence".into()),
+        }
+    }
+    Ok(value)
+}
+
+/// Parses the lines of a two-column data file into key-value pairs.
+///
+/// Each line is split at its first `=`, or at its first run of whitespace
+/// if it has none, and both sides are trimmed. Blank lines and lines
+/// starting with `#` are skipped. Errors name the offending line.
+pub fn parse_pairs(text: &str) -> Result<Vec<(String, String)>, String> {
+    let mut pairs = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (key, value) = match line.split_once('=') {
+            Some(pair) => pair,
+            None => line.split_once(char::is_whitespace).unwrap_or((line, "")),
+        };
+        let (key, value) = (key.trim(), value.trim());
+        if key.is_empty() || value.is_empty() {
+            return Err(format!(
+                "line {} is not a key-value pair: `{}`",
+                number + 1,
+                line
+            ));
+        }
+        pairs.push((key.to_string(), value.to_string()));
+    }
+    Ok(pairs)
+}
+
+#[cfg(feature = "toml")]
+pub fn validate_toml(document: &str) -> Result<(), String> {
+    toml::from_str::<toml::Table>(document)
+        .map(|_| ())
+        .map_err(|e| e.message().to_string())
+}
+
+#[cfg(feature = "yaml")]
+pub fn validate_yaml(document: &str) -> Result<(), String> {
+    serde_yaml::from_str::<serde_yaml::Mapping>(document)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_pairs, unescape};
+
+    #[test]
+    fn plain_and_raw() {
+        assert_eq!(unescape("a = 1", false).unwrap(), "a = 1");
+        assert_eq!(
+            unescape(r"path = 'C:\dir'", true).unwrap(),
+            r"path = 'C:\dir'"
+        );
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(
+            unescape(r#"a\n\t\"b\"\\ \x41\u{e9}\0"#, false).unwrap(),
+            "a\n\t\"b\"\\ A\u{e9}\0"
+        );
+        assert_eq!(unescape("one \\\n    two", false).unwrap(), "one two");
+    }
+
+    #[test]
+    fn invalid_escapes() {
+        assert!(unescape(r"\q", false).is_err());
+        assert!(unescape(r"\u{110000}", false).is_err());
+    }
+
+    #[test]
+    fn pairs_with_both_separators() {
+        let pairs =
+            parse_pairs("# mime types\n\ntext/html   html htm\njson = application/json\n").unwrap();
+        assert_eq!(
+            pairs,
+            [
+                ("text/html".to_string(), "html htm".to_string()),
+                ("json".to_string(), "application/json".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn pairs_errors() {
+        assert_eq!(
+            parse_pairs("a = 1\nlonely\n").unwrap_err(),
+            "line 2 is not a key-value pair: `lonely`"
+        );
+        assert!(parse_pairs("= value").is_err());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_documents() {
+        use super::validate_toml;
+
+        assert!(validate_toml("a = 1\n[b]\nc = [\"x\"]").is_ok());
+        assert!(validate_toml("").is_ok());
+        assert!(validate_toml("a = ").is_err());
+        assert!(validate_toml("a = 1\na = 2").is_err());
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn yaml_documents() {
+        use super::validate_yaml;
+
+        assert!(validate_yaml("a: 1\nb:\n  - x\n  - y").is_ok());
+        assert!(validate_yaml("a: [1, 2").is_err());
+        assert!(validate_yaml("- just\n- a list").is_err());
+    }
+}
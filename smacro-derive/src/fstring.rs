@@ -0,0 +1,193 @@
+//! Template parsing for the `f!` macro.
+//!
+//! The template is split into a `format!` string with positional
+//! placeholders and the list of expressions that were embedded in it.
+
+/// A parsed `f!` template.
+#[derive(Debug, PartialEq)]
+pub struct Template {
+    /// The body of the format string, still escaped as in the source literal.
+    pub format: String,
+    /// The source text of each embedded expression, in order.
+    pub args: Vec<String>,
+}
+
+/// Parses the body of a string literal, without its quotes.
+///
+/// `raw` tells whether the literal was a raw string, in which case escape
+/// sequences are left untouched inside expressions.
+pub fn parse(body: &str, raw: bool) -> Result<Template, String> {
+    let mut format = String::with_capacity(body.len());
+    let mut args = Vec::new();
+    let mut chars = body.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                format.push_str("{{");
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                format.push_str("}}");
+            }
+            '}' => return Err("unmatched `}` in format string, use `}}` to escape it".into()),
+            '{' => {
+                let (expr, spec) = placeholder(&mut chars, raw)?;
+                if expr.trim().is_empty() {
+                    return Err("empty placeholder, put an expression between the braces".into());
+                }
+                format.push('{');
+                if let Some(spec) = spec {
+                    format.push(':');
+                    format.push_str(&spec);
+                }
+                format.push('}');
+                args.push(expr.trim().to_string());
+            }
+            '\\' if !raw => {
+                // Keep escape sequences intact, so an escaped quote or
+                // backslash is never mistaken for the end of the literal.
+                format.push(c);
+                if let Some(next) = chars.next() {
+                    format.push(next);
+                }
+            }
+            _ => format.push(c),
+        }
+    }
+
+    Ok(Template { format, args })
+}
+
+/// Reads a placeholder up to its closing brace, returning the expression and
+/// the format spec after the top-level `:`, if any.
+fn placeholder(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    raw: bool,
+) -> Result<(String, Option<String>), String> {
+    let mut expr = String::new();
+    let mut depth = 0usize;
+    let mut in_str = false;
+    let mut escaped = false;
+
+    loop {
+        let Some(mut c) = chars.next() else {
+            return Err("unclosed `{` in format string, use `{{` to escape it".into());
+        };
+
+        if c == '\\' && !raw {
+            // Undo the escaping the literal itself needed, so `\"` in the
+            // template becomes a quote in the expression.
+            match chars.next() {
+                Some(next @ ('"' | '\\')) => c = next,
+                Some(next) => {
+                    expr.push(c);
+                    expr.push(next);
+                    continue;
+                }
+                None => return Err("unclosed `{` in format string".into()),
+            }
+        }
+
+        if in_str {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_str = false;
+            }
+            expr.push(c);
+            continue;
+        }
+
+        match c {
+            '"' => in_str = true,
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            '}' if depth == 0 => return Ok((expr, None)),
+            '}' => depth -= 1,
+            ':' if depth == 0 => {
+                if chars.peek() == Some(&':') {
+                    chars.next();
+                    expr.push_str("::");
+                    continue;
+                }
+                let mut spec = String::new();
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        return Ok((expr, Some(spec)));
+                    }
+                    spec.push(c);
+                }
+                return Err("unclosed `{` in format string, use `{{` to escape it".into());
+            }
+            _ => {}
+        }
+        expr.push(c);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(format: &str, args: &[&str]) -> Template {
+        Template {
+            format: format.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn plain_text() {
+        assert_eq!(parse("hello", false), Ok(template("hello", &[])));
+    }
+
+    #[test]
+    fn field_and_method_calls() {
+        assert_eq!(
+            parse("Hi {user.name}, {msgs.len()} new", false),
+            Ok(template("Hi {}, {} new", &["user.name", "msgs.len()"]))
+        );
+    }
+
+    #[test]
+    fn format_specs_and_paths() {
+        assert_eq!(
+            parse("{price * 2.0:>8.2} {std::f64::consts::PI:.1}", false),
+            Ok(template(
+                "{:>8.2} {:.1}",
+                &["price * 2.0", "std::f64::consts::PI"]
+            ))
+        );
+    }
+
+    #[test]
+    fn nested_braces_and_strings() {
+        assert_eq!(
+            parse(r#"{map.get(\"a:}\").map(|v| { v + 1 }):?}"#, false),
+            Ok(template("{:?}", &[r#"map.get("a:}").map(|v| { v + 1 })"#]))
+        );
+        assert_eq!(
+            parse(r#"{map["key"]}"#, true),
+            Ok(template("{}", &[r#"map["key"]"#]))
+        );
+    }
+
+    #[test]
+    fn escaped_braces() {
+        assert_eq!(
+            parse("{{literal}} {x}\\n", false),
+            Ok(template("{{literal}} {}\\n", &["x"]))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(parse("{}", false).is_err());
+        assert!(parse("{x", false).is_err());
+        assert!(parse("x}", false).is_err());
+    }
+}
@@ -0,0 +1,396 @@
+//! # smacro-derive
+//!
+//! Procedural macros for [`smacro`](https://crates.io/crates/smacro).
+//!
+//! This crate is not meant to be used directly: enable the matching feature
+//! of `smacro` instead, which re-exports everything defined here.
+//!
+//! - `f!` - Format strings with arbitrary embedded expressions (`fstring` feature)
+//! - `#[derive(ToMap)]`, `#[derive(FromMap)]` - Convert structs to and from
+//!   string maps (`derive` feature)
+//! - `include_map!` - Embed a two-column data file as a map (`derive` feature)
+//! - `toml_map!`, `yaml_map!` - Inline TOML and YAML documents checked at
+//!   compile time (`toml` and `yaml` features)
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+mod data;
+mod fstring;
+mod paste;
+mod to_map;
+
+/// A convenience macro for formatting strings with embedded expressions.
+///
+/// `format!` and `s!` only capture plain identifiers, so `{user.name}` or
+/// `{items.len()}` cannot be written inline. `f!` accepts any expression
+/// between the braces and expands to `format!` with positional arguments.
+///
+/// A format spec can follow the expression after a `:`, as in `format!`.
+/// Braces are escaped by doubling them.
+///
+/// # Examples
+///
+/// ## Field access and method calls
+/// ```
+/// # use smacro_derive::f;
+/// struct User {
+///     name: String,
+/// }
+///
+/// let user = User { name: "Ada".to_string() };
+/// let messages = vec!["hi", "bye"];
+///
+/// let text = f!("Hello {user.name}, you have {messages.len()} messages");
+/// assert_eq!(text, "Hello Ada, you have 2 messages");
+/// ```
+///
+/// ## Format specs
+/// ```
+/// # use smacro_derive::f;
+/// let prices = [1.5, 20.25];
+///
+/// let line = f!("[{prices[0] * 2.0:>6.2}] [{prices.iter().sum::<f64>():.1}] {{total}}");
+/// assert_eq!(line, "[  3.00] [21.8] {total}");
+/// ```
+///
+/// ## String literals inside expressions
+/// ```
+/// # use smacro_derive::f;
+/// use std::collections::HashMap;
+///
+/// let env = HashMap::from([("HOME", "/home/ada")]);
+///
+/// let raw = f!(r#"home is {env["HOME"]}"#);
+/// let escaped = f!("home is {env.get(\"HOME\").unwrap()}");
+///
+/// assert_eq!(raw, "home is /home/ada");
+/// assert_eq!(escaped, raw);
+/// ```
+///
+/// # Evaluation
+///
+/// Each expression is evaluated exactly once, from left to right. An
+/// expression cannot contain a `:` outside of brackets other than in a `::`
+/// path, since it would start the format spec.
+#[proc_macro]
+pub fn f(input: TokenStream) -> TokenStream {
+    let Some(literal) = string_literal(input) else {
+        return compile_error("f! expects a single string literal", Span::call_site());
+    };
+
+    let source = literal.to_string();
+    let (prefix, body, suffix) = match split_literal(&source) {
+        Some(parts) => parts,
+        None => return compile_error("f! expects a string literal", literal.span()),
+    };
+
+    let template = match fstring::parse(body, prefix.starts_with('r')) {
+        Ok(template) => template,
+        Err(message) => return compile_error(&format!("f!: {}", message), literal.span()),
+    };
+
+    let mut args = String::new();
+    for expr in &template.args {
+        if expr.parse::<TokenStream>().is_err() {
+            return compile_error(
+                &format!("f!: invalid expression `{}`", expr),
+                literal.span(),
+            );
+        }
+        args.push_str(&format!(", ({})", expr));
+    }
+
+    format!(
+        "::std::format!({}{}{}{})",
+        prefix, template.format, suffix, args
+    )
+    .parse()
+    .unwrap_or_else(|_| compile_error("f!: invalid format string", literal.span()))
+}
+
+// Documented, with a doctest, on the `smacro::ToMap` re-export.
+#[proc_macro_derive(ToMap)]
+pub fn derive_to_map(input: TokenStream) -> TokenStream {
+    let item = match to_map::parse(input) {
+        Ok(item) => item,
+        Err(message) => return compile_error(&format!("ToMap {}", message), Span::call_site()),
+    };
+
+    let inserts: String = item
+        .fields
+        .iter()
+        .map(|field| {
+            format!(
+                "map.insert(::std::string::String::from({:?}), ::std::string::ToString::to_string(&self.{}));",
+                field.trim_start_matches("r#"),
+                field
+            )
+        })
+        .collect();
+
+    format!(
+        "impl{} ::smacro::ToMap for {}{} {} {{
+            fn to_map(&self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {{
+                let mut map = ::std::collections::HashMap::with_capacity({});
+                {}
+                map
+            }}
+        }}",
+        item.impl_generics(),
+        item.name,
+        item.type_generics(),
+        item.where_clause,
+        item.fields.len(),
+        inserts
+    )
+    .parse()
+    .unwrap_or_else(|_| compile_error("ToMap: failed to generate the implementation", Span::call_site()))
+}
+
+// Documented, with a doctest, on the `smacro::FromMap` re-export.
+#[proc_macro_derive(FromMap)]
+pub fn derive_from_map(input: TokenStream) -> TokenStream {
+    let item = match to_map::parse(input) {
+        Ok(item) => item,
+        Err(message) => return compile_error(&format!("FromMap {}", message), Span::call_site()),
+    };
+
+    let fields: String = item
+        .fields
+        .iter()
+        .map(|field| {
+            format!(
+                "{}: ::smacro::to_map::field(map, {:?})?,",
+                field,
+                field.trim_start_matches("r#")
+            )
+        })
+        .collect();
+
+    format!(
+        "impl{} ::smacro::FromMap for {}{} {} {{
+            fn from_map(
+                map: &::std::collections::HashMap<::std::string::String, ::std::string::String>,
+            ) -> ::std::result::Result<Self, ::std::string::String> {{
+                ::std::result::Result::Ok(Self {{ {} }})
+            }}
+        }}",
+        item.impl_generics(),
+        item.name,
+        item.type_generics(),
+        item.where_clause,
+        fields
+    )
+    .parse()
+    .unwrap_or_else(|_| {
+        compile_error(
+            "FromMap: failed to generate the implementation",
+            Span::call_site(),
+        )
+    })
+}
+
+// Documented, with a doctest, on the `smacro::include_map` re-export.
+#[proc_macro]
+pub fn include_map(input: TokenStream) -> TokenStream {
+    let Some(literal) = string_literal(input) else {
+        return compile_error(
+            "include_map! expects a single string literal",
+            Span::call_site(),
+        );
+    };
+
+    let source = literal.to_string();
+    let path = match split_literal(&source)
+        .map(|(prefix, body, _)| data::unescape(body, prefix.starts_with('r')))
+    {
+        Some(Ok(path)) => path,
+        _ => return compile_error("include_map! expects a string literal", literal.span()),
+    };
+
+    let Some(file) = literal.span().local_file() else {
+        return compile_error(
+            "include_map! must be called from a source file on disk",
+            literal.span(),
+        );
+    };
+    let dir = file.parent().unwrap_or(std::path::Path::new(""));
+    let full_path = std::env::current_dir()
+        .unwrap_or_default()
+        .join(dir)
+        .join(&path);
+    let text = match std::fs::read_to_string(&full_path) {
+        Ok(text) => text,
+        Err(e) => {
+            return compile_error(
+                &format!(
+                    "include_map!: failed to read {}: {}",
+                    full_path.display(),
+                    e
+                ),
+                literal.span(),
+            );
+        }
+    };
+    let pairs = match data::parse_pairs(&text) {
+        Ok(pairs) => pairs,
+        Err(message) => {
+            return compile_error(
+                &format!("include_map!: {}: {}", path, message),
+                literal.span(),
+            );
+        }
+    };
+
+    let inserts: String = pairs
+        .iter()
+        .map(|(key, value)| format!("map.insert({:?}, {:?});", key, value))
+        .collect();
+    format!(
+        "{{
+            const _: &str = ::core::include_str!({:?});
+            let mut map: ::std::collections::HashMap<&'static str, &'static str> =
+                ::std::collections::HashMap::with_capacity({});
+            {}
+            map
+        }}",
+        full_path.display().to_string(),
+        pairs.len(),
+        inserts
+    )
+    .parse()
+    .unwrap_or_else(|_| compile_error("include_map!: failed to generate the map", literal.span()))
+}
+
+// Documented, with a doctest, on the `smacro::toml_map` re-export.
+#[cfg(feature = "toml")]
+#[proc_macro]
+pub fn toml_map(input: TokenStream) -> TokenStream {
+    inline_document(
+        input,
+        "toml_map!",
+        data::validate_toml,
+        "::smacro::__toml::from_str::<::smacro::__toml::Table>",
+    )
+}
+
+// Documented, with a doctest, on the `smacro::yaml_map` re-export.
+#[cfg(feature = "yaml")]
+#[proc_macro]
+pub fn yaml_map(input: TokenStream) -> TokenStream {
+    inline_document(
+        input,
+        "yaml_map!",
+        data::validate_yaml,
+        "::smacro::__serde_yaml::from_str::<::smacro::__serde_yaml::Mapping>",
+    )
+}
+
+/// Validates the document in a string literal at compile time and expands
+/// to `parser(literal)`, unwrapped.
+#[cfg(any(feature = "toml", feature = "yaml"))]
+fn inline_document(
+    input: TokenStream,
+    name: &str,
+    validate: fn(&str) -> Result<(), String>,
+    parser: &str,
+) -> TokenStream {
+    let Some(literal) = string_literal(input) else {
+        return compile_error(
+            &format!("{} expects a single string literal", name),
+            Span::call_site(),
+        );
+    };
+
+    let source = literal.to_string();
+    let Some((prefix, body, _)) = split_literal(&source) else {
+        return compile_error(
+            &format!("{} expects a string literal", name),
+            literal.span(),
+        );
+    };
+
+    let document = match data::unescape(body, prefix.starts_with('r')) {
+        Ok(document) => document,
+        Err(message) => return compile_error(&format!("{}: {}", name, message), literal.span()),
+    };
+    if let Err(message) = validate(&document) {
+        return compile_error(&format!("{}: {}", name, message.trim()), literal.span());
+    }
+
+    let mut call: TokenStream = parser.parse().expect("parser path is valid");
+    call.extend([TokenTree::Group(Group::new(
+        Delimiter::Parenthesis,
+        TokenTree::Literal(literal).into(),
+    ))]);
+    call.extend(
+        format!(
+            ".expect(\"{} document was validated at compile time\")",
+            name
+        )
+        .parse::<TokenStream>()
+        .expect("method call is valid"),
+    );
+    call
+}
+
+/// Pastes identifiers together inside `[< ... >]` brackets.
+///
+/// Used by the `macro_rules!` macros of `smacro` that need to generate new
+/// identifiers, such as `getset!` and `builder!`. Not part of the public API.
+#[doc(hidden)]
+#[proc_macro]
+pub fn paste(input: TokenStream) -> TokenStream {
+    paste::expand(input).unwrap_or_else(|(message, span)| compile_error(&message, span))
+}
+
+/// Returns the only token of the input if it is a literal, looking through
+/// the invisible groups `macro_rules!` wraps captured fragments in.
+fn string_literal(input: TokenStream) -> Option<Literal> {
+    let mut tokens = input.into_iter();
+    let token = tokens.next()?;
+    if tokens.next().is_some() {
+        return None;
+    }
+
+    match token {
+        TokenTree::Literal(literal) => Some(literal),
+        TokenTree::Group(group) if group.delimiter() == Delimiter::None => {
+            string_literal(group.stream())
+        }
+        _ => None,
+    }
+}
+
+/// Splits a string literal into its opening quote, body and closing quote,
+/// keeping the `r#` prefix of raw strings.
+fn split_literal(source: &str) -> Option<(&str, &str, &str)> {
+    let open = source.find('"')?;
+    let prefix = &source[..=open];
+    if !(prefix == "\"" || prefix.starts_with('r') && prefix[1..open].chars().all(|c| c == '#')) {
+        return None;
+    }
+
+    let close = source.rfind('"')?;
+    if close <= open {
+        return None;
+    }
+    Some((prefix, &source[open + 1..close], &source[close..]))
+}
+
+fn compile_error(message: &str, span: Span) -> TokenStream {
+    let mut literal = Literal::string(message);
+    literal.set_span(span);
+    let mut group = Group::new(Delimiter::Parenthesis, TokenTree::Literal(literal).into());
+    group.set_span(span);
+    let mut bang = Punct::new('!', Spacing::Alone);
+    bang.set_span(span);
+
+    [
+        TokenTree::Ident(Ident::new("compile_error", span)),
+        TokenTree::Punct(bang),
+        TokenTree::Group(group),
+    ]
+    .into_iter()
+    .collect()
+}
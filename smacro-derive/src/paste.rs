@@ -0,0 +1,95 @@
+//! Identifier pasting for `macro_rules!` macros in `smacro`.
+//!
+//! `macro_rules!` cannot build new identifiers, so macros that generate
+//! methods like `set_port` or types like `RequestBuilder` wrap their output
+//! in the hidden `__paste!` macro and write `[<set_ $field>]`. Every such
+//! bracket is replaced by a single identifier made of its parts.
+
+use proc_macro::{Delimiter, Group, Ident, Spacing, Span, TokenStream, TokenTree};
+
+/// Replaces every `[< ... >]` in `input`, including inside groups.
+pub fn expand(input: TokenStream) -> Result<TokenStream, (String, Span)> {
+    let mut output = Vec::new();
+    for token in input {
+        match token {
+            TokenTree::Group(group) => {
+                if group.delimiter() == Delimiter::Bracket
+                    && let Some(parts) = pasted_parts(&group)
+                {
+                    output.push(TokenTree::Ident(paste(&parts, group.span())?));
+                    continue;
+                }
+                let mut expanded = Group::new(group.delimiter(), expand(group.stream())?);
+                expanded.set_span(group.span());
+                output.push(TokenTree::Group(expanded));
+            }
+            token => output.push(token),
+        }
+    }
+    Ok(output.into_iter().collect())
+}
+
+/// Returns the tokens between `<` and `>` if the group is a `[< ... >]`.
+fn pasted_parts(group: &Group) -> Option<Vec<TokenTree>> {
+    let tokens: Vec<TokenTree> = group.stream().into_iter().collect();
+    match (tokens.first(), tokens.last()) {
+        (Some(TokenTree::Punct(open)), Some(TokenTree::Punct(close)))
+            if tokens.len() >= 2
+                && open.as_char() == '<'
+                && close.as_char() == '>'
+                && close.spacing() == Spacing::Alone =>
+        {
+            Some(tokens[1..tokens.len() - 1].to_vec())
+        }
+        _ => None,
+    }
+}
+
+/// Joins identifiers and literals into one identifier, dropping the `r#`
+/// prefix of raw identifiers and the quotes of string literals.
+fn paste(parts: &[TokenTree], span: Span) -> Result<Ident, (String, Span)> {
+    let mut name = String::new();
+    let mut ident_span = None;
+    push_parts(parts, &mut name, &mut ident_span)?;
+
+    if name.is_empty() {
+        return Err(("cannot paste an empty identifier".into(), span));
+    }
+    let span = ident_span.unwrap_or(span);
+    if name.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+        return Err((format!("`{}` is not a valid identifier", name), span));
+    }
+    Ok(Ident::new(&name, span))
+}
+
+fn push_parts(
+    parts: &[TokenTree],
+    name: &mut String,
+    ident_span: &mut Option<Span>,
+) -> Result<(), (String, Span)> {
+    for part in parts {
+        match part {
+            TokenTree::Ident(ident) => {
+                let text = ident.to_string();
+                name.push_str(text.strip_prefix("r#").unwrap_or(&text));
+                ident_span.get_or_insert(ident.span());
+            }
+            TokenTree::Literal(literal) => {
+                let text = literal.to_string();
+                name.push_str(text.trim_matches('"'));
+            }
+            // Fragments captured by `macro_rules!` arrive in invisible groups
+            TokenTree::Group(group) if group.delimiter() == Delimiter::None => {
+                let inner: Vec<TokenTree> = group.stream().into_iter().collect();
+                push_parts(&inner, name, ident_span)?;
+            }
+            other => {
+                return Err((
+                    format!("cannot paste `{}` into an identifier", other),
+                    other.span(),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
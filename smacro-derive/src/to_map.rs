@@ -0,0 +1,171 @@
+//! Struct parsing for the `ToMap` and `FromMap` derives.
+//!
+//! Only the parts needed by the derives are parsed: the struct name, its
+//! generics, its where clause and the names of its fields. Everything else
+//! is copied through as tokens.
+
+use proc_macro::{Delimiter, Spacing, TokenStream, TokenTree};
+
+/// The parts of a struct with named fields that the derives need.
+pub struct Struct {
+    pub name: String,
+    /// Generic parameters with their bounds but without defaults.
+    pub params: Vec<String>,
+    pub where_clause: String,
+    /// The identifier of each field, possibly a raw identifier like `r#type`.
+    pub fields: Vec<String>,
+}
+
+impl Struct {
+    /// Returns the generics for the `impl` keyword, e.g. `<'a, T: Display>`.
+    pub fn impl_generics(&self) -> String {
+        if self.params.is_empty() {
+            return String::new();
+        }
+        format!("<{}>", self.params.join(", "))
+    }
+
+    /// Returns the generics for the type, e.g. `<'a, T>`.
+    pub fn type_generics(&self) -> String {
+        if self.params.is_empty() {
+            return String::new();
+        }
+        let names: Vec<&str> = self
+            .params
+            .iter()
+            .map(|param| {
+                let name = param.split(':').next().unwrap_or(param).trim();
+                name.strip_prefix("const ").unwrap_or(name).trim()
+            })
+            .collect();
+        format!("<{}>", names.join(", "))
+    }
+}
+
+/// Parses the input of a derive, which must be a struct with named fields.
+pub fn parse(input: TokenStream) -> Result<Struct, String> {
+    let mut tokens = input.into_iter().peekable();
+
+    // Skip attributes and visibility up to the `struct` keyword
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "struct" => break,
+            Some(TokenTree::Ident(ident)) if ["enum", "union"].contains(&&*ident.to_string()) => {
+                return Err("can only be derived for structs".into());
+            }
+            Some(_) => {}
+            None => return Err("expected a struct".into()),
+        }
+    }
+
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(ident)) => ident.to_string(),
+        _ => return Err("expected a struct name".into()),
+    };
+
+    let mut params = Vec::new();
+    if matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '<') {
+        tokens.next();
+        let mut depth = 1;
+        let mut current = Vec::new();
+        let mut in_default = false;
+        let mut prev = None;
+        for token in tokens.by_ref() {
+            if let TokenTree::Punct(p) = &token {
+                match p.as_char() {
+                    '<' => depth += 1,
+                    '>' if !is_joint_dash(&prev) => {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    ',' if depth == 1 => {
+                        params.push(join(&current));
+                        current.clear();
+                        in_default = false;
+                        prev = None;
+                        continue;
+                    }
+                    // Defaults are only allowed on the struct, not on impls
+                    '=' if depth == 1 => in_default = true,
+                    _ => {}
+                }
+            }
+            if !in_default {
+                current.push(token.clone());
+            }
+            prev = Some(token);
+        }
+        if !current.is_empty() {
+            params.push(join(&current));
+        }
+    }
+
+    let mut where_tokens = Vec::new();
+    let body = loop {
+        match tokens.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => break group,
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+                return Err("can only be derived for structs with named fields".into());
+            }
+            Some(TokenTree::Punct(p)) if p.as_char() == ';' => {
+                return Err("can only be derived for structs with named fields".into());
+            }
+            Some(token) => where_tokens.push(token),
+            None => return Err("expected struct fields".into()),
+        }
+    };
+
+    Ok(Struct {
+        name,
+        params,
+        where_clause: join(&where_tokens),
+        fields: field_names(body.stream()),
+    })
+}
+
+/// Returns the names of the fields in the body of a struct.
+///
+/// A field name is the identifier right before a top-level `:` that is not
+/// part of a `::` path, with commas inside angle brackets ignored.
+fn field_names(body: TokenStream) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut expecting_name = true;
+    let mut last_ident = None;
+    let mut depth = 0usize;
+    let mut prev: Option<TokenTree> = None;
+
+    for token in body {
+        match &token {
+            TokenTree::Ident(ident) if expecting_name => last_ident = Some(ident.to_string()),
+            TokenTree::Punct(p) => match p.as_char() {
+                ':' if expecting_name && p.spacing() == Spacing::Alone && !is_colon(&prev) => {
+                    if let Some(name) = last_ident.take() {
+                        names.push(name);
+                    }
+                    expecting_name = false;
+                }
+                '<' => depth += 1,
+                '>' if !is_joint_dash(&prev) => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => expecting_name = true,
+                _ => {}
+            },
+            _ => {}
+        }
+        prev = Some(token);
+    }
+    names
+}
+
+fn is_colon(token: &Option<TokenTree>) -> bool {
+    matches!(token, Some(TokenTree::Punct(p)) if p.as_char() == ':')
+}
+
+fn is_joint_dash(token: &Option<TokenTree>) -> bool {
+    matches!(token, Some(TokenTree::Punct(p)) if p.as_char() == '-' && p.spacing() == Spacing::Joint)
+}
+
+fn join(tokens: &[TokenTree]) -> String {
+    tokens.iter().cloned().collect::<TokenStream>().to_string()
+}
@@ -0,0 +1,174 @@
+//! Type map utilities.
+//!
+//! Requires the `map` feature to be enabled.
+//!
+//! [`TypeMap`] stores at most one value per type, keyed by its [`TypeId`],
+//! the extension data pattern used by web frameworks and plugin systems. The
+//! `anymap!` macro builds one from a list of values.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A map holding at most one value of each type.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// Values are keyed by their [`TypeId`] and looked up by type, so no
+/// downcasting is needed at the call site. They must be `Send + Sync` so the
+/// map can be shared between threads.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::anymap::TypeMap;
+/// struct Port(u16);
+///
+/// let mut extensions = TypeMap::new();
+/// extensions.insert(Port(8080));
+/// extensions.insert("production");
+///
+/// assert_eq!(extensions.get::<Port>().map(|port| port.0), Some(8080));
+/// assert_eq!(extensions.get::<&str>(), Some(&"production"));
+/// assert!(extensions.get::<u32>().is_none());
+/// ```
+#[derive(Default)]
+pub struct TypeMap {
+    values: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl TypeMap {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a value under its type, returning the previous value of that type.
+    pub fn insert<T: Any + Send + Sync>(&mut self, value: T) -> Option<T> {
+        self.values
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|old| old.downcast().ok())
+            .map(|old| *old)
+    }
+
+    /// Returns a reference to the value of type `T`, if present.
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    /// Returns a mutable reference to the value of type `T`, if present.
+    pub fn get_mut<T: Any + Send + Sync>(&mut self) -> Option<&mut T> {
+        self.values
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_mut())
+    }
+
+    /// Removes and returns the value of type `T`, if present.
+    pub fn remove<T: Any + Send + Sync>(&mut self) -> Option<T> {
+        self.values
+            .remove(&TypeId::of::<T>())
+            .and_then(|value| value.downcast().ok())
+            .map(|value| *value)
+    }
+
+    /// Returns `true` if the map holds a value of type `T`.
+    pub fn contains<T: Any + Send + Sync>(&self) -> bool {
+        self.values.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Returns the number of values in the map.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the map holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl fmt::Debug for TypeMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypeMap")
+            .field("len", &self.values.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A convenience macro for creating a [`TypeMap`].
+///
+/// Requires the `map` feature to be enabled.
+///
+/// Each value is inserted under its own type, so values are later retrieved
+/// with [`TypeMap::get`]. If two values have the same type, the last one
+/// wins.
+///
+/// # Examples
+///
+/// ## Framework extensions
+/// ```
+/// # use smacro::anymap;
+/// #[derive(Debug, PartialEq)]
+/// struct Config {
+///     debug: bool,
+/// }
+///
+/// struct HttpClient;
+///
+/// let extensions = anymap![HttpClient, Config { debug: true }];
+///
+/// assert_eq!(extensions.len(), 2);
+/// assert!(extensions.contains::<HttpClient>());
+/// assert_eq!(extensions.get::<Config>(), Some(&Config { debug: true }));
+/// ```
+///
+/// ## Empty map
+/// ```
+/// # use smacro::anymap;
+/// let extensions = anymap![];
+///
+/// assert!(extensions.is_empty());
+/// ```
+#[macro_export]
+macro_rules! anymap {
+    () => {
+        $crate::anymap::TypeMap::new()
+    };
+    ($($value:expr),+ $(,)?) => {{
+        let mut map = $crate::anymap::TypeMap::new();
+        $(map.insert($value);)+
+        map
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TypeMap;
+
+    #[test]
+    fn insert_get_remove() {
+        let mut map = anymap![1u8, String::from("a")];
+        assert_eq!(map.insert(2u8), Some(1));
+        *map.get_mut::<String>().unwrap() += "b";
+        assert_eq!(map.get::<String>().map(String::as_str), Some("ab"));
+        assert_eq!(map.remove::<u8>(), Some(2));
+        assert_eq!(map.remove::<u8>(), None);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn last_value_of_a_type_wins() {
+        let map = anymap![1i32, 2i32,];
+        assert_eq!(map.get::<i32>(), Some(&2));
+        assert_eq!(format!("{map:?}"), "TypeMap { len: 1, .. }");
+    }
+
+    #[test]
+    fn empty() {
+        let map: TypeMap = anymap![];
+        assert!(map.is_empty());
+        assert!(!map.contains::<()>());
+    }
+}
@@ -0,0 +1,136 @@
+//! Async task spawning utilities.
+//!
+//! Requires the `tokio` feature to be enabled.
+//!
+//! The `aspawn!` macro clones a list of captured values (typically `Arc`s
+//! and channel senders) before spawning a `tokio` task, removing the
+//! `let state = state.clone();` preamble in front of every `tokio::spawn`.
+
+use std::future::Future;
+
+#[doc(hidden)]
+pub fn spawn<F>(future: F) -> tokio::task::JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::spawn(future)
+}
+
+/// A convenience macro for spawning `tokio` tasks with cloned captures.
+///
+/// Requires the `tokio` feature to be enabled.
+///
+/// Each listed variable is cloned and shadowed before the future is
+/// spawned, so the `async move` block takes ownership of the clones while
+/// the originals stay usable. The `JoinHandle` of the task is returned.
+///
+/// This macro must be called from within a `tokio` runtime.
+///
+/// # Examples
+///
+/// ## Spawning with shared state
+/// ```
+/// # use smacro::aspawn;
+/// use std::sync::{Arc, Mutex};
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let hits = Arc::new(Mutex::new(0));
+///
+/// let handle = aspawn!([hits] async move {
+///     *hits.lock().unwrap() += 1;
+/// });
+/// handle.await.unwrap();
+///
+/// // `hits` was cloned, so it is still available here
+/// assert_eq!(*hits.lock().unwrap(), 1);
+/// # });
+/// ```
+///
+/// ## Spawning with several captures
+/// ```
+/// # use smacro::aspawn;
+/// use std::sync::Arc;
+/// use tokio::sync::mpsc;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let config = Arc::new(String::from("prod"));
+/// let (tx, mut rx) = mpsc::unbounded_channel();
+///
+/// for id in 0..3 {
+///     aspawn!([config, tx] async move {
+///         tx.send(format!("{}-{}", config, id)).unwrap();
+///     });
+/// }
+/// drop(tx);
+///
+/// let mut messages = Vec::new();
+/// while let Some(message) = rx.recv().await {
+///     messages.push(message);
+/// }
+/// messages.sort();
+///
+/// assert_eq!(messages, vec!["prod-0", "prod-1", "prod-2"]);
+/// # });
+/// ```
+#[macro_export]
+macro_rules! aspawn {
+    ([$($capture:ident),* $(,)?] $future:expr $(,)?) => {
+        {
+            $(
+                let $capture = core::clone::Clone::clone(&$capture);
+            )*
+            $crate::aspawn::spawn($future)
+        }
+    };
+    ($future:expr $(,)?) => {
+        $crate::aspawn::spawn($future)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn spawn_without_captures() {
+        let handle = aspawn!(async { 40 + 2 });
+        assert_eq!(handle.await.unwrap(), 42);
+    }
+
+    #[tokio::test]
+    async fn spawn_clones_captures() {
+        let counter = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                aspawn!([counter] async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(counter.load(Ordering::SeqCst), 4);
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+
+    #[tokio::test]
+    async fn spawn_with_several_captures() {
+        let name = Arc::new("worker".to_string());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+        aspawn!([name, tx,] async move {
+            tx.send(name.len()).unwrap();
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(rx.recv().await, Some(6));
+        assert_eq!(name.as_str(), "worker");
+    }
+}
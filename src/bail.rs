@@ -0,0 +1,179 @@
+//! Early-return error utilities.
+//!
+//! The `bail!` and `ensure!` macros return early from functions returning
+//! `Result`, building the error from a format string the same way `s!`
+//! does. The resulting `String` is converted with `From`, so it works with
+//! `Result<_, String>`, `Result<_, Box<dyn Error>>` and any error type
+//! implementing `From<String>`.
+
+/// A convenience macro for returning early with a formatted error.
+///
+/// This macro provides two ways to return an error:
+/// - Format a message, converting the resulting `String` with `From`
+/// - Return an existing error value, converting it with `From`
+///
+/// # Examples
+///
+/// ## Returning a formatted message
+/// ```
+/// # use smacro::bail;
+/// fn parse_port(input: &str) -> Result<u16, String> {
+///     match input.parse() {
+///         Ok(port) => Ok(port),
+///         Err(_) => bail!("bad port: {}", input),
+///     }
+/// }
+///
+/// assert_eq!(parse_port("80"), Ok(80));
+/// assert_eq!(parse_port("http"), Err("bad port: http".to_string()));
+/// ```
+///
+/// ## Boxed errors
+/// ```
+/// # use smacro::bail;
+/// use std::error::Error;
+///
+/// fn check(name: &str) -> Result<(), Box<dyn Error>> {
+///     if name.is_empty() {
+///         bail!("name must not be empty");
+///     }
+///     Ok(())
+/// }
+///
+/// assert_eq!(check("").unwrap_err().to_string(), "name must not be empty");
+/// ```
+///
+/// ## Returning an error value
+/// ```
+/// # use smacro::bail;
+/// #[derive(Debug, PartialEq)]
+/// enum LoginError {
+///     Locked,
+/// }
+///
+/// fn login(attempts: u32) -> Result<(), LoginError> {
+///     if attempts > 3 {
+///         bail!(LoginError::Locked);
+///     }
+///     Ok(())
+/// }
+///
+/// assert_eq!(login(5), Err(LoginError::Locked));
+/// ```
+#[macro_export]
+macro_rules! bail {
+    ($fmt:literal $(, $($arg:tt)*)?) => {
+        return Err(core::convert::From::from(format!($fmt $(, $($arg)*)?)))
+    };
+    ($err:expr $(,)?) => {
+        return Err(core::convert::From::from($err))
+    };
+}
+
+/// A convenience macro for returning early with an error unless a condition holds.
+///
+/// `ensure!(cond, ...)` is equivalent to `if !cond { bail!(...) }`. When no
+/// message is given, the error describes the failed condition.
+///
+/// # Examples
+///
+/// ## Checking a condition with a message
+/// ```
+/// # use smacro::ensure;
+/// fn sqrt(n: f64) -> Result<f64, String> {
+///     ensure!(n >= 0.0, "must be positive, got {}", n);
+///     Ok(n.sqrt())
+/// }
+///
+/// assert_eq!(sqrt(9.0), Ok(3.0));
+/// assert_eq!(sqrt(-1.0), Err("must be positive, got -1".to_string()));
+/// ```
+///
+/// ## Checking a condition without a message
+/// ```
+/// # use smacro::ensure;
+/// fn halve(n: u32) -> Result<u32, String> {
+///     ensure!(n % 2 == 0);
+///     Ok(n / 2)
+/// }
+///
+/// assert_eq!(halve(3), Err("condition failed: `n % 2 == 0`".to_string()));
+/// ```
+#[macro_export]
+macro_rules! ensure {
+    ($cond:expr $(,)?) => {
+        if !$cond {
+            $crate::bail!("condition failed: `{}`", stringify!($cond));
+        }
+    };
+    ($cond:expr, $($arg:tt)+) => {
+        if !$cond {
+            $crate::bail!($($arg)+);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    #[derive(Debug, PartialEq)]
+    struct ConfigError(String);
+
+    impl From<String> for ConfigError {
+        fn from(message: String) -> Self {
+            ConfigError(message)
+        }
+    }
+
+    fn string_error(fail: bool) -> Result<u8, String> {
+        if fail {
+            bail!("failed with code {}", 7);
+        }
+        Ok(1)
+    }
+
+    fn boxed_error(value: i32) -> Result<i32, Box<dyn Error + Send + Sync>> {
+        ensure!(value > 0, "must be positive, got {value}");
+        Ok(value)
+    }
+
+    fn custom_error(port: u32) -> Result<u32, ConfigError> {
+        ensure!(port <= 65535, "port {} out of range", port);
+        ensure!(port != 0);
+        Ok(port)
+    }
+
+    #[test]
+    fn bail_with_format() {
+        assert_eq!(string_error(false), Ok(1));
+        assert_eq!(string_error(true), Err("failed with code 7".to_string()));
+    }
+
+    #[test]
+    fn bail_with_value() {
+        fn inner() -> Result<(), ConfigError> {
+            bail!(ConfigError("direct".to_string()));
+        }
+        assert_eq!(inner(), Err(ConfigError("direct".to_string())));
+    }
+
+    #[test]
+    fn ensure_with_boxed_error() {
+        assert_eq!(boxed_error(3).unwrap(), 3);
+        assert_eq!(boxed_error(-2).unwrap_err().to_string(), "must be positive, got -2");
+    }
+
+    #[test]
+    fn ensure_with_custom_error() {
+        assert_eq!(custom_error(80), Ok(80));
+        assert_eq!(
+            custom_error(70000),
+            Err(ConfigError("port 70000 out of range".to_string()))
+        );
+        assert_eq!(
+            custom_error(0),
+            Err(ConfigError("condition failed: `port != 0`".to_string()))
+        );
+    }
+}
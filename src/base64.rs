@@ -0,0 +1,224 @@
+//! Compile-time Base64 utilities.
+//!
+//! Requires the `base64` feature to be enabled.
+//!
+//! The `base64!` macro encodes or decodes string literals with the standard
+//! Base64 alphabet during constant evaluation, so invalid input is reported
+//! as a compile error and no work is left for runtime.
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[doc(hidden)]
+pub const fn encoded_len(len: usize) -> usize {
+    len.div_ceil(3) * 4
+}
+
+#[doc(hidden)]
+pub const fn encode<const N: usize>(input: &[u8]) -> [u8; N] {
+    let mut out = [b'='; N];
+    let mut i = 0;
+    let mut o = 0;
+
+    while i < input.len() {
+        let b0 = input[i] as u32;
+        let b1 = if i + 1 < input.len() {
+            input[i + 1] as u32
+        } else {
+            0
+        };
+        let b2 = if i + 2 < input.len() {
+            input[i + 2] as u32
+        } else {
+            0
+        };
+        let triple = (b0 << 16) | (b1 << 8) | b2;
+
+        out[o] = ALPHABET[(triple >> 18) as usize & 63];
+        out[o + 1] = ALPHABET[(triple >> 12) as usize & 63];
+        if i + 1 < input.len() {
+            out[o + 2] = ALPHABET[(triple >> 6) as usize & 63];
+        }
+        if i + 2 < input.len() {
+            out[o + 3] = ALPHABET[triple as usize & 63];
+        }
+
+        i += 3;
+        o += 4;
+    }
+    out
+}
+
+/// Returns the number of Base64 characters before the padding.
+const fn data_len(input: &[u8]) -> usize {
+    let mut len = input.len();
+    while len > 0 && input[len - 1] == b'=' {
+        len -= 1;
+    }
+    if input.len() - len > 2 || (len != input.len() && !input.len().is_multiple_of(4)) {
+        panic!("base64!: invalid padding");
+    }
+    if len % 4 == 1 {
+        panic!("base64!: truncated input");
+    }
+    len
+}
+
+#[doc(hidden)]
+pub const fn decoded_len(input: &[u8]) -> usize {
+    let len = data_len(input);
+    len / 4 * 3
+        + match len % 4 {
+            2 => 1,
+            3 => 2,
+            _ => 0,
+        }
+}
+
+const fn value(c: u8) -> u32 {
+    match c {
+        b'A'..=b'Z' => (c - b'A') as u32,
+        b'a'..=b'z' => (c - b'a') as u32 + 26,
+        b'0'..=b'9' => (c - b'0') as u32 + 52,
+        b'+' => 62,
+        b'/' => 63,
+        _ => panic!("base64!: invalid character in input"),
+    }
+}
+
+#[doc(hidden)]
+pub const fn decode<const N: usize>(input: &[u8]) -> [u8; N] {
+    let len = data_len(input);
+    let mut out = [0; N];
+    let mut acc = 0u32;
+    let mut bits = 0;
+    let mut i = 0;
+    let mut o = 0;
+
+    while i < len {
+        acc = (acc << 6) | value(input[i]);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out[o] = (acc >> bits) as u8;
+            acc &= (1 << bits) - 1;
+            o += 1;
+        }
+        i += 1;
+    }
+    out
+}
+
+#[doc(hidden)]
+pub const fn utf8(bytes: &[u8]) -> &str {
+    match core::str::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(_) => panic!("base64!: decoded data is not valid UTF-8"),
+    }
+}
+
+/// A convenience macro for encoding and decoding Base64 at compile time.
+///
+/// Requires the `base64` feature to be enabled.
+///
+/// This macro provides three ways to use a string literal:
+/// - Encode it, producing a `&'static str`
+/// - Decode it (`decode` prefix), producing a `[u8; N]`
+/// - Decode it as text (`decode str` prefix), producing a `&'static str`
+///
+/// Decoding accepts input with or without `=` padding. Everything happens
+/// during constant evaluation, so the macro can be used to initialize
+/// `const` and `static` items.
+///
+/// # Examples
+///
+/// ## Encoding
+/// ```
+/// # use smacro::base64;
+/// const AUTH: &str = base64!("user:secret");
+///
+/// assert_eq!(AUTH, "dXNlcjpzZWNyZXQ=");
+/// assert_eq!(base64!("hello"), "aGVsbG8=");
+/// ```
+///
+/// ## Decoding
+/// ```
+/// # use smacro::base64;
+/// let bytes = base64!(decode "AAEC/w==");
+/// let text = base64!(decode str "aGVsbG8");
+///
+/// assert_eq!(bytes, [0, 1, 2, 255]);
+/// assert_eq!(text, "hello");
+/// ```
+///
+/// ## Invalid input
+/// ```compile_fail
+/// # use smacro::base64;
+/// // `!` is not part of the Base64 alphabet
+/// let bytes = base64!(decode "aGVsbG8!");
+/// ```
+///
+/// # Panics
+///
+/// Invalid input never panics at runtime: the panic happens during constant
+/// evaluation and is reported as a compile error.
+#[macro_export]
+macro_rules! base64 {
+    (decode str $input:literal) => {{
+        const INPUT: &[u8] = $input.as_bytes();
+        const N: usize = $crate::base64::decoded_len(INPUT);
+        const BYTES: [u8; N] = $crate::base64::decode::<N>(INPUT);
+        const TEXT: &str = $crate::base64::utf8(&BYTES);
+        TEXT
+    }};
+    (decode $input:literal) => {{
+        const INPUT: &[u8] = $input.as_bytes();
+        const N: usize = $crate::base64::decoded_len(INPUT);
+        const BYTES: [u8; N] = $crate::base64::decode::<N>(INPUT);
+        BYTES
+    }};
+    ($input:literal) => {{
+        const INPUT: &[u8] = $input.as_bytes();
+        const N: usize = $crate::base64::encoded_len(INPUT.len());
+        const BYTES: [u8; N] = $crate::base64::encode::<N>(INPUT);
+        const TEXT: &str = $crate::base64::utf8(&BYTES);
+        TEXT
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn encode_padding() {
+        assert_eq!(base64!(""), "");
+        assert_eq!(base64!("f"), "Zg==");
+        assert_eq!(base64!("fo"), "Zm8=");
+        assert_eq!(base64!("foo"), "Zm9v");
+        assert_eq!(base64!("foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn encode_non_ascii() {
+        assert_eq!(base64!("é🦀"), "w6nwn6aA");
+    }
+
+    #[test]
+    fn decode_bytes() {
+        assert_eq!(base64!(decode ""), [0u8; 0]);
+        assert_eq!(base64!(decode "Zg=="), *b"f");
+        assert_eq!(base64!(decode "Zm8"), *b"fo");
+        assert_eq!(base64!(decode "+/+/"), [0xfb, 0xff, 0xbf]);
+    }
+
+    #[test]
+    fn decode_str_round_trip() {
+        const ENCODED: &str = base64!("round trip ✓");
+        assert_eq!(ENCODED, "cm91bmQgdHJpcCDinJM=");
+        assert_eq!(base64!(decode str "cm91bmQgdHJpcCDinJM="), "round trip ✓");
+    }
+
+    #[test]
+    fn usable_in_statics() {
+        static KEY: [u8; 3] = base64!(decode "AQID");
+        assert_eq!(KEY, [1, 2, 3]);
+    }
+}
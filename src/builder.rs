@@ -0,0 +1,193 @@
+//! Builder generation utilities.
+//!
+//! Requires the `derive` feature to be enabled.
+//!
+//! The `builder!` macro declares a struct together with a matching builder
+//! type, with chained setters, per-field defaults and a `build()` method that
+//! reports missing required fields.
+
+/// A convenience macro for declaring a struct with a builder.
+///
+/// Requires the `derive` feature to be enabled.
+///
+/// The macro declares the struct as written, with attributes and visibility,
+/// and generates a `<Name>Builder` type with:
+/// - A setter per field, named after the field and accepting anything that
+///   converts `Into` the field type
+/// - A `build()` method returning `Result<Name, String>`
+///
+/// Fields with `= default` are optional, and the default expression is
+/// evaluated by `build()` when the field was not set. Fields without one are
+/// required, and `build()` fails with ``missing field `name` `` if they were
+/// not set. The builder is created with `Name::builder()` or
+/// `NameBuilder::default()`. Generic structs are not supported.
+///
+/// # Examples
+///
+/// ## Required and optional fields
+/// ```
+/// # use smacro::{builder, s};
+/// use std::time::Duration;
+///
+/// builder!(
+///     #[derive(Debug)]
+///     pub Request {
+///         pub url: String,
+///         pub method: String = s!("GET"),
+///         pub timeout: Duration = Duration::from_secs(30),
+///     }
+/// );
+///
+/// let request = Request::builder()
+///     .url("https://example.com")
+///     .timeout(Duration::from_secs(5))
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(request.url, "https://example.com");
+/// assert_eq!(request.method, "GET");
+/// assert_eq!(request.timeout, Duration::from_secs(5));
+/// ```
+///
+/// ## Missing required fields
+/// ```
+/// # use smacro::builder;
+/// builder!(#[derive(Debug)] User {
+///     name: String,
+///     admin: bool = false,
+/// });
+///
+/// let error = UserBuilder::default().admin(true).build().unwrap_err();
+/// assert_eq!(error, "missing field `name`");
+/// ```
+#[macro_export]
+macro_rules! builder {
+    (@value $value:expr, $name:expr) => {
+        match $value {
+            core::option::Option::Some(value) => value,
+            core::option::Option::None => {
+                return core::result::Result::Err($crate::s!("missing field `{}`", $name));
+            }
+        }
+    };
+    (@value $value:expr, $name:expr, $default:expr) => {
+        match $value {
+            core::option::Option::Some(value) => value,
+            core::option::Option::None => $default,
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis $name:ident {
+            $($field_vis:vis $field:ident : $ty:ty $(= $default:expr)?),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $($field_vis $field: $ty),*
+        }
+
+        $crate::__paste! {
+            #[doc = concat!("A builder for [`", stringify!($name), "`].")]
+            #[derive(Default)]
+            $vis struct [<$name Builder>] {
+                $($field: core::option::Option<$ty>),*
+            }
+
+            impl $name {
+                #[doc = concat!("Returns a new [`", stringify!($name), "Builder`].")]
+                $vis fn builder() -> [<$name Builder>] {
+                    core::default::Default::default()
+                }
+            }
+
+            impl [<$name Builder>] {
+                $(
+                    #[doc = concat!("Sets `", stringify!($field), "`.")]
+                    $vis fn $field(mut self, $field: impl core::convert::Into<$ty>) -> Self {
+                        self.$field = core::option::Option::Some($field.into());
+                        self
+                    }
+                )*
+
+                #[doc = concat!("Builds the [`", stringify!($name), "`], filling in defaults.")]
+                ///
+                /// Fails if a field without a default was not set.
+                $vis fn build(self) -> core::result::Result<$name, std::string::String> {
+                    core::result::Result::Ok($name {
+                        $(
+                            $field: $crate::builder!(
+                                @value self.$field,
+                                stringify!($field).trim_start_matches("r#")
+                                $(, $default)?
+                            ),
+                        )*
+                    })
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    builder!(
+        #[derive(Debug, PartialEq)]
+        Request {
+            url: String,
+            method: String = crate::s!("GET"),
+            timeout: Duration = Duration::from_secs(30),
+            retries: u8 = 0,
+        }
+    );
+
+    builder!(pub(crate) Point { pub x: i32, pub y: i32 });
+
+    #[test]
+    fn defaults_fill_unset_fields() {
+        let request = Request::builder().url("/health").build().unwrap();
+        assert_eq!(
+            request,
+            Request {
+                url: "/health".to_string(),
+                method: "GET".to_string(),
+                timeout: Duration::from_secs(30),
+                retries: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn setters_override_defaults() {
+        let request = RequestBuilder::default()
+            .url("/upload")
+            .method("POST")
+            .timeout(Duration::from_millis(500))
+            .retries(3)
+            .build()
+            .unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.timeout, Duration::from_millis(500));
+        assert_eq!(request.retries, 3);
+    }
+
+    #[test]
+    fn missing_required_fields() {
+        assert_eq!(
+            Request::builder().method("PUT").build(),
+            Err("missing field `url`".to_string())
+        );
+        assert_eq!(
+            Point::builder().x(1).build().err(),
+            Some("missing field `y`".to_string())
+        );
+    }
+
+    #[test]
+    fn last_setter_wins() {
+        let p = Point::builder().x(1).y(2).x(3).build().unwrap();
+        assert_eq!((p.x, p.y), (3, 2));
+    }
+}
@@ -0,0 +1,137 @@
+//! Chunking utilities.
+//!
+//! The `chunked!` and `windows_vec!` macros split any iterable into owned
+//! `Vec`s of a fixed size, which works for iterators and owned values where
+//! slice methods like `chunks` and `windows` don't.
+
+#[doc(hidden)]
+pub fn chunked<I: IntoIterator>(items: I, size: usize) -> Vec<Vec<I::Item>> {
+    assert!(size > 0, "chunk size must be greater than zero");
+    let mut chunks = Vec::new();
+    let mut chunk = Vec::new();
+    for item in items {
+        chunk.push(item);
+        if chunk.len() == size {
+            chunks.push(std::mem::take(&mut chunk));
+        }
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+#[doc(hidden)]
+pub fn windows<I>(items: I, size: usize) -> Vec<Vec<I::Item>>
+where
+    I: IntoIterator,
+    I::Item: Clone,
+{
+    assert!(size > 0, "window size must be greater than zero");
+    let items: Vec<I::Item> = items.into_iter().collect();
+    items.windows(size).map(<[I::Item]>::to_vec).collect()
+}
+
+/// A convenience macro for splitting an iterable into chunks.
+///
+/// The elements are moved into `Vec`s of the given size, in order, and the
+/// result is a `Vec<Vec<T>>`. The last chunk is shorter when the number of
+/// elements is not a multiple of the size.
+///
+/// # Examples
+///
+/// ## Batching
+/// ```
+/// # use smacro::chunked;
+/// let ids = 1..=7;
+/// let batches = chunked!(ids, 3);
+///
+/// assert_eq!(batches, vec![vec![1, 2, 3], vec![4, 5, 6], vec![7]]);
+/// ```
+///
+/// ## Owned values
+/// ```
+/// # use smacro::chunked;
+/// let names = vec!["ada".to_string(), "alan".to_string(), "grace".to_string()];
+///
+/// for batch in chunked!(names, 2) {
+///     assert!(batch.len() <= 2);
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the size is zero.
+#[macro_export]
+macro_rules! chunked {
+    ($items:expr, $size:expr $(,)?) => {
+        $crate::chunk::chunked($items, $size)
+    };
+}
+
+/// A convenience macro for collecting the overlapping windows of an iterable.
+///
+/// Each window is a `Vec` of the given size starting one element after the
+/// previous one, so elements are cloned into every window containing them.
+/// The result is empty when there are fewer elements than the size.
+///
+/// # Examples
+///
+/// ## Pairs of neighbours
+/// ```
+/// # use smacro::windows_vec;
+/// let readings = [10, 12, 11, 15];
+/// let deltas: Vec<i32> = windows_vec!(readings, 2)
+///     .iter()
+///     .map(|w| w[1] - w[0])
+///     .collect();
+///
+/// assert_eq!(deltas, [2, -1, 4]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the size is zero.
+#[macro_export]
+macro_rules! windows_vec {
+    ($items:expr, $size:expr $(,)?) => {
+        $crate::chunk::windows($items, $size)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn exact_and_uneven_chunks() {
+        assert_eq!(chunked!(vec![1, 2, 3, 4], 2), [vec![1, 2], vec![3, 4]]);
+        assert_eq!(
+            chunked!("abcde".chars(), 2,),
+            [vec!['a', 'b'], vec!['c', 'd'], vec!['e']]
+        );
+        assert_eq!(chunked!(1..3, 5), [vec![1, 2]]);
+        assert_eq!(chunked!(vec![1, 2, 3], usize::MAX), [vec![1, 2, 3]]);
+    }
+
+    #[test]
+    fn empty_input() {
+        assert!(chunked!(Vec::<u8>::new(), 3).is_empty());
+        assert!(windows_vec!(Vec::<u8>::new(), 1).is_empty());
+        assert!(windows_vec!([1, 2], 3).is_empty());
+    }
+
+    #[test]
+    fn windows_overlap() {
+        let words = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        assert_eq!(
+            windows_vec!(&words, 2),
+            [vec![&words[0], &words[1]], vec![&words[1], &words[2]]]
+        );
+        assert_eq!(windows_vec!(0..3, 3), [vec![0, 1, 2]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be greater than zero")]
+    fn zero_size_panics() {
+        chunked!(0..10, 0);
+    }
+}
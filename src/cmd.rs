@@ -0,0 +1,299 @@
+//! Process utilities.
+//!
+//! The `cmd!` macro builds a `std::process::Command` with its arguments,
+//! environment and working directory in a single expression, and `sh!` runs
+//! a formatted command line and captures its output, for scripting-style
+//! tools.
+
+use std::process::Command;
+
+#[doc(hidden)]
+pub const fn captures_inline(line: &str) -> bool {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    while i + 1 < bytes.len() {
+        let next = bytes[i + 1];
+        if bytes[i] == b'{' {
+            if next == b'{' {
+                i += 1;
+            } else if next == b'_' || next.is_ascii_alphabetic() || !next.is_ascii() {
+                return true;
+            }
+        }
+        i += 1;
+    }
+    false
+}
+
+#[doc(hidden)]
+pub fn split(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word = None::<String>;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => words.extend(word.take()),
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err(format!("unterminated quote in `{}`", line)),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => word.push(c),
+                            Some(c) => word.extend(['\\', c]),
+                            None => return Err(format!("unterminated quote in `{}`", line)),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err(format!("unterminated quote in `{}`", line)),
+                    }
+                }
+            }
+            '\\' => word.get_or_insert_with(String::new).extend(chars.next()),
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+#[doc(hidden)]
+pub fn run(line: &str) -> Result<String, String> {
+    let words = split(line)?;
+    let Some((program, args)) = words.split_first() else {
+        return Err("empty command".to_string());
+    };
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("failed to run `{}`: {}", line, e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "`{}` failed with {}: {}",
+            line,
+            output.status,
+            stderr.trim_end()
+        ));
+    }
+    let mut stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    if stdout.ends_with('\n') {
+        stdout.pop();
+        if stdout.ends_with('\r') {
+            stdout.pop();
+        }
+    }
+    Ok(stdout)
+}
+
+/// A convenience macro for building a `std::process::Command`.
+///
+/// The program comes first, followed by its arguments, each of which can be
+/// anything implementing `AsRef<OsStr>`. Options can follow after a `;`:
+/// - `env: pairs` adds environment variables from any iterator of pairs,
+///   such as a map built with `map!`
+/// - `cwd: path` sets the working directory
+///
+/// The command is returned unstarted, so it can still be adjusted before
+/// calling `output`, `status` or `spawn`.
+///
+/// # Examples
+///
+/// ## Arguments
+/// ```
+/// # use smacro::{cmd, s};
+/// let version = "1.2.0";
+/// let command = cmd!("git", "commit", "-m", s!("release {}", version));
+///
+/// assert_eq!(command.get_program(), "git");
+/// assert_eq!(command.get_args().collect::<Vec<_>>(), ["commit", "-m", "release 1.2.0"]);
+/// ```
+///
+/// ## Environment and working directory
+/// ```
+/// # use smacro::cmd;
+/// use std::path::Path;
+///
+/// let repo = Path::new("/tmp");
+/// let command = cmd!("git", "status"; env: [("GIT_AUTHOR_NAME", "ci")], cwd: repo);
+///
+/// assert_eq!(command.get_current_dir(), Some(repo));
+/// assert_eq!(command.get_envs().count(), 1);
+/// ```
+#[macro_export]
+macro_rules! cmd {
+    (@option $command:ident, env, $value:expr) => {
+        $command.envs($value);
+    };
+    (@option $command:ident, cwd, $value:expr) => {
+        $command.current_dir($value);
+    };
+    ($program:expr $(, $arg:expr)* $(,)? $(; $($option:ident : $value:expr),* $(,)?)?) => {{
+        #[allow(unused_mut)]
+        let mut command = std::process::Command::new($program);
+        $(command.arg($arg);)*
+        $($($crate::cmd!(@option command, $option, $value);)*)?
+        command
+    }};
+}
+
+/// A convenience macro for running a command line and capturing its output.
+///
+/// The command line is a format string with `format!` arguments. It is split
+/// into words at whitespace, like a shell would, with single quotes, double
+/// quotes and backslashes to keep spaces inside a word. Arguments after the
+/// format string are quoted before being substituted, so values containing
+/// spaces or quotes stay a single word. Identifiers captured inline in the
+/// format string, such as `{path}`, could not be quoted and are rejected at
+/// compile time.
+///
+/// The program is run directly, without a shell, so pipes, redirections and
+/// globs are not supported. Returns the standard output as a `String`
+/// without its final newline, or an `Err(String)` with the exit status and
+/// standard error if the command could not be run or did not succeed.
+///
+/// # Examples
+///
+/// ## Capturing output
+/// ```
+/// # #[cfg(unix)]
+/// # {
+/// # use smacro::sh;
+/// let dir = "my files";
+/// let out = sh!("echo listing {} 'and more'", dir)?;
+///
+/// assert_eq!(out, "listing my files and more");
+/// # }
+/// # Ok::<(), String>(())
+/// ```
+///
+/// ## Failing commands
+/// ```
+/// # #[cfg(unix)]
+/// # {
+/// # use smacro::sh;
+/// let error = sh!("ls {}", "/does/not/exist").unwrap_err();
+///
+/// assert!(error.starts_with("`ls /does/not/exist` failed with exit status: "));
+/// # }
+/// ```
+///
+/// ## Inline captures
+/// ```compile_fail
+/// # use smacro::sh;
+/// let dir = "my files";
+/// let out = sh!("ls {dir}");
+/// ```
+#[macro_export]
+macro_rules! sh {
+    (@check $line:literal) => {
+        const {
+            assert!(
+                !$crate::cmd::captures_inline($line),
+                "sh! cannot quote inline captures, pass the values as arguments instead"
+            )
+        }
+    };
+    ($line:literal $(,)?) => {{
+        $crate::sh!(@check $line);
+        $crate::cmd::run(&format!($line))
+    }};
+    ($line:literal, $($arg:expr),+ $(,)?) => {{
+        $crate::sh!(@check $line);
+        $crate::cmd::run(&format!(
+            $line,
+            $($crate::s::escape_shell(&std::string::ToString::to_string(&$arg))),+
+        ))
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::ffi::OsStr;
+
+    #[test]
+    fn program_only() {
+        let command = cmd!("ls");
+        assert_eq!(command.get_program(), "ls");
+        assert_eq!(command.get_args().count(), 0);
+    }
+
+    #[test]
+    fn args_and_options() {
+        let env = HashMap::from([("A", "1")]);
+        let command = cmd!("echo", "a", String::from("b"),; cwd: "src", env: &env,);
+        assert_eq!(command.get_args().collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(command.get_current_dir(), Some("src".as_ref()));
+        assert_eq!(
+            command.get_envs().collect::<Vec<_>>(),
+            [(OsStr::new("A"), Some(OsStr::new("1")))]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn runs() {
+        let output = cmd!("sh", "-c", "echo $GREETING"; env: [("GREETING", "hi")])
+            .output()
+            .unwrap();
+        assert_eq!(output.stdout, b"hi\n");
+    }
+
+    use super::{captures_inline, split};
+    use crate::s::escape_shell;
+
+    #[test]
+    fn split_words() {
+        assert_eq!(
+            split(r#"  a 'b c'  "d \"e\" \n" f\ g''h "" "#).unwrap(),
+            ["a", "b c", "d \"e\" \\n", "f gh", ""]
+        );
+        assert!(split("a 'b").is_err());
+        assert!(split("\"a").is_err());
+    }
+
+    #[test]
+    fn quoted_round_trips() {
+        for value in ["plain-1.0", "two words", "it's", "", "a\"b", "a\\b\n"] {
+            let line = format!("cmd {}", escape_shell(value));
+            assert_eq!(split(&line).unwrap(), ["cmd", value]);
+        }
+    }
+
+    #[test]
+    fn inline_captures_are_detected() {
+        assert!(captures_inline("rm {path}"));
+        assert!(captures_inline("rm {_path:?}"));
+        assert!(!captures_inline("rm {} {0} {:>4} {{path}}"));
+        assert!(!captures_inline("echo {{}}"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn sh_output_and_errors() {
+        assert_eq!(sh!("printf {}", "a\nb\n"), Ok("a\nb".to_string()));
+        assert_eq!(sh!("printf [%s] {}", "a b"), Ok("[a b]".to_string()));
+        assert_eq!(sh!("printf {{}}"), Ok("{}".to_string()));
+        assert_eq!(sh!("   "), Err("empty command".to_string()));
+        assert!(
+            sh!("sh -c 'echo oops >&2; exit 3'")
+                .unwrap_err()
+                .ends_with("exit status: 3: oops")
+        );
+        assert!(
+            sh!("smacro-no-such-program")
+                .unwrap_err()
+                .starts_with("failed to run `smacro-no-such-program`: ")
+        );
+    }
+}
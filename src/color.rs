@@ -0,0 +1,150 @@
+//! Terminal color utilities.
+//!
+//! Requires the `color` feature to be enabled.
+//!
+//! The `cs!` macro wraps a formatted string in ANSI escape codes, resetting
+//! the style at the end. Colors are only emitted when standard output is a
+//! terminal and the `NO_COLOR` environment variable is not set, so piped
+//! output and log files stay clean.
+
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+/// The SGR codes of the styles accepted by [`cs!`](crate::cs).
+#[doc(hidden)]
+#[allow(non_upper_case_globals)]
+pub mod code {
+    pub const bold: &str = "1";
+    pub const dim: &str = "2";
+    pub const italic: &str = "3";
+    pub const underline: &str = "4";
+    pub const reverse: &str = "7";
+    pub const strikethrough: &str = "9";
+
+    pub const black: &str = "30";
+    pub const red: &str = "31";
+    pub const green: &str = "32";
+    pub const yellow: &str = "33";
+    pub const blue: &str = "34";
+    pub const magenta: &str = "35";
+    pub const cyan: &str = "36";
+    pub const white: &str = "37";
+
+    pub const bright_black: &str = "90";
+    pub const bright_red: &str = "91";
+    pub const bright_green: &str = "92";
+    pub const bright_yellow: &str = "93";
+    pub const bright_blue: &str = "94";
+    pub const bright_magenta: &str = "95";
+    pub const bright_cyan: &str = "96";
+    pub const bright_white: &str = "97";
+
+    pub const on_black: &str = "40";
+    pub const on_red: &str = "41";
+    pub const on_green: &str = "42";
+    pub const on_yellow: &str = "43";
+    pub const on_blue: &str = "44";
+    pub const on_magenta: &str = "45";
+    pub const on_cyan: &str = "46";
+    pub const on_white: &str = "47";
+}
+
+/// Returns whether colors should be emitted.
+///
+/// This is `true` when standard output is a terminal and `NO_COLOR` is unset
+/// or empty. The check is done once and cached for the rest of the program.
+pub fn enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        !no_color && std::io::stdout().is_terminal()
+    })
+}
+
+#[doc(hidden)]
+pub fn paint(codes: &[&str], text: String) -> String {
+    if enabled() { style(codes, &text) } else { text }
+}
+
+#[doc(hidden)]
+pub fn style(codes: &[&str], text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 4 * codes.len() + 6);
+    out.push_str("\x1b[");
+    out.push_str(&codes.join(";"));
+    out.push('m');
+    out.push_str(text);
+    out.push_str("\x1b[0m");
+    out
+}
+
+/// A convenience macro for creating colored and styled strings.
+///
+/// Requires the `color` feature to be enabled.
+///
+/// One or more styles are listed before a `;`, followed by anything `s!`
+/// accepts. The result is a `String` wrapped in the matching ANSI escape
+/// codes and ending with a reset, or the plain string when colors are
+/// disabled (see [`enabled`](crate::color::enabled)).
+///
+/// Available styles:
+/// - Colors: `black`, `red`, `green`, `yellow`, `blue`, `magenta`, `cyan`, `white`
+/// - Bright colors: `bright_red`, `bright_blue`, ... for each color
+/// - Backgrounds: `on_red`, `on_blue`, ... for each color
+/// - Attributes: `bold`, `dim`, `italic`, `underline`, `reverse`, `strikethrough`
+///
+/// An unknown style name is a compile error.
+///
+/// # Examples
+///
+/// ## Styled messages
+/// ```
+/// # use smacro::cs;
+/// let msg = "disk full";
+///
+/// let error = cs!(red bold; "error: {}", msg);
+/// let note = cs!(dim; "retrying in {}s", 5);
+///
+/// // Without a terminal the text is left untouched
+/// assert!(error.contains("error: disk full"));
+/// println!("{} ({})", error, note);
+/// ```
+#[macro_export]
+macro_rules! cs {
+    ($($style:ident)+; $($arg:tt)+) => {
+        $crate::color::paint(&[$($crate::color::code::$style),+], $crate::s!($($arg)+))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{code, style};
+
+    #[test]
+    fn style_wraps_and_resets() {
+        assert_eq!(style(&[code::red], "x"), "\x1b[31mx\x1b[0m");
+    }
+
+    #[test]
+    fn style_combines_codes() {
+        assert_eq!(
+            style(&[code::bold, code::bright_green, code::on_black], "ok"),
+            "\x1b[1;92;40mok\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn cs_formats_text() {
+        let s = cs!(yellow; "{} warnings", 3);
+        assert!(s.contains("3 warnings"));
+        if !super::enabled() {
+            assert_eq!(s, "3 warnings");
+        }
+    }
+
+    #[test]
+    fn cs_accepts_any_s_input() {
+        let n = 42;
+        assert!(cs!(cyan underline; n).contains("42"));
+        assert!(cs!(blue; "plain").contains("plain"));
+    }
+}
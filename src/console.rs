@@ -0,0 +1,375 @@
+//! Console utilities.
+//!
+//! The `prompt!` and `read_line!` macros cover the interactive input
+//! boilerplate of command line tools: printing a prompt, flushing stdout,
+//! reading a line, trimming it and parsing it. The `infoln!`, `warnln!`
+//! and `errln!` macros form a zero-dependency logging layer for small
+//! binaries, writing leveled messages to stderr, and `progress!` and
+//! `finish!` keep a single progress line updated in place.
+
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[doc(hidden)]
+pub fn progress_to(writer: &mut impl Write, message: &str, done: bool) -> io::Result<()> {
+    write!(writer, "\r{}\x1b[K", message)?;
+    if done {
+        writeln!(writer)?;
+    }
+    writer.flush()
+}
+
+#[doc(hidden)]
+pub fn progress(message: &str, done: bool) {
+    // Progress output is best effort and must not abort the work it reports on
+    let _ = progress_to(&mut io::stderr().lock(), message, done);
+}
+
+#[doc(hidden)]
+pub fn progress_newline() {
+    eprintln!();
+}
+
+#[doc(hidden)]
+pub fn utc_timestamp(secs: u64) -> String {
+    let (days, rest) = (secs / 86_400, secs % 86_400);
+    let z = days + 719_468;
+    let (era, doe) = (z / 146_097, z % 146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rest / 3_600,
+        rest % 3_600 / 60,
+        rest % 60
+    )
+}
+
+#[doc(hidden)]
+pub fn log_line(level: &str, module: &str, timestamp: Option<u64>, message: &str) -> String {
+    match timestamp {
+        Some(secs) => format!("{} [{} {}] {}", utc_timestamp(secs), level, module, message),
+        None => format!("[{} {}] {}", level, module, message),
+    }
+}
+
+#[doc(hidden)]
+pub fn log(level: &str, module: &str, timestamp: bool, message: String) {
+    let now = timestamp.then(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs())
+    });
+    eprintln!("{}", log_line(level, module, now, &message));
+}
+
+#[doc(hidden)]
+pub fn read_line_from(reader: &mut impl BufRead) -> io::Result<String> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "end of input"));
+    }
+    Ok(line.trim().to_string())
+}
+
+#[doc(hidden)]
+pub fn prompt_from(
+    reader: &mut impl BufRead,
+    writer: &mut impl Write,
+    message: &str,
+) -> io::Result<String> {
+    writer.write_all(message.as_bytes())?;
+    writer.flush()?;
+    read_line_from(reader)
+}
+
+#[doc(hidden)]
+pub fn parse<T>(input: io::Result<String>) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let input = input.map_err(|e| format!("failed to read input: {}", e))?;
+    input
+        .parse()
+        .map_err(|e| format!("invalid input {:?}: {}", input, e))
+}
+
+#[doc(hidden)]
+pub fn prompt(message: &str) -> io::Result<String> {
+    prompt_from(&mut io::stdin().lock(), &mut io::stdout().lock(), message)
+}
+
+#[doc(hidden)]
+pub fn expect(input: io::Result<String>) -> String {
+    input.unwrap_or_else(|e| panic!("failed to read from stdin: {}", e))
+}
+
+/// A convenience macro for reading a trimmed line from stdin.
+///
+/// Returns an `io::Result<String>` with surrounding whitespace, including
+/// the line ending, removed. Reaching the end of input is reported as an
+/// `UnexpectedEof` error rather than an empty line, so input loops end when
+/// stdin is closed.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use smacro::read_line;
+/// while let Ok(line) = read_line!() {
+///     println!("got {}", line);
+/// }
+/// ```
+#[macro_export]
+macro_rules! read_line {
+    () => {
+        $crate::console::read_line_from(&mut std::io::stdin().lock())
+    };
+}
+
+/// A convenience macro for prompting the user for a line of input.
+///
+/// The prompt accepts anything `s!` does. It is printed to stdout without a
+/// newline, stdout is flushed, and a line is read from stdin and trimmed.
+///
+/// This macro provides two forms:
+/// - Return the answer as a `String`
+/// - Parse the answer into any `FromStr` type with `parse;`, returning a
+///   `Result<T, String>` describing invalid input
+///
+/// # Panics
+///
+/// The `String` form panics if stdin cannot be read or is already closed.
+///
+/// # Examples
+///
+/// ## Reading a string
+/// ```no_run
+/// # use smacro::prompt;
+/// let name: String = prompt!("Your name: ");
+/// println!("Hello, {}!", name);
+/// ```
+///
+/// ## Parsing the answer
+/// ```no_run
+/// # use smacro::prompt;
+/// # fn main() -> Result<(), String> {
+/// let max = 120;
+/// let age: u32 = prompt!(parse; "Age (0-{}): ", max)?;
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! prompt {
+    (parse; $($message:tt)+) => {
+        $crate::console::parse($crate::console::prompt(&$crate::s!($($message)+)))
+    };
+    ($($message:tt)+) => {
+        $crate::console::expect($crate::console::prompt(&$crate::s!($($message)+)))
+    };
+}
+
+/// A convenience macro for writing an informational message to stderr.
+///
+/// The message accepts anything `s!` does. It is written to stderr as a
+/// single line, prefixed with the level and the module path of the call
+/// site, as in `[INFO my_app::db] message`. With the `time;` prefix, the
+/// line also starts with the current UTC time in RFC 3339 format.
+///
+/// See also [`warnln!`](crate::warnln) and [`errln!`](crate::errln).
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::infoln;
+/// let files = 12;
+///
+/// // [INFO my_app] indexed 12 files
+/// infoln!("indexed {} files", files);
+///
+/// // 2024-05-01T09:30:00Z [INFO my_app] done
+/// infoln!(time; "done");
+/// ```
+#[macro_export]
+macro_rules! infoln {
+    (time; $($message:tt)+) => {
+        $crate::console::log("INFO", module_path!(), true, $crate::s!($($message)+))
+    };
+    ($($message:tt)+) => {
+        $crate::console::log("INFO", module_path!(), false, $crate::s!($($message)+))
+    };
+}
+
+/// A convenience macro for writing a warning to stderr.
+///
+/// The message accepts anything `s!` does. It is written to stderr as a
+/// single line, prefixed with the level and the module path of the call
+/// site, as in `[WARN my_app::db] message`. With the `time;` prefix, the
+/// line also starts with the current UTC time in RFC 3339 format.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::warnln;
+/// let usage = 91;
+///
+/// // [WARN my_app] disk usage at 91%
+/// warnln!("disk usage at {}%", usage);
+/// ```
+#[macro_export]
+macro_rules! warnln {
+    (time; $($message:tt)+) => {
+        $crate::console::log("WARN", module_path!(), true, $crate::s!($($message)+))
+    };
+    ($($message:tt)+) => {
+        $crate::console::log("WARN", module_path!(), false, $crate::s!($($message)+))
+    };
+}
+
+/// A convenience macro for writing an error message to stderr.
+///
+/// The message accepts anything `s!` does. It is written to stderr as a
+/// single line, prefixed with the level and the module path of the call
+/// site, as in `[ERROR my_app::db] message`. With the `time;` prefix, the
+/// line also starts with the current UTC time in RFC 3339 format.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::errln;
+/// let path = "config.toml";
+///
+/// // 2024-05-01T09:30:00Z [ERROR my_app] could not read config.toml
+/// errln!(time; "could not read {}", path);
+/// ```
+#[macro_export]
+macro_rules! errln {
+    (time; $($message:tt)+) => {
+        $crate::console::log("ERROR", module_path!(), true, $crate::s!($($message)+))
+    };
+    ($($message:tt)+) => {
+        $crate::console::log("ERROR", module_path!(), false, $crate::s!($($message)+))
+    };
+}
+
+/// A convenience macro for updating a single progress line on stderr.
+///
+/// The message accepts anything `s!` does. Each call returns the cursor to
+/// the start of the line with `\r`, writes the message and clears what is
+/// left of the previous one, so repeated calls rewrite the same terminal
+/// line. Call [`finish!`](crate::finish) once the work is done to move on
+/// to the next line.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::{finish, progress};
+/// let files = ["a.txt", "b.txt", "c.txt"];
+///
+/// for (done, file) in files.iter().enumerate() {
+///     progress!("{}/{} files, processing {}", done + 1, files.len(), file);
+/// }
+/// finish!("processed {} files", files.len());
+/// ```
+#[macro_export]
+macro_rules! progress {
+    ($($message:tt)+) => {
+        $crate::console::progress(&$crate::s!($($message)+), false)
+    };
+}
+
+/// A convenience macro for ending a progress line started with [`progress!`](crate::progress).
+///
+/// Without arguments, the last progress message is kept and a newline is
+/// written. With a message, accepting anything `s!` does, the progress line
+/// is replaced by the final message first.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::{finish, progress};
+/// progress!("downloading");
+/// finish!();
+///
+/// progress!("{}%", 50);
+/// finish!("done");
+/// ```
+#[macro_export]
+macro_rules! finish {
+    () => {
+        $crate::console::progress_newline()
+    };
+    ($($message:tt)+) => {
+        $crate::console::progress(&$crate::s!($($message)+), true)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{log_line, parse, progress_to, prompt_from, read_line_from, utc_timestamp};
+    use std::io::{Cursor, ErrorKind};
+
+    #[test]
+    fn reads_trimmed_lines() {
+        let mut input = Cursor::new("  alice \r\n\n");
+        assert_eq!(read_line_from(&mut input).unwrap(), "alice");
+        assert_eq!(read_line_from(&mut input).unwrap(), "");
+        assert_eq!(
+            read_line_from(&mut input).unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn prompts_and_parses() {
+        let mut output = Vec::new();
+        let answer = prompt_from(&mut Cursor::new("42\nx\n"), &mut output, "Age: ");
+        assert_eq!(output, b"Age: ");
+        assert_eq!(parse::<u32>(answer), Ok(42));
+        assert_eq!(
+            parse::<u32>(Ok("x".to_string())),
+            Err("invalid input \"x\": invalid digit found in string".to_string())
+        );
+    }
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(utc_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(utc_timestamp(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(utc_timestamp(1_714_555_800), "2024-05-01T09:30:00Z");
+        assert_eq!(utc_timestamp(4_102_444_799), "2099-12-31T23:59:59Z");
+    }
+
+    #[test]
+    fn log_lines() {
+        assert_eq!(
+            log_line("WARN", "app::db", None, "slow"),
+            "[WARN app::db] slow"
+        );
+        assert_eq!(
+            log_line("INFO", "app", Some(0), "up"),
+            "1970-01-01T00:00:00Z [INFO app] up"
+        );
+        infoln!("plain {}", 1);
+        warnln!(time; "timed");
+        errln!(upper "shouting");
+    }
+
+    #[test]
+    fn progress_lines() {
+        let mut output = Vec::new();
+        progress_to(&mut output, "1/2", false).unwrap();
+        progress_to(&mut output, "2/2", false).unwrap();
+        progress_to(&mut output, "done", true).unwrap();
+        assert_eq!(output, b"\r1/2\x1b[K\r2/2\x1b[K\rdone\x1b[K\n");
+    }
+}
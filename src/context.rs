@@ -0,0 +1,248 @@
+//! Structured context utilities.
+//!
+//! The `context!` macro builds a [`Context`], an ordered list of key-value
+//! pairs with the `map!` literal syntax, which formats as `key=value` pairs
+//! for log lines. The `attach!` macro appends a context to the error of a
+//! `Result`, so failures carry the identifiers needed to track them down.
+
+use std::fmt;
+
+/// An ordered list of key-value pairs describing what was being done.
+///
+/// Values are stored formatted, so anything implementing `Display` can be
+/// attached. Keys keep their insertion order, and inserting an existing key
+/// replaces its value in place.
+///
+/// A context displays in logfmt style, as space-separated `key=value`
+/// pairs, quoting values that are empty or contain spaces, `=` or quotes.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::context::Context;
+/// let mut context = Context::new();
+/// context.insert("request_id", 42);
+/// context.insert("path", "/api/users list");
+///
+/// assert_eq!(context.get("request_id"), Some("42"));
+/// assert_eq!(context.to_string(), "request_id=42 path=\"/api/users list\"");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Context {
+    entries: Vec<(String, String)>,
+}
+
+impl Context {
+    /// Creates an empty context.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets `key` to the formatted `value`, replacing any previous value.
+    pub fn insert(&mut self, key: impl Into<String>, value: impl fmt::Display) {
+        let key = key.into();
+        let value = value.to_string();
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => *v = value,
+            None => self.entries.push((key, value)),
+        }
+    }
+
+    /// Returns the value stored under `key`, if any.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Inserts every pair of `other`, replacing values of existing keys.
+    pub fn merge(&mut self, other: Context) {
+        for (key, value) in other.entries {
+            self.insert(key, value);
+        }
+    }
+
+    /// Returns an iterator over the pairs in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Returns the number of pairs.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the context holds no pairs.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl fmt::Display for Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.entries.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            let quote = value.is_empty()
+                || value.contains(|c: char| c.is_whitespace() || c == '=' || c == '"');
+            if quote {
+                write!(f, "{key}={value:?}")?;
+            } else {
+                write!(f, "{key}={value}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[doc(hidden)]
+pub fn attach<T, E: fmt::Display>(
+    result: Result<T, E>,
+    context: impl FnOnce() -> Context,
+) -> Result<T, String> {
+    result.map_err(|e| {
+        let context = context();
+        if context.is_empty() {
+            e.to_string()
+        } else {
+            format!("{} [{}]", e, context)
+        }
+    })
+}
+
+/// A convenience macro for creating a [`Context`].
+///
+/// This macro provides three ways to create a context:
+/// - Create an empty context
+/// - Create a context from `key => value` pairs, as with `map!`
+/// - Create a context with identifier keys, written like a struct literal
+///
+/// Keys are converted with `Into<String>` and values are formatted with
+/// `Display`.
+///
+/// # Examples
+///
+/// ## Context for a log line
+/// ```
+/// # use smacro::context;
+/// let (id, user) = (42, "alice");
+/// let context = context!["request_id" => id, "user" => user];
+///
+/// assert_eq!(format!("request failed {context}"), "request failed request_id=42 user=alice");
+/// ```
+///
+/// ## Identifier keys
+/// ```
+/// # use smacro::context;
+/// let context = context! { attempt: 3, host: "db-1" };
+///
+/// assert_eq!(context.get("host"), Some("db-1"));
+/// assert_eq!(context.len(), 2);
+/// ```
+#[macro_export]
+macro_rules! context {
+    () => {
+        $crate::context::Context::new()
+    };
+    ($($key:ident : $value:expr),+ $(,)?) => {
+        $crate::context!($(stringify!($key) => $value),+)
+    };
+    ($($key:expr => $value:expr),+ $(,)?) => {{
+        let mut context = $crate::context::Context::new();
+        $(context.insert($key, $value);)+
+        context
+    }};
+}
+
+/// A convenience macro for attaching a [`Context`] to the error of a `Result`.
+///
+/// This macro provides two ways to attach context:
+/// - Attach an existing `Context` value
+/// - Attach pairs written as in [`context!`](macro@crate::context)
+///
+/// On error, the error's `Display` output is followed by the context in
+/// brackets, producing a `Result<T, String>`. The context is only built if
+/// the result is an error, and an empty context leaves the message as is.
+///
+/// # Examples
+///
+/// ## Attaching pairs
+/// ```
+/// # use smacro::attach;
+/// let user = "alice";
+/// let result = attach!("x".parse::<u32>(), "field" => "age", "user" => user);
+///
+/// assert_eq!(result, Err("invalid digit found in string [field=age user=alice]".to_string()));
+/// ```
+///
+/// ## Attaching an existing context
+/// ```
+/// # use smacro::{attach, context};
+/// let request = context! { request_id: 7 };
+///
+/// assert_eq!(attach!("12".parse::<u32>(), request.clone()), Ok(12));
+/// assert_eq!(
+///     attach!("".parse::<u32>(), request),
+///     Err("cannot parse integer from empty string [request_id=7]".to_string())
+/// );
+/// ```
+#[macro_export]
+macro_rules! attach {
+    ($result:expr, $($key:ident : $value:expr),+ $(,)?) => {
+        $crate::context::attach($result, || $crate::context!($($key: $value),+))
+    };
+    ($result:expr, $($key:expr => $value:expr),+ $(,)?) => {
+        $crate::context::attach($result, || $crate::context!($($key => $value),+))
+    };
+    ($result:expr, $context:expr $(,)?) => {
+        $crate::context::attach($result, || $context)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Context;
+
+    #[test]
+    fn insert_replaces_in_place() {
+        let mut context = context!["a" => 1, "b" => 2];
+        context.insert("a", 3);
+        context.merge(context! { c: "x", b: 4 });
+        assert_eq!(
+            context.iter().collect::<Vec<_>>(),
+            [("a", "3"), ("b", "4"), ("c", "x")]
+        );
+    }
+
+    #[test]
+    fn display_quotes_values() {
+        let context =
+            context!["empty" => "", "eq" => "a=b", "quote" => "say \"hi\"", "plain" => 1.5];
+        assert_eq!(
+            context.to_string(),
+            r#"empty="" eq="a=b" quote="say \"hi\"" plain=1.5"#
+        );
+        assert_eq!(Context::new().to_string(), "");
+    }
+
+    #[test]
+    fn attach_is_lazy() {
+        let mut built = false;
+        let result = attach!(Ok::<_, String>(1), {
+            built = true;
+            context![]
+        });
+        assert_eq!(result, Ok(1));
+        assert!(!built);
+        assert_eq!(
+            attach!(Err::<(), _>("failed"), context![]),
+            Err("failed".to_string())
+        );
+        assert_eq!(
+            attach!(Err::<(), _>("failed"), id: 3,),
+            Err("failed [id=3]".to_string())
+        );
+    }
+}
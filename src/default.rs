@@ -0,0 +1,129 @@
+//! Default value utilities.
+//!
+//! The `default!` macro is a shorthand for `Default::default()`, and can also
+//! build a struct from a few explicit fields, filling in the rest with
+//! struct-update syntax.
+
+/// A convenience macro for creating default values.
+///
+/// This macro provides three ways to create a value:
+/// - An inferred `Default::default()`
+/// - The default value of a named type
+/// - A struct literal with some fields set and the others defaulted
+///
+/// # Examples
+///
+/// ## Inferred default
+/// ```
+/// # use smacro::default;
+/// let count: u32 = default!();
+/// let name: String = default!();
+///
+/// assert_eq!(count, 0);
+/// assert!(name.is_empty());
+/// ```
+///
+/// ## Default of a named type
+/// ```
+/// # use smacro::default;
+/// let buffer = default!(Vec<u8>);
+/// assert!(buffer.is_empty());
+/// ```
+///
+/// ## Struct with some fields overridden
+/// ```
+/// # use smacro::default;
+/// #[derive(Default)]
+/// struct Config {
+///     host: String,
+///     port: u16,
+///     debug: bool,
+/// }
+///
+/// let debug = true;
+/// let config = default!(Config { port: 9000, debug });
+///
+/// assert_eq!(config.host, "");
+/// assert_eq!(config.port, 9000);
+/// assert!(config.debug);
+/// ```
+///
+/// The struct form expands to
+/// `Config { port: 9000, debug, ..Default::default() }`, so field init
+/// shorthand and trailing commas are supported.
+#[macro_export]
+macro_rules! default {
+    () => {
+        core::default::Default::default()
+    };
+    ($($name:ident)::+ { $($field:ident $(: $value:expr)?),* $(,)? }) => {
+        $($name)::+ {
+            $($field $(: $value)?,)*
+            ..core::default::Default::default()
+        }
+    };
+    ($t:ty) => {
+        <$t as core::default::Default>::default()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[derive(Debug, Default, PartialEq)]
+    struct Config {
+        host: String,
+        port: u16,
+        retries: u8,
+    }
+
+    #[test]
+    fn inferred_default() {
+        let n: i64 = default!();
+        let v: Vec<String> = default!();
+        assert_eq!(n, 0);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn named_type_default() {
+        assert_eq!(default!(u8), 0);
+        assert_eq!(default!(Option<i32>), None);
+        assert_eq!(default!(Config), Config::default());
+    }
+
+    #[test]
+    fn struct_with_overrides() {
+        let c = default!(Config { port: 9000 });
+        assert_eq!(c.port, 9000);
+        assert_eq!(c.host, "");
+        assert_eq!(c.retries, 0);
+    }
+
+    #[test]
+    fn struct_with_shorthand_and_trailing_comma() {
+        let host = "localhost".to_string();
+        let c = default!(Config { host, retries: 3, });
+        assert_eq!(
+            c,
+            Config {
+                host: "localhost".to_string(),
+                port: 0,
+                retries: 3
+            }
+        );
+    }
+
+    #[test]
+    fn struct_with_path() {
+        mod inner {
+            #[derive(Default)]
+            pub struct Point {
+                pub x: i32,
+                pub y: i32,
+            }
+        }
+
+        let p = default!(inner::Point { y: 4 });
+        assert_eq!((p.x, p.y), (0, 4));
+    }
+}
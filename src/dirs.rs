@@ -0,0 +1,143 @@
+//! User directory utilities.
+//!
+//! Requires the `dirs` feature to be enabled.
+//!
+//! The `home!`, `config_dir!` and `cache_dir!` macros look up the user's
+//! home, configuration and cache directories for the current platform,
+//! using the `dirs` crate, and join them with an `s!` formatted subpath.
+
+use std::path::PathBuf;
+
+#[doc(hidden)]
+pub fn home() -> Option<PathBuf> {
+    ::dirs::home_dir()
+}
+
+#[doc(hidden)]
+pub fn config() -> Option<PathBuf> {
+    ::dirs::config_dir()
+}
+
+#[doc(hidden)]
+pub fn cache() -> Option<PathBuf> {
+    ::dirs::cache_dir()
+}
+
+#[doc(hidden)]
+pub fn join(base: Option<PathBuf>, subpath: String) -> Option<PathBuf> {
+    base.map(|base| base.join(subpath))
+}
+
+/// A convenience macro for the user's home directory.
+///
+/// Requires the `dirs` feature to be enabled.
+///
+/// Returns the home directory as an `Option<PathBuf>`, `None` if it cannot
+/// be determined. A subpath can be given, accepting anything `s!` does, and
+/// is joined onto the directory.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::home;
+/// let name = "notes";
+///
+/// if let (Some(home), Some(notes)) = (home!(), home!(".{}/today.md", name)) {
+///     assert_eq!(notes, home.join(".notes/today.md"));
+/// }
+/// ```
+#[macro_export]
+macro_rules! home {
+    () => {
+        $crate::dirs::home()
+    };
+    ($($subpath:tt)+) => {
+        $crate::dirs::join($crate::dirs::home(), $crate::s!($($subpath)+))
+    };
+}
+
+/// A convenience macro for the user's configuration directory.
+///
+/// Requires the `dirs` feature to be enabled.
+///
+/// Returns the platform's configuration directory as an `Option<PathBuf>`,
+/// such as `~/.config` on Linux, `~/Library/Application Support` on macOS
+/// and `%APPDATA%` on Windows, or `None` if it cannot be determined. A
+/// subpath can be given, accepting anything `s!` does, and is joined onto
+/// the directory.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::config_dir;
+/// let profile = "default";
+///
+/// if let Some(path) = config_dir!("myapp/{}.toml", profile) {
+///     assert!(path.ends_with("myapp/default.toml"));
+///     assert!(path.starts_with(config_dir!().unwrap()));
+/// }
+/// ```
+#[macro_export]
+macro_rules! config_dir {
+    () => {
+        $crate::dirs::config()
+    };
+    ($($subpath:tt)+) => {
+        $crate::dirs::join($crate::dirs::config(), $crate::s!($($subpath)+))
+    };
+}
+
+/// A convenience macro for the user's cache directory.
+///
+/// Requires the `dirs` feature to be enabled.
+///
+/// Returns the platform's cache directory as an `Option<PathBuf>`, such as
+/// `~/.cache` on Linux, `~/Library/Caches` on macOS and `%LOCALAPPDATA%` on
+/// Windows, or `None` if it cannot be determined. A subpath can be given,
+/// accepting anything `s!` does, and is joined onto the directory.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::cache_dir;
+/// let version = 3;
+///
+/// if let Some(path) = cache_dir!("myapp/index-v{}", version) {
+///     assert!(path.ends_with("myapp/index-v3"));
+/// }
+/// ```
+#[macro_export]
+macro_rules! cache_dir {
+    () => {
+        $crate::dirs::cache()
+    };
+    ($($subpath:tt)+) => {
+        $crate::dirs::join($crate::dirs::cache(), $crate::s!($($subpath)+))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::join;
+    use std::path::PathBuf;
+
+    #[test]
+    fn joins_subpaths() {
+        let base = Some(PathBuf::from("/base"));
+        assert_eq!(join(base, "a/b".into()), Some(PathBuf::from("/base/a/b")));
+        assert_eq!(join(None, "a".into()), None);
+    }
+
+    #[test]
+    fn macros_match_dirs() {
+        assert_eq!(home!(), ::dirs::home_dir());
+        assert_eq!(
+            cache_dir!("x"),
+            ::dirs::cache_dir().map(|dir| dir.join("x"))
+        );
+        assert_eq!(
+            config_dir!("{}.toml", 1),
+            ::dirs::config_dir().map(|dir| dir.join("1.toml"))
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Display implementation utilities.
+//!
+//! The `display!` macro generates an `impl Display` block from a format
+//! string and arguments, pairing naturally with `s!`: where `s!` makes
+//! strings easy to build, `display!` makes your own types easy to format.
+
+/// A convenience macro for implementing `Display` with a format string.
+///
+/// The arguments are exactly those of `write!`, and may refer to the value
+/// being formatted through `self`.
+///
+/// # Examples
+///
+/// ## Formatting fields
+/// ```
+/// # use smacro::display;
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// display!(Point, "({}, {})", self.x, self.y);
+///
+/// let p = Point { x: 3, y: -1 };
+/// assert_eq!(p.to_string(), "(3, -1)");
+/// ```
+///
+/// ## Calling methods and using format specs
+/// ```
+/// # use smacro::display;
+/// struct Temperature(f64);
+///
+/// impl Temperature {
+///     fn fahrenheit(&self) -> f64 {
+///         self.0 * 9.0 / 5.0 + 32.0
+///     }
+/// }
+///
+/// display!(Temperature, "{:.1}°C / {:.1}°F", self.0, self.fahrenheit());
+///
+/// assert_eq!(Temperature(21.5).to_string(), "21.5°C / 70.7°F");
+/// ```
+///
+/// ## Constant output
+/// ```
+/// # use smacro::display;
+/// struct Unit;
+///
+/// display!(Unit, "()");
+///
+/// assert_eq!(Unit.to_string(), "()");
+/// ```
+///
+/// # Hygiene
+///
+/// Macros cannot normally see a `self` written by their caller. `display!`
+/// works around this by looking for the first `self` among the arguments and
+/// using that token as the receiver of the generated `fmt` method.
+#[macro_export]
+macro_rules! display {
+    (@scan $name:ty; [$($args:tt)*];) => {
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, $($args)*)
+            }
+        }
+    };
+    (@scan $name:ty; [$($args:tt)*]; ($($inner:tt)*) $($rest:tt)*) => {
+        $crate::display!(@scan $name; [$($args)*]; $($inner)* $($rest)*);
+    };
+    (@scan $name:ty; [$($args:tt)*]; [$($inner:tt)*] $($rest:tt)*) => {
+        $crate::display!(@scan $name; [$($args)*]; $($inner)* $($rest)*);
+    };
+    (@scan $name:ty; [$($args:tt)*]; {$($inner:tt)*} $($rest:tt)*) => {
+        $crate::display!(@scan $name; [$($args)*]; $($inner)* $($rest)*);
+    };
+    (@scan $name:ty; [$($args:tt)*]; $head:ident $($rest:tt)*) => {
+        $crate::display!(@probe $name; [$($args)*]; ($head) $head; $($rest)*);
+    };
+    (@scan $name:ty; [$($args:tt)*]; $head:tt $($rest:tt)*) => {
+        $crate::display!(@scan $name; [$($args)*]; $($rest)*);
+    };
+    (@probe $name:ty; [$($args:tt)*]; (self) $receiver:tt; $($rest:tt)*) => {
+        impl core::fmt::Display for $name {
+            fn fmt(&$receiver, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, $($args)*)
+            }
+        }
+    };
+    (@probe $name:ty; [$($args:tt)*]; ($other:tt) $_other:tt; $($rest:tt)*) => {
+        $crate::display!(@scan $name; [$($args)*]; $($rest)*);
+    };
+    ($name:ty, $($args:tt)+) => {
+        $crate::display!(@scan $name; [$($args)+]; $($args)+);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    display!(Point, "({}, {})", self.x, self.y);
+
+    struct Wrapper(Vec<u8>);
+
+    display!(Wrapper, "{} bytes, first: {:?}", self.0.len(), self.0.first());
+
+    struct Nested {
+        a: u8,
+        b: u8,
+    }
+
+    display!(Nested, "{}", (self.a as u16 + self.b as u16));
+
+    struct Named {
+        name: &'static str,
+    }
+
+    display!(Named, "name={n}", n = self.name);
+
+    struct Empty;
+
+    display!(Empty, "<empty>");
+
+    #[test]
+    fn display_fields() {
+        assert_eq!(Point { x: 1, y: 2 }.to_string(), "(1, 2)");
+    }
+
+    #[test]
+    fn display_method_calls() {
+        assert_eq!(Wrapper(vec![7, 8]).to_string(), "2 bytes, first: Some(7)");
+    }
+
+    #[test]
+    fn display_self_inside_group() {
+        assert_eq!(Nested { a: 200, b: 100 }.to_string(), "300");
+    }
+
+    #[test]
+    fn display_named_arguments() {
+        assert_eq!(Named { name: "ada" }.to_string(), "name=ada");
+    }
+
+    #[test]
+    fn display_without_self() {
+        assert_eq!(Empty.to_string(), "<empty>");
+    }
+
+    #[test]
+    fn display_works_with_s() {
+        let p = Point { x: 0, y: 5 };
+        assert_eq!(crate::s!("P{}", p), "P(0, 5)");
+    }
+}
@@ -0,0 +1,135 @@
+//! Duplicate detection utilities.
+//!
+//! The `first_duplicate!` and `has_duplicates!` macros scan an iterable
+//! with a `HashSet` to find repeated elements, which is a common check
+//! before building a map keyed by those elements.
+
+use std::collections::HashSet;
+use std::hash::Hash;
+
+#[doc(hidden)]
+pub fn first_duplicate<I>(items: I) -> Option<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Eq + Hash,
+{
+    let mut seen = HashSet::new();
+    for item in items {
+        if seen.contains(&item) {
+            return Some(item);
+        }
+        seen.insert(item);
+    }
+    None
+}
+
+#[doc(hidden)]
+pub fn first_duplicate_by<I, K, F>(items: I, mut key: F) -> Option<I::Item>
+where
+    I: IntoIterator,
+    K: Eq + Hash,
+    F: FnMut(&I::Item) -> K,
+{
+    let mut seen = HashSet::new();
+    items.into_iter().find(|item| !seen.insert(key(item)))
+}
+
+/// A convenience macro for finding the first repeated element of an iterable.
+///
+/// This macro provides two ways to compare elements:
+/// - By the elements themselves, which must implement `Eq` and `Hash`
+/// - By a key computed from each element with a closure
+///
+/// It returns `Some` with the first element equal to, or with the same key
+/// as, an earlier one, and `None` when all elements are distinct. The scan
+/// stops at the first duplicate.
+///
+/// # Examples
+///
+/// ## Repeated values
+/// ```
+/// # use smacro::first_duplicate;
+/// let ids = vec![4, 8, 15, 8, 4];
+///
+/// assert_eq!(first_duplicate!(ids), Some(8));
+/// assert_eq!(first_duplicate!([1, 2, 3]), None);
+/// ```
+///
+/// ## Repeated keys
+/// ```
+/// # use smacro::first_duplicate;
+/// struct User {
+///     id: u32,
+///     name: &'static str,
+/// }
+///
+/// let users = [User { id: 1, name: "ada" }, User { id: 1, name: "alan" }];
+/// let clash = first_duplicate!(&users, |u| u.id).unwrap();
+///
+/// assert_eq!(clash.name, "alan");
+/// ```
+#[macro_export]
+macro_rules! first_duplicate {
+    ($items:expr $(,)?) => {
+        $crate::duplicate::first_duplicate($items)
+    };
+    ($items:expr, $key:expr $(,)?) => {
+        $crate::duplicate::first_duplicate_by($items, $key)
+    };
+}
+
+/// A convenience macro for checking whether an iterable has repeated elements.
+///
+/// Accepts the same forms as [`first_duplicate!`] and returns `true` when
+/// it would return `Some`.
+///
+/// # Examples
+///
+/// ## Validating input
+/// ```
+/// # use smacro::has_duplicates;
+/// let emails = ["ada@example.com", "ADA@example.com"];
+///
+/// assert!(!has_duplicates!(emails));
+/// assert!(has_duplicates!(emails, |e| e.to_lowercase()));
+/// ```
+#[macro_export]
+macro_rules! has_duplicates {
+    ($($args:tt)+) => {
+        $crate::first_duplicate!($($args)+).is_some()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn finds_first_repeat() {
+        assert_eq!(first_duplicate!("abcbca".chars()), Some('b'));
+        assert_eq!(first_duplicate!(vec!["x", "y", "x"],), Some("x"));
+        assert_eq!(first_duplicate!(Vec::<u8>::new()), None);
+    }
+
+    #[test]
+    fn borrowed_items() {
+        let names = vec!["a".to_string(), "b".to_string(), "a".to_string()];
+        assert_eq!(first_duplicate!(&names), Some(&names[2]));
+        assert_eq!(names.len(), 3);
+    }
+
+    #[test]
+    fn keyed_duplicates() {
+        let words = ["apple", "banana", "avocado", "blueberry"];
+        assert_eq!(
+            first_duplicate!(words, |w| w.chars().next()),
+            Some("avocado")
+        );
+        assert_eq!(first_duplicate!(words, |w| w.len()), None);
+    }
+
+    #[test]
+    fn has_duplicates_forms() {
+        assert!(has_duplicates!([1, 2, 1]));
+        assert!(!has_duplicates!(1..100));
+        assert!(has_duplicates!(1..100, |n| n % 10));
+    }
+}
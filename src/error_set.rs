@@ -0,0 +1,180 @@
+//! Error enum declaration utilities.
+//!
+//! The `error_set!` macro declares an error enum together with its `Debug`,
+//! `Display` and `Error` impls, and a `From` impl for every wrapped type, as a
+//! lightweight, dependency-free alternative to derive-based error crates.
+
+/// A convenience macro for declaring error enums.
+///
+/// Each variant is either a unit variant or wraps a single value, and can
+/// carry a message used by `Display`. The macro generates:
+/// - The enum itself, deriving `Debug`
+/// - `Display`, writing the message (or the variant name) followed by the wrapped value
+/// - `Error`
+/// - `From<Wrapped>` for every wrapped type, so `?` converts automatically
+///
+/// Since a `From` impl is generated for each wrapped type, two variants
+/// cannot wrap the same type.
+///
+/// # Examples
+///
+/// ## Declaring an error set
+/// ```
+/// # use smacro::error_set;
+/// error_set!(AppError {
+///     Io(std::io::Error),
+///     Parse(std::num::ParseIntError),
+///     NotFound,
+/// });
+///
+/// fn parse_port(input: &str) -> Result<u16, AppError> {
+///     Ok(input.parse()?)
+/// }
+///
+/// let err = parse_port("http").unwrap_err();
+/// assert!(matches!(err, AppError::Parse(_)));
+/// assert_eq!(err.to_string(), "Parse: invalid digit found in string");
+/// assert_eq!(AppError::NotFound.to_string(), "NotFound");
+/// ```
+///
+/// ## Custom messages, attributes and visibility
+/// ```
+/// # use smacro::error_set;
+/// error_set!(
+///     /// Errors returned by the configuration loader.
+///     #[non_exhaustive]
+///     pub ConfigError {
+///         Io(std::io::Error) => "could not read config",
+///         Missing => "config file is missing",
+///     }
+/// );
+///
+/// let err = ConfigError::from(std::io::Error::other("disk on fire"));
+///
+/// assert_eq!(err.to_string(), "could not read config: disk on fire");
+/// assert_eq!(ConfigError::Missing.to_string(), "config file is missing");
+/// ```
+///
+/// ## Works with `Box<dyn Error>`
+/// ```
+/// # use smacro::error_set;
+/// use std::error::Error;
+///
+/// error_set!(LookupError { Unknown(String) });
+///
+/// let err: Box<dyn Error> = Box::new(LookupError::from("id 7".to_string()));
+/// assert_eq!(err.to_string(), "Unknown: id 7");
+/// ```
+#[macro_export]
+macro_rules! error_set {
+    (@display $name:ident $f:ident [$($arms:tt)*]) => {
+        impl core::fmt::Display for $name {
+            fn fmt(&self, $f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                match self {
+                    $($arms)*
+                }
+            }
+        }
+    };
+    (@display $name:ident $f:ident [$($arms:tt)*] $variant:ident($inner:ty) => $msg:literal $(, $($rest:tt)*)?) => {
+        $crate::error_set!(@display $name $f [
+            $($arms)*
+            $name::$variant(inner) => write!($f, "{}: {}", $msg, inner),
+        ] $($($rest)*)?);
+    };
+    (@display $name:ident $f:ident [$($arms:tt)*] $variant:ident($inner:ty) $(, $($rest:tt)*)?) => {
+        $crate::error_set!(@display $name $f [
+            $($arms)*
+            $name::$variant(inner) => write!($f, "{}: {}", stringify!($variant), inner),
+        ] $($($rest)*)?);
+    };
+    (@display $name:ident $f:ident [$($arms:tt)*] $variant:ident => $msg:literal $(, $($rest:tt)*)?) => {
+        $crate::error_set!(@display $name $f [
+            $($arms)*
+            $name::$variant => $f.write_str($msg),
+        ] $($($rest)*)?);
+    };
+    (@display $name:ident $f:ident [$($arms:tt)*] $variant:ident $(, $($rest:tt)*)?) => {
+        $crate::error_set!(@display $name $f [
+            $($arms)*
+            $name::$variant => $f.write_str(stringify!($variant)),
+        ] $($($rest)*)?);
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident $(($inner:ty))? $(=> $msg:literal)?
+            ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug)]
+        $vis enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant $(($inner))?,
+            )+
+        }
+
+        $crate::error_set!(@display $name f [] $($variant $(($inner))? $(=> $msg)?),+);
+
+        impl core::error::Error for $name {}
+
+        $($(
+            impl core::convert::From<$inner> for $name {
+                fn from(value: $inner) -> Self {
+                    $name::$variant(value)
+                }
+            }
+        )?)+
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+    use std::num::ParseIntError;
+
+    error_set!(AppError {
+        Io(std::io::Error),
+        Parse(ParseIntError) => "invalid number",
+        /// The requested item does not exist.
+        NotFound,
+        Forbidden => "access denied",
+    });
+
+    fn parse(input: &str) -> Result<i32, AppError> {
+        Ok(input.parse::<i32>()?)
+    }
+
+    #[test]
+    fn from_impls_enable_question_mark() {
+        assert_eq!(parse("42").unwrap(), 42);
+        assert!(matches!(parse("x"), Err(AppError::Parse(_))));
+    }
+
+    #[test]
+    fn display_with_wrapped_value() {
+        let io = AppError::from(std::io::Error::other("boom"));
+        assert_eq!(io.to_string(), "Io: boom");
+        assert_eq!(
+            parse("").unwrap_err().to_string(),
+            "invalid number: cannot parse integer from empty string"
+        );
+    }
+
+    #[test]
+    fn display_unit_variants() {
+        assert_eq!(AppError::NotFound.to_string(), "NotFound");
+        assert_eq!(AppError::Forbidden.to_string(), "access denied");
+    }
+
+    #[test]
+    fn implements_error() {
+        let err: Box<dyn Error> = Box::new(AppError::NotFound);
+        assert!(err.source().is_none());
+        assert_eq!(format!("{:?}", AppError::Forbidden), "Forbidden");
+    }
+}
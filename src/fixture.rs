@@ -0,0 +1,209 @@
+//! Fixture loading utilities.
+//!
+//! Requires the `map` feature to be enabled.
+//!
+//! The `fixture_map!` and `include_fixtures!` macros load a set of files
+//! into a `HashMap<String, String>` keyed by file stem, either at runtime
+//! from a wildcard pattern or at compile time with `include_str!`.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Matches `name` against a pattern where `*` matches any run of characters
+/// and `?` matches exactly one.
+fn matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` swallow one more character and retry
+                Some((star, start)) => {
+                    backtrack = Some((star, start + 1));
+                    p = star + 1;
+                    n = start + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[doc(hidden)]
+pub fn stem(path: &str) -> String {
+    Path::new(path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[doc(hidden)]
+pub fn load(pattern: &str) -> HashMap<String, String> {
+    let pattern = Path::new(pattern);
+    let dir = match pattern.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let file_pattern = pattern
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let entries = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("failed to read fixture directory {}: {}", dir.display(), e));
+
+    let mut fixtures = HashMap::new();
+    for entry in entries {
+        let path = entry
+            .unwrap_or_else(|e| panic!("failed to read fixture directory {}: {}", dir.display(), e))
+            .path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if !path.is_file() || !matches(&file_pattern, &name) {
+            continue;
+        }
+
+        let content = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read fixture {}: {}", path.display(), e));
+        let key = stem(&name);
+        if fixtures.contains_key(&key) {
+            panic!("duplicate fixture name {:?} in {}", key, dir.display());
+        }
+        fixtures.insert(key, content);
+    }
+    fixtures
+}
+
+/// A convenience macro for loading a directory of fixture files into a map.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// The pattern is a path whose file name may contain `*` and `?`
+/// wildcards. Every matching file is read into a `HashMap<String, String>`
+/// keyed by its file stem, so `tests/fixtures/user.json` becomes `"user"`.
+/// Relative paths are resolved against the current directory, which is the
+/// package root when running `cargo test`.
+///
+/// # Examples
+///
+/// ## Table-driven tests
+/// ```no_run
+/// # use smacro::fixture_map;
+/// let fixtures = fixture_map!("tests/fixtures/*.json");
+///
+/// for (name, json) in &fixtures {
+///     assert!(json.starts_with('{'), "fixture {} is not an object", name);
+/// }
+/// ```
+///
+/// ## Formatted pattern
+/// ```
+/// # use smacro::fixture_map;
+/// let sources = fixture_map!("{}/src/*.rs", env!("CARGO_MANIFEST_DIR"));
+///
+/// assert!(sources["lib"].contains("pub mod fixture;"));
+/// ```
+///
+/// # Panics
+///
+/// Panics if the directory or a matching file cannot be read, or if two
+/// matching files have the same stem. Wildcards in directory names are not
+/// supported.
+#[macro_export]
+macro_rules! fixture_map {
+    ($($pattern:tt)+) => {
+        $crate::fixture::load(&$crate::s!($($pattern)+))
+    };
+}
+
+/// A convenience macro for embedding fixture files into a map at compile time.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// A directory relative to the package root is followed by a `;` and the
+/// list of file names to embed with `include_str!`. The result is a
+/// `HashMap<String, String>` keyed by file stem, like [`fixture_map!`], but
+/// the files are part of the binary and missing files are compile errors.
+///
+/// # Examples
+///
+/// ## Embedding files
+/// ```
+/// # use smacro::include_fixtures;
+/// let fixtures = include_fixtures!("examples"; "demo.rs");
+///
+/// assert!(fixtures["demo"].contains("fn main"));
+/// ```
+#[macro_export]
+macro_rules! include_fixtures {
+    ($dir:literal; $($file:literal),+ $(,)?) => {
+        {
+            let mut fixtures = std::collections::HashMap::new();
+            $(
+                fixtures.insert(
+                    $crate::fixture::stem($file),
+                    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $dir, "/", $file)).to_string(),
+                );
+            )+
+            fixtures
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::matches;
+    use std::fs;
+
+    #[test]
+    fn wildcard_matching() {
+        assert!(matches("*.json", "user.json"));
+        assert!(matches("*", ""));
+        assert!(matches("case_??.txt", "case_01.txt"));
+        assert!(matches("a*b*c", "aXXbYYbc"));
+        assert!(!matches("*.json", "user.json.bak"));
+        assert!(!matches("case_?.txt", "case_01.txt"));
+    }
+
+    #[test]
+    fn loads_matching_files() {
+        let dir = std::env::temp_dir().join(format!("smacro-fixtures-{}", std::process::id()));
+        fs::create_dir_all(dir.join("nested.json")).unwrap();
+        fs::write(dir.join("a.json"), "{\"a\": 1}").unwrap();
+        fs::write(dir.join("b.json"), "{}").unwrap();
+        fs::write(dir.join("notes.txt"), "skip me").unwrap();
+
+        let fixtures = fixture_map!("{}/*.json", dir.display());
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(fixtures.len(), 2);
+        assert_eq!(fixtures["a"], "{\"a\": 1}");
+        assert_eq!(fixtures["b"], "{}");
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to read fixture directory")]
+    fn missing_directory_panics() {
+        fixture_map!("does/not/exist/*.json");
+    }
+
+    #[test]
+    fn include_fixtures_by_stem() {
+        let fixtures = include_fixtures!("src"; "fixture.rs", "map.rs",);
+        assert_eq!(fixtures.len(), 2);
+        assert!(fixtures["fixture"].starts_with("//! Fixture loading utilities."));
+        assert!(fixtures["map"].contains("macro_rules! map"));
+    }
+}
@@ -0,0 +1,202 @@
+//! String parsing utilities.
+//!
+//! The `from_str!` macro generates a `FromStr` implementation mapping string
+//! literals to the unit variants of an enum, and optionally the reverse
+//! `Display` implementation, replacing the lookup tables written by hand in
+//! every command line tool.
+
+/// A convenience macro for implementing `FromStr` for enums from a table of strings.
+///
+/// This macro generates:
+/// - `FromStr`, matching the input against each string literal
+/// - Optionally `Display`, writing the first literal of each variant (`; display`)
+///
+/// By default, parse errors are a `String` such as
+/// ``unknown Level `trace`, expected one of: debug, info``. A custom error
+/// type implementing `From<String>` can be used instead with `; error(Type)`.
+///
+/// # Examples
+///
+/// ## Parsing enum variants
+/// ```
+/// # use smacro::from_str;
+/// #[derive(Debug, PartialEq)]
+/// enum Level {
+///     Debug,
+///     Info,
+/// }
+///
+/// from_str!(Level { "debug" => Debug, "info" => Info });
+///
+/// assert_eq!("debug".parse::<Level>(), Ok(Level::Debug));
+/// assert_eq!(
+///     "trace".parse::<Level>(),
+///     Err("unknown Level `trace`, expected one of: debug, info".to_string())
+/// );
+/// ```
+///
+/// ## Aliases and the reverse `Display`
+/// ```
+/// # use smacro::from_str;
+/// #[derive(Debug, PartialEq)]
+/// enum Color {
+///     Red,
+///     Green,
+/// }
+///
+/// from_str!(Color { "red" | "r" => Red, "green" | "g" => Green }; display);
+///
+/// assert_eq!("g".parse::<Color>(), Ok(Color::Green));
+/// assert_eq!(Color::Green.to_string(), "green");
+/// ```
+///
+/// ## Custom error type
+/// ```
+/// # use smacro::from_str;
+/// #[derive(Debug)]
+/// struct ParseModeError(String);
+///
+/// impl From<String> for ParseModeError {
+///     fn from(message: String) -> Self {
+///         ParseModeError(message)
+///     }
+/// }
+///
+/// enum Mode {
+///     Fast,
+///     Safe,
+/// }
+///
+/// from_str!(Mode { "fast" => Fast, "safe" => Safe }; error(ParseModeError));
+///
+/// let err = "slow".parse::<Mode>().err().unwrap();
+/// assert!(err.0.starts_with("unknown Mode `slow`"));
+/// ```
+#[macro_export]
+macro_rules! from_str {
+    (@err) => { String };
+    (@err $err:ty) => { $err };
+    (
+        @from_str $name:ident { $($first:literal $(| $alias:literal)* => $variant:ident),+ $(,)? }
+        $(; error($err:ty))?
+    ) => {
+        impl core::str::FromStr for $name {
+            type Err = $crate::from_str!(@err $($err)?);
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $($first $(| $alias)* => Ok($name::$variant),)+
+                    _ => Err(core::convert::From::from(format!(
+                        "unknown {} `{}`, expected one of: {}",
+                        stringify!($name),
+                        s,
+                        [$($first $(, $alias)*),+].join(", "),
+                    ))),
+                }
+            }
+        }
+    };
+    (
+        $name:ident { $($first:literal $(| $alias:literal)* => $variant:ident),+ $(,)? }
+        $(; error($err:ty))?; display
+    ) => {
+        $crate::from_str!(
+            @from_str $name { $($first $(| $alias)* => $variant),+ } $(; error($err))?
+        );
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str(match self {
+                    $($name::$variant => $first,)+
+                })
+            }
+        }
+    };
+    (
+        $name:ident { $($first:literal $(| $alias:literal)* => $variant:ident),+ $(,)? }
+        $(; error($err:ty))?
+    ) => {
+        $crate::from_str!(
+            @from_str $name { $($first $(| $alias)* => $variant),+ } $(; error($err))?
+        );
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    #[derive(Debug, PartialEq)]
+    enum Level {
+        Debug,
+        Info,
+        Warn,
+    }
+
+    from_str!(Level {
+        "debug" => Debug,
+        "info" => Info,
+        "warn" | "warning" => Warn,
+    }; display);
+
+    #[derive(Debug, PartialEq)]
+    enum Format {
+        Json,
+        Yaml,
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct FormatError(String);
+
+    impl From<String> for FormatError {
+        fn from(message: String) -> Self {
+            FormatError(message)
+        }
+    }
+
+    from_str!(Format { "json" => Json, "yaml" | "yml" => Yaml }; error(FormatError));
+
+    #[test]
+    fn parses_variants() {
+        assert_eq!(Level::from_str("debug"), Ok(Level::Debug));
+        assert_eq!("info".parse::<Level>(), Ok(Level::Info));
+    }
+
+    #[test]
+    fn parses_aliases() {
+        assert_eq!("warn".parse::<Level>(), Ok(Level::Warn));
+        assert_eq!("warning".parse::<Level>(), Ok(Level::Warn));
+        assert_eq!("yml".parse::<Format>(), Ok(Format::Yaml));
+    }
+
+    #[test]
+    fn default_error_message() {
+        assert_eq!(
+            "Debug".parse::<Level>(),
+            Err("unknown Level `Debug`, expected one of: debug, info, warn, warning".to_string())
+        );
+    }
+
+    #[test]
+    fn custom_error_type() {
+        assert_eq!(
+            "toml".parse::<Format>(),
+            Err(FormatError(
+                "unknown Format `toml`, expected one of: json, yaml, yml".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn display_uses_first_literal() {
+        assert_eq!(Level::Warn.to_string(), "warn");
+        assert_eq!(Level::Debug.to_string(), "debug");
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for level in [Level::Debug, Level::Info, Level::Warn] {
+            assert_eq!(level.to_string().parse::<Level>(), Ok(level));
+        }
+    }
+}
@@ -0,0 +1,178 @@
+//! File system utilities.
+//!
+//! The `read_s!` and `write_s!` macros combine `s!` formatting with
+//! `std::fs::read_to_string` and `std::fs::write`, the most common file
+//! operations in tools and tests. The `lines_of!` and `lines_set!` macros
+//! read a file straight into its lines.
+
+use std::io;
+
+#[doc(hidden)]
+pub fn read_lines<C: FromIterator<String>>(path: String) -> io::Result<C> {
+    let text = std::fs::read_to_string(path)?;
+    Ok(text.lines().map(String::from).collect())
+}
+
+/// A convenience macro for reading a file at a formatted path into a `String`.
+///
+/// The path accepts anything `s!` does, such as a format string with
+/// arguments, and the file is read with `std::fs::read_to_string`, returning
+/// its `io::Result<String>`.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::{read_s, write_s};
+/// let dir = std::env::temp_dir();
+/// let name = "smacro-read-s-doc";
+/// write_s!(dir.join(format!("{}.toml", name)), "port = {}", 8080)?;
+///
+/// let config = read_s!("{}/{}.toml", dir.display(), name)?;
+/// assert_eq!(config, "port = 8080");
+/// # std::fs::remove_file(dir.join(format!("{}.toml", name)))?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[macro_export]
+macro_rules! read_s {
+    ($($path:tt)+) => {
+        std::fs::read_to_string($crate::s!($($path)+))
+    };
+}
+
+/// A convenience macro for writing formatted content to a file.
+///
+/// The path comes first and can be anything implementing `AsRef<Path>`. The
+/// content accepts anything `s!` does, and is written with `std::fs::write`,
+/// creating or truncating the file and returning its `io::Result<()>`.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::write_s;
+/// let path = std::env::temp_dir().join("smacro-write-s-doc.txt");
+/// let (name, version) = ("smacro", "0.1.0");
+///
+/// write_s!(&path, "{} {}", name, version)?;
+/// assert_eq!(std::fs::read_to_string(&path)?, "smacro 0.1.0");
+///
+/// write_s!(&path, 42)?;
+/// assert_eq!(std::fs::read_to_string(&path)?, "42");
+/// # std::fs::remove_file(&path)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[macro_export]
+macro_rules! write_s {
+    ($path:expr, $($content:tt)+) => {
+        std::fs::write($path, $crate::s!($($content)+))
+    };
+}
+
+/// A convenience macro for reading the lines of a file into a `Vec<String>`.
+///
+/// This macro provides two ways to read the lines:
+/// - At runtime, from a path accepting anything `s!` does, returning an
+///   `io::Result<Vec<String>>`
+/// - At compile time with `include`, embedding the file like `include_str!`
+///   and returning a `Vec<&'static str>`
+///
+/// Lines are split with `str::lines`, so both `\n` and `\r\n` endings are
+/// removed and a final line ending does not produce an empty line. Blank
+/// lines in the middle of the file are kept.
+///
+/// # Examples
+///
+/// ## Reading at runtime
+/// ```
+/// # use smacro::{lines_of, write_s};
+/// let path = std::env::temp_dir().join("smacro-lines-of-doc.txt");
+/// write_s!(&path, "apple\nbanana\n")?;
+///
+/// let words = lines_of!(path.display())?;
+/// assert_eq!(words, ["apple", "banana"]);
+/// # std::fs::remove_file(&path)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// ## Embedding at compile time
+/// ```
+/// # use smacro::lines_of;
+/// // The path is relative to the current file, as with `include_str!`
+/// let lines = lines_of!(include "../Cargo.toml");
+///
+/// assert_eq!(lines[0], "[package]");
+/// ```
+#[macro_export]
+macro_rules! lines_of {
+    (include $path:literal) => {
+        include_str!($path).lines().collect::<std::vec::Vec<&'static str>>()
+    };
+    ($($path:tt)+) => {
+        $crate::fs::read_lines::<std::vec::Vec<String>>($crate::s!($($path)+))
+    };
+}
+
+/// A convenience macro for reading the lines of a file into a `HashSet<String>`.
+///
+/// Accepts the same forms as [`lines_of!`](crate::lines_of), collecting the
+/// distinct lines into a set for membership tests, such as a word list or an
+/// allow list. The `include` form returns a `HashSet<&'static str>`.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::{lines_set, write_s};
+/// let path = std::env::temp_dir().join("smacro-lines-set-doc.txt");
+/// write_s!(&path, "admin\nroot\nadmin\n")?;
+///
+/// let reserved = lines_set!(path.display())?;
+/// assert_eq!(reserved.len(), 2);
+/// assert!(reserved.contains("root"));
+/// # std::fs::remove_file(&path)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[macro_export]
+macro_rules! lines_set {
+    (include $path:literal) => {
+        include_str!($path)
+            .lines()
+            .collect::<std::collections::HashSet<&'static str>>()
+    };
+    ($($path:tt)+) => {
+        $crate::fs::read_lines::<std::collections::HashSet<String>>($crate::s!($($path)+))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    #[test]
+    fn round_trip() {
+        let path = std::env::temp_dir().join(format!("smacro-fs-{}.txt", std::process::id()));
+        write_s!(&path, upper "hello {}", "world").unwrap();
+        assert_eq!(read_s!(path.display()).unwrap(), "HELLO WORLD");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn missing_file() {
+        let error = read_s!("/does/not/exist/{}", 1).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn lines() {
+        let path = std::env::temp_dir().join(format!("smacro-lines-{}.txt", std::process::id()));
+        write_s!(&path, "b\r\na\n\nb").unwrap();
+        assert_eq!(lines_of!(path.display()).unwrap(), ["b", "a", "", "b"]);
+        assert_eq!(lines_set!(path.display()).unwrap().len(), 3);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn included_lines() {
+        let lines = lines_of!(include "fs.rs");
+        assert_eq!(lines[0], "//! File system utilities.");
+        assert!(lines_set!(include "fs.rs").contains("}"));
+    }
+}
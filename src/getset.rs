@@ -0,0 +1,166 @@
+//! Accessor generation utilities.
+//!
+//! Requires the `derive` feature to be enabled.
+//!
+//! The `getset!` macro generates getters, setters and optionally `with_`
+//! builder methods for the listed fields of a struct, without deriving
+//! anything on the struct itself. The `set_` and `with_` method names are
+//! built by a procedural macro from `smacro-derive`, which is why the
+//! `derive` feature is required.
+
+/// A convenience macro for generating getters and setters for struct fields.
+///
+/// Requires the `derive` feature to be enabled. `macro_rules!` cannot join
+/// identifiers, so the `set_<field>` and `with_<field>` names are pasted
+/// together by a procedural macro from `smacro-derive`. That crate has no
+/// dependencies of its own unless the `toml` or `yaml` features are
+/// enabled, so the cost is compiling one small proc-macro crate.
+///
+/// The macro takes a struct name and the fields to generate accessors for,
+/// with their types, and expands to an `impl` block containing for each
+/// field:
+/// - A getter named after the field, returning a reference
+/// - A setter named `set_<field>`, returning `&mut Self` for chaining
+/// - A `with_<field>` method taking and returning `self`, if `; with` is
+///   appended
+///
+/// A visibility before the struct name applies to every generated method.
+/// Fields that are not listed get no accessors. Generic structs are not
+/// supported.
+///
+/// # Examples
+///
+/// ## Getters and setters
+/// ```
+/// # use smacro::getset;
+/// #[derive(Default)]
+/// pub struct Config {
+///     port: u16,
+///     host: String,
+/// }
+///
+/// getset!(pub Config { port: u16, host: String });
+///
+/// let mut config = Config::default();
+/// config.set_port(8080).set_host("localhost".to_string());
+///
+/// assert_eq!(*config.port(), 8080);
+/// assert_eq!(config.host(), "localhost");
+/// ```
+///
+/// ## Builder methods
+/// ```
+/// # use smacro::getset;
+/// #[derive(Default)]
+/// struct Request {
+///     url: String,
+///     retries: u8,
+/// }
+///
+/// getset!(Request { url: String, retries: u8 }; with);
+///
+/// let request = Request::default()
+///     .with_url("https://example.com".to_string())
+///     .with_retries(3);
+///
+/// assert_eq!(request.url(), "https://example.com");
+/// assert_eq!(*request.retries(), 3);
+/// ```
+#[macro_export]
+macro_rules! getset {
+    ($vis:vis $name:ident { $($field:ident : $ty:ty),* $(,)? } ; with) => {
+        $crate::getset!($vis $name { $($field: $ty),* });
+
+        $crate::__paste! {
+            impl $name {
+                $(
+                    #[doc = concat!("Returns `self` with `", stringify!($field), "` set to the given value.")]
+                    $vis fn [<with_ $field>](mut self, $field: $ty) -> Self {
+                        self.$field = $field;
+                        self
+                    }
+                )*
+            }
+        }
+    };
+    ($vis:vis $name:ident { $($field:ident : $ty:ty),* $(,)? }) => {
+        $crate::__paste! {
+            impl $name {
+                $(
+                    #[doc = concat!("Returns a reference to `", stringify!($field), "`.")]
+                    $vis fn $field(&self) -> &$ty {
+                        &self.$field
+                    }
+
+                    #[doc = concat!("Sets `", stringify!($field), "` and returns `self` for chaining.")]
+                    $vis fn [<set_ $field>](&mut self, $field: $ty) -> &mut Self {
+                        self.$field = $field;
+                        self
+                    }
+                )*
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[derive(Debug, Default, PartialEq)]
+    struct Config {
+        host: String,
+        port: u16,
+        tags: Vec<&'static str>,
+        r#type: &'static str,
+    }
+
+    getset!(Config { host: String, port: u16, r#type: &'static str, });
+
+    #[derive(Debug, Default)]
+    struct Request {
+        url: String,
+        retries: u8,
+    }
+
+    getset!(pub(crate) Request { url: String, retries: u8 }; with);
+
+    #[test]
+    fn getters() {
+        let config = Config {
+            host: "localhost".to_string(),
+            port: 80,
+            tags: vec!["a"],
+            r#type: "dev",
+        };
+        assert_eq!(config.host(), "localhost");
+        assert_eq!(*config.port(), 80);
+        assert_eq!(*config.r#type(), "dev");
+        assert_eq!(config.tags, ["a"]);
+    }
+
+    #[test]
+    fn chained_setters() {
+        let mut config = Config::default();
+        config.set_port(8080).set_host("example.com".to_string());
+        config.set_type("prod");
+        assert_eq!(config.port, 8080);
+        assert_eq!(config.host, "example.com");
+        assert_eq!(config.r#type, "prod");
+    }
+
+    #[test]
+    fn with_methods() {
+        let request = Request::default()
+            .with_retries(2)
+            .with_url("https://example.com".to_string());
+        assert_eq!(request.url(), "https://example.com");
+        assert_eq!(*request.retries(), 2);
+    }
+
+    #[test]
+    fn setters_alongside_with_methods() {
+        let mut request = Request::default().with_retries(1);
+        request.set_retries(5).set_url("/health".to_string());
+        assert_eq!(request.retries, 5);
+        assert_eq!(request.url, "/health");
+    }
+}
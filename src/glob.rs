@@ -0,0 +1,103 @@
+//! Path globbing utilities.
+//!
+//! Requires the `glob` feature to be enabled.
+//!
+//! The `glob_vec!` macro collects the paths matching a shell-style pattern
+//! into a sorted `Vec<PathBuf>`, using the `glob` crate.
+
+use std::io;
+use std::path::PathBuf;
+
+#[doc(hidden)]
+pub fn glob(pattern: &str) -> io::Result<Vec<PathBuf>> {
+    let paths = ::glob::glob(pattern).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid glob pattern {:?}: {}", pattern, e),
+        )
+    })?;
+    let mut paths = paths
+        .map(|path| path.map_err(|e| io::Error::new(e.error().kind(), e.to_string())))
+        .collect::<io::Result<Vec<_>>>()?;
+    paths.sort();
+    Ok(paths)
+}
+
+/// A convenience macro for collecting the paths matching a glob pattern.
+///
+/// Requires the `glob` feature to be enabled.
+///
+/// The pattern accepts anything `s!` does and supports `?`, `*`, `**` for
+/// any number of directories, and character classes such as `[a-z]`. The
+/// matching paths are returned as an `io::Result<Vec<PathBuf>>`, sorted so
+/// the order is the same on every platform and run.
+///
+/// An invalid pattern is an `InvalidInput` error, and a directory that
+/// cannot be read while matching fails the whole call with its I/O error.
+///
+/// # Examples
+///
+/// ## Matching files
+/// ```
+/// # use smacro::glob_vec;
+/// let sources = glob_vec!("src/**/*.rs")?;
+///
+/// assert!(sources.iter().any(|path| path.ends_with("lib.rs")));
+/// assert!(sources.windows(2).all(|pair| pair[0] < pair[1]));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// ## Formatted patterns
+/// ```
+/// # use smacro::glob_vec;
+/// let extension = "toml";
+/// let manifests = glob_vec!("Cargo.{}", extension)?;
+///
+/// assert_eq!(manifests.len(), 1);
+/// assert!(glob_vec!("[").is_err());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[macro_export]
+macro_rules! glob_vec {
+    ($($pattern:tt)+) => {
+        $crate::glob::glob(&$crate::s!($($pattern)+))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    #[test]
+    fn sorted_matches() {
+        let dir = std::env::temp_dir().join(format!("smacro-glob-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        for name in ["b.log", "a.log", "c.txt", "nested/d.log"] {
+            std::fs::write(dir.join(name), "").unwrap();
+        }
+
+        let logs = glob_vec!("{}/**/*.log", dir.display()).unwrap();
+        let names: Vec<_> = logs
+            .iter()
+            .map(|path| path.strip_prefix(&dir).unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            ["a.log", "b.log", "nested/d.log"].map(std::path::Path::new)
+        );
+        assert!(glob_vec!("{}/*.gz", dir.display()).unwrap().is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_pattern() {
+        let error = glob_vec!("a/***").unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        assert!(
+            error
+                .to_string()
+                .starts_with("invalid glob pattern \"a/***\": ")
+        );
+    }
+}
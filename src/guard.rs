@@ -0,0 +1,303 @@
+//! Early-return guard utilities.
+//!
+//! The `guard!` macro flattens the pyramid of `if let` and early-return
+//! checks at the top of functions, either by binding a pattern or by
+//! checking a condition. The `let_some!` and `let_ok!` macros unwrap an
+//! `Option` or a `Result` the same way, with the error available to the
+//! diverging branch.
+
+/// A convenience macro for early returns when a pattern or condition doesn't hold.
+///
+/// This macro provides three ways to guard a function:
+/// - Bind a pattern, running a diverging `else` branch when it doesn't match
+/// - Check a condition, returning a value when it is false
+/// - Check a condition, returning `()` when it is false
+///
+/// # Examples
+///
+/// ## Binding a pattern
+/// ```
+/// # use smacro::guard;
+/// #[derive(Debug, PartialEq)]
+/// struct NotFound;
+///
+/// fn find(id: u32) -> Option<&'static str> {
+///     (id == 1).then_some("ada")
+/// }
+///
+/// fn greet(id: u32) -> Result<String, NotFound> {
+///     guard!(let Some(user) = find(id) else return Err(NotFound));
+///     Ok(format!("hello, {}", user))
+/// }
+///
+/// assert_eq!(greet(1), Ok("hello, ada".to_string()));
+/// assert_eq!(greet(2), Err(NotFound));
+/// ```
+///
+/// ## Checking a condition
+/// ```
+/// # use smacro::guard;
+/// fn div(a: i32, b: i32) -> Option<i32> {
+///     guard!(b != 0, None);
+///     Some(a / b)
+/// }
+///
+/// assert_eq!(div(6, 3), Some(2));
+/// assert_eq!(div(6, 0), None);
+/// ```
+///
+/// ## Any diverging `else` branch
+/// ```
+/// # use smacro::guard;
+/// let mut total = 0;
+///
+/// for input in ["1", "x", "3"] {
+///     guard!(let Ok(n) = input.parse::<i32>() else continue);
+///     total += n;
+/// }
+///
+/// assert_eq!(total, 4);
+/// ```
+///
+/// The pattern form expands to a `let ... else` statement, so the `else`
+/// branch must diverge (`return`, `continue`, `break`, `panic!`, ...).
+#[macro_export]
+macro_rules! guard {
+    (@let [$pat:pat] [$($value:tt)+] else $($otherwise:tt)+) => {
+        let $pat = $($value)+ else { $($otherwise)+ };
+    };
+    (@let [$pat:pat] [$($value:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::guard!(@let [$pat] [$($value)* $next] $($rest)*)
+    };
+    (let $pat:pat = $($rest:tt)+) => {
+        $crate::guard!(@let [$pat] [] $($rest)+)
+    };
+    ($cond:expr, $ret:expr $(,)?) => {
+        if !$cond {
+            return $ret;
+        }
+    };
+    ($cond:expr $(,)?) => {
+        if !$cond {
+            return;
+        }
+    };
+}
+
+/// A convenience macro for unwrapping an `Option` or running a diverging branch.
+///
+/// `let_some!(name = expr, else action)` binds the value inside `Some` to
+/// `name`, or runs `action` if the option is `None`. The action must
+/// diverge: `return`, `continue`, `break`, `panic!`, ... The binding can be
+/// any irrefutable pattern, such as `mut name` or a tuple.
+///
+/// The expansion is a plain `match`, so it also works where `let ... else`
+/// is unavailable or unwanted.
+///
+/// # Examples
+///
+/// ## Returning early
+/// ```
+/// # use smacro::let_some;
+/// fn find(id: u32) -> Option<&'static str> {
+///     (id == 1).then_some("ada")
+/// }
+///
+/// fn greet(id: u32) -> String {
+///     let_some!(user = find(id), else return "not found".to_string());
+///     format!("hello, {}", user)
+/// }
+///
+/// assert_eq!(greet(1), "hello, ada");
+/// assert_eq!(greet(2), "not found");
+/// ```
+///
+/// ## Skipping loop iterations
+/// ```
+/// # use smacro::let_some;
+/// let mut total = 0;
+///
+/// for word in ["a1", "b", "c3"] {
+///     let_some!((_, digit) = word.split_at_checked(1), else continue);
+///     let_some!(mut n = digit.parse::<i32>().ok(), else continue);
+///     n *= 10;
+///     total += n;
+/// }
+///
+/// assert_eq!(total, 40);
+/// ```
+#[macro_export]
+macro_rules! let_some {
+    ($pat:pat = $value:expr, else $($otherwise:tt)+) => {
+        let $pat = match $value {
+            core::option::Option::Some(value) => value,
+            core::option::Option::None => { $($otherwise)+ }
+        };
+    };
+}
+
+/// A convenience macro for unwrapping a `Result` or running a diverging branch.
+///
+/// This macro provides two ways to handle the error:
+/// - `let_ok!(name = expr, else action)` ignores the error
+/// - `let_ok!(name = expr, else |err| action)` binds the error to `err`
+///
+/// The value inside `Ok` is bound to `name`, which can be any irrefutable
+/// pattern. As with [`let_some!`], the action must diverge.
+///
+/// # Examples
+///
+/// ## Converting the error
+/// ```
+/// # use smacro::let_ok;
+/// fn parse_port(input: &str) -> Result<u16, String> {
+///     let_ok!(port = input.parse::<u16>(), else |e| return Err(format!("bad port: {}", e)));
+///     Ok(port)
+/// }
+///
+/// assert_eq!(parse_port("80"), Ok(80));
+/// assert_eq!(parse_port("x"), Err("bad port: invalid digit found in string".to_string()));
+/// ```
+///
+/// ## Ignoring the error
+/// ```
+/// # use smacro::let_ok;
+/// let mut numbers = vec![];
+///
+/// for input in ["1", "two", "3"] {
+///     let_ok!(n = input.parse::<i32>(), else continue);
+///     numbers.push(n);
+/// }
+///
+/// assert_eq!(numbers, [1, 3]);
+/// ```
+#[macro_export]
+macro_rules! let_ok {
+    ($pat:pat = $value:expr, else |$err:pat_param| $($otherwise:tt)+) => {
+        let $pat = match $value {
+            core::result::Result::Ok(value) => value,
+            core::result::Result::Err($err) => { $($otherwise)+ }
+        };
+    };
+    ($pat:pat = $value:expr, else $($otherwise:tt)+) => {
+        let $pat = match $value {
+            core::result::Result::Ok(value) => value,
+            core::result::Result::Err(_) => { $($otherwise)+ }
+        };
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    fn first_even(values: &[i32]) -> Result<i32, &'static str> {
+        guard!(let Some(first) = values.first() else return Err("empty"));
+        guard!(first % 2 == 0, Err("odd"));
+        Ok(*first)
+    }
+
+    #[test]
+    fn guard_pattern_binds() {
+        assert_eq!(first_even(&[4, 1]), Ok(4));
+    }
+
+    #[test]
+    fn guard_pattern_else_branch() {
+        assert_eq!(first_even(&[]), Err("empty"));
+    }
+
+    #[test]
+    fn guard_condition_returns_value() {
+        assert_eq!(first_even(&[3]), Err("odd"));
+    }
+
+    #[test]
+    fn guard_condition_returns_unit() {
+        fn push_positive(values: &mut Vec<i32>, v: i32) {
+            guard!(v > 0);
+            values.push(v);
+        }
+
+        let mut values = vec![];
+        push_positive(&mut values, 1);
+        push_positive(&mut values, -1);
+        assert_eq!(values, vec![1]);
+    }
+
+    #[test]
+    fn guard_with_loop_control() {
+        let mut seen = vec![];
+        for item in [Some(1), None, Some(3), None] {
+            guard!(let Some(v) = item else continue);
+            seen.push(v);
+        }
+        assert_eq!(seen, vec![1, 3]);
+    }
+
+    #[test]
+    fn guard_with_block_and_struct_pattern() {
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        fn on_axis(p: Option<Point>) -> bool {
+            guard!(let Some(Point { x, y }) = p else {
+                return false;
+            });
+            x == 0 || y == 0
+        }
+
+        assert!(on_axis(Some(Point { x: 0, y: 5 })));
+        assert!(!on_axis(Some(Point { x: 1, y: 5 })));
+        assert!(!on_axis(None));
+    }
+
+    #[test]
+    fn let_some_binds_or_diverges() {
+        fn double_first(values: &[i32]) -> i32 {
+            let_some!(first = values.first(), else return -1);
+            first * 2
+        }
+
+        assert_eq!(double_first(&[4, 1]), 8);
+        assert_eq!(double_first(&[]), -1);
+    }
+
+    #[test]
+    fn let_some_with_patterns() {
+        let mut found = vec![];
+        for pair in [Some((1, 'a')), None, Some((3, 'c'))] {
+            let_some!((mut n, c) = pair, else continue);
+            n += 1;
+            found.push((n, c));
+        }
+        assert_eq!(found, [(2, 'a'), (4, 'c')]);
+    }
+
+    #[test]
+    fn let_ok_with_error_binding() {
+        let mut errors = vec![];
+        let mut sum = 0;
+        for input in ["1", "", "x", "4"] {
+            let_ok!(n = input.parse::<i32>(), else |e| {
+                errors.push(e.to_string());
+                continue;
+            });
+            sum += n;
+        }
+        assert_eq!(sum, 5);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0], "cannot parse integer from empty string");
+    }
+
+    #[test]
+    fn let_ok_ignoring_error() {
+        fn or_zero(result: Result<u8, ()>) -> u8 {
+            let_ok!(v = result, else return 0);
+            v
+        }
+
+        assert_eq!(or_zero(Ok(7)), 7);
+        assert_eq!(or_zero(Err(())), 0);
+    }
+}
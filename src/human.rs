@@ -0,0 +1,449 @@
+//! Human-readable number formatting utilities.
+//!
+//! The `human_bytes!` and `human_num!` macros format byte counts with binary
+//! or decimal units and large numbers with thousands separators, and
+//! `ordinal!` and `roman!` write numbers as ordinals and Roman numerals,
+//! and `duration_s!` writes durations with units, all returning a `String`
+//! like `s!`.
+
+use crate::stats::ToF64;
+use std::fmt::Display;
+use std::time::Duration;
+
+const BINARY_UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+const DECIMAL_UNITS: [&str; 7] = ["B", "kB", "MB", "GB", "TB", "PB", "EB"];
+const ROMAN: [(u32, &str); 13] = [
+    (1000, "M"),
+    (900, "CM"),
+    (500, "D"),
+    (400, "CD"),
+    (100, "C"),
+    (90, "XC"),
+    (50, "L"),
+    (40, "XL"),
+    (10, "X"),
+    (9, "IX"),
+    (5, "V"),
+    (4, "IV"),
+    (1, "I"),
+];
+
+#[doc(hidden)]
+pub fn bytes(value: impl ToF64, si: bool) -> String {
+    let (base, units) = if si {
+        (1000.0, &DECIMAL_UNITS)
+    } else {
+        (1024.0, &BINARY_UNITS)
+    };
+
+    let mut size = value.to_f64();
+    let mut unit = 0;
+    while size.abs() >= base && unit < units.len() - 1 {
+        size /= base;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        return format!("{} {}", size, units[0]);
+    }
+
+    // A size just below the next unit can round up to it, such as 1023.96 KiB
+    if (size.abs() * 10.0).round() / 10.0 >= base && unit < units.len() - 1 {
+        size /= base;
+        unit += 1;
+    }
+
+    let mut out = format!("{:.1}", size);
+    if out.ends_with(".0") {
+        out.truncate(out.len() - 2);
+    }
+    out.push(' ');
+    out.push_str(units[unit]);
+    out
+}
+
+#[doc(hidden)]
+pub fn separated(value: impl Display, separator: char) -> String {
+    let digits = value.to_string();
+    let (sign, rest) = match digits.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", digits.as_str()),
+    };
+    let int_len = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    let (int, tail) = rest.split_at(int_len);
+
+    let mut out = String::with_capacity(digits.len() + int_len / 3);
+    out.push_str(sign);
+    for (i, c) in int.chars().enumerate() {
+        if i > 0 && (int_len - i) % 3 == 0 {
+            out.push(separator);
+        }
+        out.push(c);
+    }
+    out.push_str(tail);
+    out
+}
+
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub enum Precision {
+    Compact,
+    Seconds,
+    Millis,
+}
+
+#[doc(hidden)]
+pub fn duration(duration: Duration, precision: Precision) -> String {
+    let secs = duration.as_secs();
+    let millis = duration.subsec_millis();
+    if secs == 0 {
+        let nanos = duration.subsec_nanos();
+        return match nanos {
+            0 => "0s".to_string(),
+            1..=999 => format!("{}ns", nanos),
+            1_000..=999_999 => format!("{}µs", nanos / 1_000),
+            _ => format!("{}ms", millis),
+        };
+    }
+
+    let parts = [
+        (secs / 86_400, "d"),
+        (secs % 86_400 / 3_600, "h"),
+        (secs % 3_600 / 60, "m"),
+        (secs % 60, "s"),
+        (u64::from(millis), "ms"),
+    ];
+    let shown = match precision {
+        Precision::Millis => parts.len(),
+        _ => parts.len() - 1,
+    };
+    let mut out = String::new();
+    for (value, unit) in parts[..shown].iter().filter(|(value, _)| *value > 0) {
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        out.push_str(&format!("{}{}", value, unit));
+        if let Precision::Compact = precision {
+            break;
+        }
+    }
+    out
+}
+
+#[doc(hidden)]
+pub fn ordinal(value: impl Display) -> String {
+    let mut out = value.to_string();
+    let digits = out.as_bytes();
+    let tens = digits.len() >= 2 && digits[digits.len() - 2] == b'1';
+    let suffix = match digits.last() {
+        Some(b'1') if !tens => "st",
+        Some(b'2') if !tens => "nd",
+        Some(b'3') if !tens => "rd",
+        _ => "th",
+    };
+    out.push_str(suffix);
+    out
+}
+
+#[doc(hidden)]
+pub fn roman<T: TryInto<u32> + Display + Copy>(value: T) -> String {
+    let mut n = match value.try_into() {
+        Ok(n @ 1..=3999) => n,
+        _ => panic!("roman numerals only cover 1 to 3999, got {}", value),
+    };
+    let mut out = String::new();
+    for (amount, numeral) in ROMAN {
+        while n >= amount {
+            out.push_str(numeral);
+            n -= amount;
+        }
+    }
+    out
+}
+
+/// A convenience macro for formatting a byte count with a unit.
+///
+/// This macro provides two ways to format a size:
+/// - With binary units (`KiB`, `MiB`, ...), multiples of 1024
+/// - With decimal SI units (`kB`, `MB`, ...), multiples of 1000 (`si` prefix)
+///
+/// Sizes of at least one kilobyte are shown with one decimal place, which
+/// is dropped when it is zero. Any primitive number is accepted.
+///
+/// # Examples
+///
+/// ## Binary units
+/// ```
+/// # use smacro::human_bytes;
+/// assert_eq!(human_bytes!(512), "512 B");
+/// assert_eq!(human_bytes!(1536), "1.5 KiB");
+/// assert_eq!(human_bytes!(1024 * 1024), "1 MiB");
+/// assert_eq!(human_bytes!(3_221_225_472u64), "3 GiB");
+/// ```
+///
+/// ## Decimal units
+/// ```
+/// # use smacro::human_bytes;
+/// let downloaded = 1_500_000;
+///
+/// assert_eq!(human_bytes!(si downloaded), "1.5 MB");
+/// ```
+#[macro_export]
+macro_rules! human_bytes {
+    (si $value:expr) => {
+        $crate::human::bytes(&$value, true)
+    };
+    ($value:expr) => {
+        $crate::human::bytes(&$value, false)
+    };
+}
+
+/// A convenience macro for formatting a number with thousands separators.
+///
+/// The separator defaults to `,` and can be given as a second argument.
+/// Any type implementing `Display` as plain digits works, including floats
+/// and negative numbers; only the integer part is grouped.
+///
+/// # Examples
+///
+/// ## Grouping digits
+/// ```
+/// # use smacro::human_num;
+/// assert_eq!(human_num!(1234567), "1,234,567");
+/// assert_eq!(human_num!(-9876.25), "-9,876.25");
+/// assert_eq!(human_num!(999), "999");
+/// ```
+///
+/// ## Custom separator
+/// ```
+/// # use smacro::human_num;
+/// let population = 83_200_000u64;
+///
+/// assert_eq!(human_num!(population, '.'), "83.200.000");
+/// assert_eq!(human_num!(population, ' '), "83 200 000");
+/// ```
+#[macro_export]
+macro_rules! human_num {
+    ($value:expr, $separator:expr $(,)?) => {
+        $crate::human::separated(&$value, $separator)
+    };
+    ($value:expr $(,)?) => {
+        $crate::human::separated(&$value, ',')
+    };
+}
+
+/// A convenience macro for formatting an integer as an English ordinal.
+///
+/// The `st`, `nd`, `rd` or `th` suffix is chosen from the last digits, with
+/// 11 to 13 always taking `th`. Any integer type is accepted.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::ordinal;
+/// let place = 3;
+///
+/// assert_eq!(ordinal!(place), "3rd");
+/// assert_eq!(ordinal!(11), "11th");
+/// assert_eq!(ordinal!(22u64), "22nd");
+/// ```
+#[macro_export]
+macro_rules! ordinal {
+    ($value:expr $(,)?) => {
+        $crate::human::ordinal(&$value)
+    };
+}
+
+/// A convenience macro for formatting an integer as a Roman numeral.
+///
+/// Uses the standard subtractive notation, such as `IV` for 4 and `CM` for
+/// 900. Any integer type is accepted.
+///
+/// # Panics
+///
+/// Panics if the value is not between 1 and 3999, the range standard Roman
+/// numerals can represent.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::roman;
+/// let year = 1994;
+///
+/// assert_eq!(roman!(year), "MCMXCIV");
+/// assert_eq!(roman!(4u8), "IV");
+/// ```
+#[macro_export]
+macro_rules! roman {
+    ($value:expr $(,)?) => {
+        $crate::human::roman($value)
+    };
+}
+
+/// A convenience macro for formatting a `Duration` for humans.
+///
+/// Durations of at least a second are written as their non-zero days,
+/// hours, minutes and seconds, such as `"1h 2m 13s"`, dropping anything
+/// below a second. Shorter durations are written in the largest fitting
+/// unit of `ms`, `µs` or `ns`, and a zero duration as `"0s"`. Values are
+/// truncated, never rounded up.
+///
+/// The precision can be changed after a `;`:
+/// - `compact` keeps only the largest unit, as in `"1h"`
+/// - `ms` also keeps the milliseconds, as in `"2m 13s 250ms"`
+///
+/// The duration can also be given as a reference.
+///
+/// # Examples
+///
+/// ## Elapsed time
+/// ```
+/// # use smacro::duration_s;
+/// use std::time::Duration;
+///
+/// let elapsed = Duration::from_millis(133_250);
+///
+/// assert_eq!(duration_s!(elapsed), "2m 13s");
+/// assert_eq!(duration_s!(Duration::from_micros(1500)), "1ms");
+/// ```
+///
+/// ## Precision
+/// ```
+/// # use smacro::duration_s;
+/// use std::time::Duration;
+///
+/// let uptime = Duration::from_secs(3 * 86_400 + 7_200 + 5);
+///
+/// assert_eq!(duration_s!(uptime), "3d 2h 5s");
+/// assert_eq!(duration_s!(&uptime; compact), "3d");
+/// assert_eq!(duration_s!(Duration::from_millis(61_005); ms), "1m 1s 5ms");
+/// ```
+#[macro_export]
+macro_rules! duration_s {
+    ($duration:expr; compact) => {
+        $crate::human::duration(
+            *core::borrow::Borrow::<std::time::Duration>::borrow(&$duration),
+            $crate::human::Precision::Compact,
+        )
+    };
+    ($duration:expr; ms) => {
+        $crate::human::duration(
+            *core::borrow::Borrow::<std::time::Duration>::borrow(&$duration),
+            $crate::human::Precision::Millis,
+        )
+    };
+    ($duration:expr $(,)?) => {
+        $crate::human::duration(
+            *core::borrow::Borrow::<std::time::Duration>::borrow(&$duration),
+            $crate::human::Precision::Seconds,
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn bytes_below_one_unit() {
+        assert_eq!(human_bytes!(0), "0 B");
+        assert_eq!(human_bytes!(1023), "1023 B");
+        assert_eq!(human_bytes!(si 999u16), "999 B");
+    }
+
+    #[test]
+    fn bytes_binary_units() {
+        assert_eq!(human_bytes!(1024), "1 KiB");
+        assert_eq!(human_bytes!(1_100_000), "1 MiB");
+        assert_eq!(human_bytes!(5.5 * 1024.0 * 1024.0 * 1024.0), "5.5 GiB");
+        assert_eq!(human_bytes!(u64::MAX), "16 EiB");
+    }
+
+    #[test]
+    fn bytes_decimal_units() {
+        assert_eq!(human_bytes!(si 1000), "1 kB");
+        assert_eq!(human_bytes!(si 2_345_678_901u64), "2.3 GB");
+    }
+
+    #[test]
+    fn bytes_rounding_up_to_next_unit() {
+        assert_eq!(human_bytes!(1_048_575), "1 MiB");
+        assert_eq!(human_bytes!(1_048_524), "1023.9 KiB");
+        assert_eq!(human_bytes!(si 999_999), "1 MB");
+        assert_eq!(human_bytes!(si 999_949), "999.9 kB");
+        assert_eq!(human_bytes!(-1_048_575), "-1 MiB");
+    }
+
+    #[test]
+    fn bytes_negative() {
+        assert_eq!(human_bytes!(-2048), "-2 KiB");
+    }
+
+    #[test]
+    fn num_grouping() {
+        assert_eq!(human_num!(0), "0");
+        assert_eq!(human_num!(100), "100");
+        assert_eq!(human_num!(1000), "1,000");
+        assert_eq!(human_num!(-100000), "-100,000");
+        assert_eq!(human_num!(i64::MIN), "-9,223,372,036,854,775,808");
+    }
+
+    #[test]
+    fn num_floats_and_separators() {
+        assert_eq!(human_num!(1234567.891), "1,234,567.891");
+        assert_eq!(human_num!(12345, '_'), "12_345");
+        let n = 1_000_000usize;
+        assert_eq!(human_num!(n), "1,000,000");
+        assert_eq!(n, 1_000_000);
+    }
+
+    #[test]
+    fn ordinals() {
+        let cases = [
+            (0, "0th"),
+            (1, "1st"),
+            (2, "2nd"),
+            (4, "4th"),
+            (12, "12th"),
+            (13, "13th"),
+        ];
+        for (n, expected) in cases {
+            assert_eq!(ordinal!(n), expected);
+        }
+        assert_eq!(ordinal!(101), "101st");
+        assert_eq!(ordinal!(112), "112th");
+        assert_eq!(ordinal!(-23), "-23rd");
+    }
+
+    #[test]
+    fn roman_numerals() {
+        assert_eq!(roman!(1), "I");
+        assert_eq!(roman!(49), "XLIX");
+        assert_eq!(roman!(2024usize), "MMXXIV");
+        assert_eq!(roman!(3999i64), "MMMCMXCIX");
+    }
+
+    #[test]
+    #[should_panic(expected = "roman numerals only cover 1 to 3999, got -1")]
+    fn roman_out_of_range() {
+        roman!(-1);
+    }
+
+    #[test]
+    fn durations() {
+        use std::time::Duration;
+
+        assert_eq!(duration_s!(Duration::ZERO), "0s");
+        assert_eq!(duration_s!(Duration::from_nanos(999)), "999ns");
+        assert_eq!(duration_s!(Duration::from_nanos(1_999)), "1µs");
+        assert_eq!(duration_s!(Duration::from_millis(999)), "999ms");
+        assert_eq!(duration_s!(Duration::from_millis(1_999)), "1s");
+        assert_eq!(duration_s!(Duration::from_secs(3_600)), "1h");
+        assert_eq!(duration_s!(Duration::from_secs(90_061); ms), "1d 1h 1m 1s");
+        assert_eq!(duration_s!(Duration::from_millis(250); compact), "250ms");
+        assert_eq!(
+            duration_s!(Duration::from_secs(59 * 60 + 59); compact),
+            "59m"
+        );
+    }
+}
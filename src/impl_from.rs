@@ -0,0 +1,122 @@
+//! Conversion impl utilities.
+//!
+//! The `impl_from!` macro generates `From` impls that wrap a value in an
+//! enum variant, the most repetitive boilerplate in hand-written error
+//! types.
+
+/// A convenience macro for implementing `From` for enum variants in bulk.
+///
+/// The macro takes the enum name, a `:`, and a list of `Type => Variant`
+/// pairs. For each pair it generates `From<Type>` for the enum, wrapping the
+/// value in the single-field tuple variant `Variant`. This lets `?` convert
+/// errors automatically.
+///
+/// For declaring the enum itself along with `Display` and `Error`, see
+/// [`error_set!`](macro@crate::error_set).
+///
+/// # Examples
+///
+/// ## Error conversions
+/// ```
+/// # use smacro::impl_from;
+/// use std::io;
+/// use std::num::ParseIntError;
+///
+/// #[derive(Debug)]
+/// enum ConfigError {
+///     Io(io::Error),
+///     Parse(ParseIntError),
+/// }
+///
+/// impl_from!(ConfigError: io::Error => Io, ParseIntError => Parse);
+///
+/// fn parse_port(input: &str) -> Result<u16, ConfigError> {
+///     Ok(input.parse()?)
+/// }
+///
+/// assert!(matches!(parse_port("http"), Err(ConfigError::Parse(_))));
+/// assert!(matches!(
+///     ConfigError::from(io::Error::other("boom")),
+///     ConfigError::Io(_)
+/// ));
+/// ```
+///
+/// ## Non-error enums
+/// ```
+/// # use smacro::impl_from;
+/// #[derive(Debug, PartialEq)]
+/// enum Value {
+///     Int(i64),
+///     Text(String),
+/// }
+///
+/// impl_from!(Value: i64 => Int, String => Text);
+///
+/// let values: Vec<Value> = vec![42.into(), "hi".to_string().into()];
+/// assert_eq!(values, [Value::Int(42), Value::Text("hi".to_string())]);
+/// ```
+#[macro_export]
+macro_rules! impl_from {
+    ($name:ident : $($from:ty => $variant:ident),+ $(,)?) => {
+        $(
+            impl core::convert::From<$from> for $name {
+                fn from(value: $from) -> Self {
+                    $name::$variant(value)
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt;
+    use std::num::{ParseFloatError, ParseIntError};
+
+    #[derive(Debug)]
+    enum AppError {
+        Int(ParseIntError),
+        Float(ParseFloatError),
+        Fmt(fmt::Error),
+        Other(Box<str>),
+    }
+
+    impl_from!(AppError:
+        ParseIntError => Int,
+        ParseFloatError => Float,
+        fmt::Error => Fmt,
+        Box<str> => Other,
+    );
+
+    fn parse_both(a: &str, b: &str) -> Result<(i32, f64), AppError> {
+        Ok((a.parse()?, b.parse()?))
+    }
+
+    #[test]
+    fn question_mark_converts() {
+        assert_eq!(parse_both("1", "2.5").unwrap(), (1, 2.5));
+        match parse_both("x", "2.5") {
+            Err(AppError::Int(e)) => assert_eq!(e.to_string(), "invalid digit found in string"),
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse_both("1", "y") {
+            Err(AppError::Float(e)) => assert_eq!(e.to_string(), "invalid float literal"),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn generic_and_path_types() {
+        assert!(matches!(AppError::from(fmt::Error), AppError::Fmt(_)));
+        match AppError::from(Box::<str>::from("boom")) {
+            AppError::Other(message) => assert_eq!(&*message, "boom"),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn into_uses_generated_impls() {
+        let err: AppError = "1.2.3".parse::<f32>().unwrap_err().into();
+        assert!(matches!(err, AppError::Float(_)));
+    }
+}
@@ -0,0 +1,171 @@
+//! Collection indexing utilities.
+//!
+//! Requires the `map` feature to be enabled.
+//!
+//! The `index_by!` macro builds a `HashMap` from a collection keyed by a
+//! field or any other value computed from each element, which replaces the
+//! usual loop over a `Vec` of structs.
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+#[doc(hidden)]
+pub fn index<I, K, F>(items: I, mut key: F) -> HashMap<K, I::Item>
+where
+    I: IntoIterator,
+    K: Eq + Hash,
+    F: FnMut(&I::Item) -> K,
+{
+    items.into_iter().map(|item| (key(&item), item)).collect()
+}
+
+#[doc(hidden)]
+pub fn index_strict<I, K, F>(items: I, mut key: F) -> Result<HashMap<K, I::Item>, String>
+where
+    I: IntoIterator,
+    K: Eq + Hash + Debug,
+    F: FnMut(&I::Item) -> K,
+{
+    let mut map = HashMap::new();
+    for item in items {
+        match map.entry(key(&item)) {
+            Entry::Occupied(entry) => return Err(format!("duplicate key {:?}", entry.key())),
+            Entry::Vacant(entry) => {
+                entry.insert(item);
+            }
+        }
+    }
+    Ok(map)
+}
+
+/// A convenience macro for indexing a collection by a key.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// The collection is followed by a closure computing the key of each
+/// element, and the result is a `HashMap` from keys to elements. Passing
+/// the collection by reference gives a `HashMap<K, &T>`, while passing it
+/// by value consumes it and gives a `HashMap<K, T>`.
+///
+/// This macro provides these additional modes:
+/// - `cloned;` clones the elements of a borrowed collection into a `HashMap<K, T>`
+/// - `strict;` returns a `Result` that is an `Err` naming the first duplicate key
+/// - `strict cloned;` combines both
+///
+/// Without `strict;`, the last element with a given key wins.
+///
+/// # Examples
+///
+/// ## Borrowing
+/// ```
+/// # use smacro::index_by;
+/// struct User {
+///     id: u32,
+///     name: &'static str,
+/// }
+///
+/// let users = vec![User { id: 1, name: "ada" }, User { id: 2, name: "alan" }];
+/// let by_id = index_by!(&users, |u| u.id);
+///
+/// assert_eq!(by_id[&2].name, "alan");
+/// ```
+///
+/// ## Owning
+/// ```
+/// # use smacro::index_by;
+/// let words = vec!["apple".to_string(), "banana".to_string()];
+///
+/// let copies = index_by!(cloned; &words, |w| w.len());
+/// assert_eq!(copies[&6], "banana");
+///
+/// let by_initial = index_by!(words, |w| w.chars().next().unwrap());
+/// assert_eq!(by_initial[&'a'], "apple");
+/// ```
+///
+/// ## Rejecting duplicates
+/// ```
+/// # use smacro::index_by;
+/// let emails = ["ada@example.com", "alan@example.com", "ada@example.com"];
+///
+/// let result = index_by!(strict; emails, |e| e.to_string());
+/// assert_eq!(result.unwrap_err(), "duplicate key \"ada@example.com\"");
+/// ```
+#[macro_export]
+macro_rules! index_by {
+    (strict cloned; $items:expr, $key:expr $(,)?) => {
+        $crate::index::index_strict(core::iter::IntoIterator::into_iter($items).cloned(), $key)
+    };
+    (strict; $items:expr, $key:expr $(,)?) => {
+        $crate::index::index_strict($items, $key)
+    };
+    (cloned; $items:expr, $key:expr $(,)?) => {
+        $crate::index::index(core::iter::IntoIterator::into_iter($items).cloned(), $key)
+    };
+    ($items:expr, $key:expr $(,)?) => {
+        $crate::index::index($items, $key)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Item {
+        sku: &'static str,
+        price: u32,
+    }
+
+    fn items() -> Vec<Item> {
+        vec![
+            Item {
+                sku: "a1",
+                price: 3,
+            },
+            Item {
+                sku: "b2",
+                price: 5,
+            },
+            Item {
+                sku: "c3",
+                price: 3,
+            },
+        ]
+    }
+
+    #[test]
+    fn borrowed_index() {
+        let items = items();
+        let by_sku: HashMap<&str, &Item> = index_by!(&items, |i| i.sku);
+        assert_eq!(by_sku.len(), 3);
+        assert_eq!(by_sku["b2"].price, 5);
+    }
+
+    #[test]
+    fn owned_index_last_wins() {
+        let by_price = index_by!(items(), |i| i.price);
+        assert_eq!(by_price.len(), 2);
+        assert_eq!(by_price[&3].sku, "c3");
+    }
+
+    #[test]
+    fn cloned_index_keeps_source() {
+        let items = items();
+        let by_sku: HashMap<&str, Item> = index_by!(cloned; &items, |i| i.sku,);
+        assert_eq!(by_sku["a1"], items[0]);
+        assert_eq!(items.len(), 3);
+    }
+
+    #[test]
+    fn strict_index() {
+        let items = items();
+        assert_eq!(index_by!(strict; &items, |i| i.sku).unwrap().len(), 3);
+        assert_eq!(
+            index_by!(strict cloned; &items, |i| i.price).unwrap_err(),
+            "duplicate key 3"
+        );
+        assert!(index_by!(strict; Vec::<Item>::new(), |i| i.price).is_ok());
+    }
+}
@@ -0,0 +1,179 @@
+//! Interval set utilities.
+//!
+//! The `interval_set!` macro merges overlapping and adjacent ranges into a
+//! sorted [`IntervalSet`], which can then answer membership queries with a
+//! binary search. Useful for schedulers, allocators and text-span tooling.
+
+use core::fmt;
+use core::ops::{Deref, Range};
+
+/// A sorted set of disjoint, non-adjacent, non-empty half-open ranges.
+///
+/// Created with [`interval_set!`](crate::interval_set) or [`IntervalSet::new`].
+/// It dereferences to a slice of ranges, so it can be iterated, indexed and
+/// compared like one.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Ord + Clone> IntervalSet<T> {
+    /// Builds a set from ranges in any order, dropping empty ranges and
+    /// merging those that overlap or touch.
+    pub fn new(ranges: impl IntoIterator<Item = Range<T>>) -> Self {
+        let mut sorted: Vec<Range<T>> = ranges.into_iter().filter(|r| r.start < r.end).collect();
+        sorted.sort_by(|a, b| a.start.cmp(&b.start));
+
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(sorted.len());
+        for range in sorted {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => {
+                    if range.end > last.end {
+                        last.end = range.end;
+                    }
+                }
+                _ => merged.push(range),
+            }
+        }
+        IntervalSet { ranges: merged }
+    }
+
+    /// Returns `true` if any of the ranges contains `value`.
+    pub fn contains(&self, value: &T) -> bool {
+        let after = self.ranges.partition_point(|r| r.start <= *value);
+        after > 0 && *value < self.ranges[after - 1].end
+    }
+}
+
+impl<T> IntervalSet<T> {
+    /// Returns the merged ranges, sorted by start.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    /// Consumes the set, returning the merged ranges.
+    pub fn into_vec(self) -> Vec<Range<T>> {
+        self.ranges
+    }
+}
+
+impl<T> Deref for IntervalSet<T> {
+    type Target = [Range<T>];
+
+    fn deref(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+}
+
+impl<T> IntoIterator for IntervalSet<T> {
+    type Item = Range<T>;
+    type IntoIter = std::vec::IntoIter<Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.into_iter()
+    }
+}
+
+impl<T: Ord + Clone> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        IntervalSet::new(iter)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.ranges).finish()
+    }
+}
+
+/// A convenience macro for creating sets of merged ranges.
+///
+/// The macro takes half-open ranges (`a..b`) in any order and returns an
+/// [`IntervalSet`] in which overlapping and adjacent ranges are merged,
+/// empty ranges are dropped and the result is sorted. The bounds can be any
+/// `Ord + Clone` type.
+///
+/// # Examples
+///
+/// ## Merging ranges
+/// ```
+/// # use smacro::interval_set;
+/// let busy = interval_set!(30..40, 0..10, 5..20, 20..25);
+///
+/// assert_eq!(*busy, [0..25, 30..40]);
+/// assert!(busy.contains(&24));
+/// assert!(!busy.contains(&25));
+/// ```
+///
+/// ## Text spans
+/// ```
+/// # use smacro::interval_set;
+/// let text = "let x = 1; // note";
+/// let highlighted = interval_set!(0..3, 11..18, 15..18);
+///
+/// let spans: Vec<&str> = highlighted.iter().map(|r| &text[r.clone()]).collect();
+/// assert_eq!(spans, ["let", "// note"]);
+/// ```
+#[macro_export]
+macro_rules! interval_set {
+    () => {
+        $crate::interval::IntervalSet::new([])
+    };
+    ($($range:expr),+ $(,)?) => {
+        $crate::interval::IntervalSet::new([$($range),+])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IntervalSet;
+
+    #[test]
+    fn merges_overlapping_and_adjacent() {
+        let set = interval_set!(0..10, 5..20, 30..40);
+        assert_eq!(*set, [0..20, 30..40]);
+        assert_eq!(interval_set!(0..5, 5..10,)[0], 0..10);
+        assert_eq!(
+            interval_set!(0..100, 10..20, 50..60).into_vec(),
+            [0..100; 1]
+        );
+    }
+
+    #[test]
+    fn drops_empty_ranges() {
+        let (high, low) = (8, 3);
+        let set = interval_set!(5..5, high..low, 1..2, 9..10);
+        assert_eq!(set.ranges(), [1..2, 9..10]);
+
+        let empty: IntervalSet<u8> = interval_set!();
+        assert!(empty.is_empty());
+        assert!(!empty.contains(&0));
+    }
+
+    #[test]
+    fn contains_respects_bounds() {
+        let set = interval_set!(10..20, -5..0);
+        assert!(set.contains(&-5));
+        assert!(!set.contains(&0));
+        assert!(set.contains(&10));
+        assert!(set.contains(&19));
+        assert!(!set.contains(&20));
+        assert!(!set.contains(&-6));
+    }
+
+    #[test]
+    fn non_numeric_bounds() {
+        let set = interval_set!("b".."d", "a".."c", "x".."z");
+        assert_eq!(format!("{:?}", set), r#"["a".."d", "x".."z"]"#);
+        assert!(set.contains(&"cat"));
+        assert!(!set.contains(&"d"));
+    }
+
+    #[test]
+    fn collect_and_iterate() {
+        let set: IntervalSet<u32> = (0..5).map(|i| i * 3..i * 3 + 2).collect();
+        assert_eq!(set.len(), 5);
+        let lengths: Vec<u32> = set.into_iter().map(|r| r.end - r.start).collect();
+        assert_eq!(lengths, [2; 5]);
+    }
+}
@@ -0,0 +1,243 @@
+//! Concurrent await utilities.
+//!
+//! Requires the `async` feature to be enabled.
+//!
+//! The `join_all!` and `try_join_all!` macros await several futures
+//! concurrently and return their outputs as a tuple, with the same
+//! semantics as `futures::join!` and `futures::try_join!`, without depending
+//! on the `futures` crate or on a particular runtime.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+#[doc(hidden)]
+pub enum MaybeDone<F: Future> {
+    Pending(Pin<Box<F>>),
+    Done(F::Output),
+    Taken,
+}
+
+impl<F: Future> MaybeDone<F> {
+    #[doc(hidden)]
+    pub fn new(future: F) -> Self {
+        MaybeDone::Pending(Box::pin(future))
+    }
+
+    /// Polls the inner future if needed, returning `true` once it has completed.
+    #[doc(hidden)]
+    pub fn poll(&mut self, cx: &mut Context<'_>) -> bool {
+        if let MaybeDone::Pending(future) = self {
+            match future.as_mut().poll(cx) {
+                Poll::Ready(output) => *self = MaybeDone::Done(output),
+                Poll::Pending => return false,
+            }
+        }
+        true
+    }
+
+    #[doc(hidden)]
+    pub fn take(&mut self) -> F::Output {
+        match std::mem::replace(self, MaybeDone::Taken) {
+            MaybeDone::Done(output) => output,
+            _ => unreachable!("future output taken before completion"),
+        }
+    }
+}
+
+impl<F, T, E> MaybeDone<F>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    /// Like [`poll`](MaybeDone::poll), but returns the error as soon as the future fails.
+    #[doc(hidden)]
+    pub fn try_poll(&mut self, cx: &mut Context<'_>) -> Result<bool, E> {
+        if !self.poll(cx) {
+            return Ok(false);
+        }
+        match self {
+            MaybeDone::Done(Err(_)) => self.take().map(|_| true),
+            _ => Ok(true),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn take_ok(&mut self) -> T {
+        match self.take() {
+            Ok(value) => value,
+            Err(_) => unreachable!("failed future output taken as success"),
+        }
+    }
+}
+
+/// A convenience macro for awaiting several futures concurrently.
+///
+/// Requires the `async` feature to be enabled.
+///
+/// All futures are polled concurrently on the current task, and the macro
+/// evaluates to a tuple of their outputs once all of them have completed.
+/// It must be used inside an `async` context, and works with any runtime.
+///
+/// # Examples
+///
+/// ## Joining futures
+/// ```
+/// # use smacro::join_all;
+/// async fn fetch_user() -> &'static str {
+///     "ada"
+/// }
+///
+/// async fn fetch_count() -> u32 {
+///     3
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let (user, count) = join_all!(fetch_user(), fetch_count());
+///
+/// assert_eq!(user, "ada");
+/// assert_eq!(count, 3);
+/// # });
+/// ```
+///
+/// # Performance Note
+///
+/// Each future is boxed so it can be polled in place without `unsafe`
+/// pinning, which costs one allocation per future. Up to 32 futures can be
+/// joined at once.
+#[macro_export]
+macro_rules! join_all {
+    (@zip $mode:ident [$($done:tt)*] [$index:tt $($indices:tt)*] $future:expr $(, $rest:expr)*) => {
+        $crate::join_all!(@zip $mode [$($done)* ($index $future)] [$($indices)*] $($rest),*)
+    };
+    (@zip join [$(($index:tt $future:expr))+] [$($unused:tt)*]) => {
+        {
+            let mut futures = ($($crate::join::MaybeDone::new($future),)+);
+            core::future::poll_fn(move |cx| {
+                let mut ready = true;
+                $(
+                    ready &= futures.$index.poll(cx);
+                )+
+                if ready {
+                    core::task::Poll::Ready(($(futures.$index.take(),)+))
+                } else {
+                    core::task::Poll::Pending
+                }
+            })
+            .await
+        }
+    };
+    (@zip try_join [$(($index:tt $future:expr))+] [$($unused:tt)*]) => {
+        {
+            let mut futures = ($($crate::join::MaybeDone::new($future),)+);
+            core::future::poll_fn(move |cx| {
+                let mut ready = true;
+                $(
+                    match futures.$index.try_poll(cx) {
+                        Ok(done) => ready &= done,
+                        Err(err) => return core::task::Poll::Ready(Err(err)),
+                    }
+                )+
+                if ready {
+                    core::task::Poll::Ready(Ok(($(futures.$index.take_ok(),)+)))
+                } else {
+                    core::task::Poll::Pending
+                }
+            })
+            .await
+        }
+    };
+    ($($future:expr),+ $(,)?) => {
+        $crate::join_all!(
+            @zip join []
+            [0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31]
+            $($future),+
+        )
+    };
+}
+
+/// A convenience macro for awaiting several fallible futures concurrently.
+///
+/// Requires the `async` feature to be enabled.
+///
+/// All futures must return a `Result` with the same error type. The macro
+/// evaluates to `Ok` with a tuple of the success values once all of them
+/// have succeeded, or to the first `Err` as soon as any future fails, in
+/// which case the remaining futures are dropped.
+///
+/// # Examples
+///
+/// ## Joining fallible futures
+/// ```
+/// # use smacro::try_join_all;
+/// async fn parse(input: &str) -> Result<u32, String> {
+///     input.parse().map_err(|_| format!("bad number: {}", input))
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let ok = try_join_all!(parse("1"), parse("2"));
+/// let err = try_join_all!(parse("1"), parse("two"));
+///
+/// assert_eq!(ok, Ok((1, 2)));
+/// assert_eq!(err, Err("bad number: two".to_string()));
+/// # });
+/// ```
+#[macro_export]
+macro_rules! try_join_all {
+    ($($future:expr),+ $(,)?) => {
+        $crate::join_all!(
+            @zip try_join []
+            [0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31]
+            $($future),+
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::oneshot;
+
+    async fn value<T>(v: T) -> T {
+        v
+    }
+
+    #[tokio::test]
+    async fn join_single_future() {
+        let (v,) = join_all!(value(1));
+        assert_eq!(v, 1);
+    }
+
+    #[tokio::test]
+    async fn join_mixed_outputs() {
+        let (a, b, c) = join_all!(value(1), value("two"), async { 3.0 });
+        assert_eq!((a, b, c), (1, "two", 3.0));
+    }
+
+    #[tokio::test]
+    async fn join_polls_concurrently() {
+        let (tx, rx) = oneshot::channel();
+        let (received, ()) = join_all!(async { rx.await.unwrap() }, async {
+            tx.send(5).unwrap();
+        });
+        assert_eq!(received, 5);
+    }
+
+    #[tokio::test]
+    async fn try_join_success() {
+        let r: Result<_, ()> = try_join_all!(value(Ok(1)), value(Ok("a")));
+        assert_eq!(r, Ok((1, "a")));
+    }
+
+    #[tokio::test]
+    async fn try_join_short_circuits() {
+        let (_tx, rx) = oneshot::channel::<()>();
+        // The first future never completes, but the error is returned anyway.
+        let r: Result<((), ()), &str> = try_join_all!(
+            async {
+                rx.await.ok();
+                Ok(())
+            },
+            value(Err("failed")),
+        );
+        assert_eq!(r, Err("failed"));
+    }
+}
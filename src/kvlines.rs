@@ -0,0 +1,140 @@
+//! Key-value text parsing utilities.
+//!
+//! Requires the `map` feature to be enabled.
+//!
+//! The `kvlines!` macro parses newline-separated `key=value` or
+//! `key: value` text, such as simple config or `.env` files, into a
+//! `HashMap<String, String>`. It is the inverse of `map!` for quick
+//! ingestion of flat settings.
+
+use std::collections::HashMap;
+
+#[doc(hidden)]
+pub struct Options<'a> {
+    pub comment: &'a str,
+    pub trim: bool,
+}
+
+impl Default for Options<'_> {
+    fn default() -> Self {
+        Options {
+            comment: "#",
+            trim: true,
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn parse(input: &str, options: Options<'_>) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    for line in input.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || !options.comment.is_empty() && trimmed.starts_with(options.comment)
+        {
+            continue;
+        }
+
+        let Some(at) = line.find(['=', ':']) else {
+            continue;
+        };
+        let (key, value) = (&line[..at], &line[at + 1..]);
+        let (key, value) = if options.trim {
+            (key.trim(), value.trim())
+        } else {
+            (key, value)
+        };
+        map.insert(key.to_string(), value.to_string());
+    }
+    map
+}
+
+/// A convenience macro for parsing `key=value` lines into a map.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// Each line is split at its first `=` or `:`, giving a
+/// `HashMap<String, String>`. Blank lines, comment lines and lines without
+/// a separator are skipped, and later keys override earlier ones.
+///
+/// Options can follow the input after a `;`:
+/// - `comment = "..."` sets the comment prefix, `#` by default, or disables
+///   comments when empty
+/// - `trim = false` keeps the whitespace around keys and values
+///
+/// # Examples
+///
+/// ## Parsing a config
+/// ```
+/// # use smacro::kvlines;
+/// let config = kvlines!("
+///     # server settings
+///     host = localhost
+///     port: 8080
+///     url = http://example.com/?a=b
+/// ");
+///
+/// assert_eq!(config.len(), 3);
+/// assert_eq!(config["host"], "localhost");
+/// assert_eq!(config["port"], "8080");
+/// assert_eq!(config["url"], "http://example.com/?a=b");
+/// ```
+///
+/// ## Options
+/// ```
+/// # use smacro::kvlines;
+/// let text = String::from("; ini-style comment\nname= padded \n");
+/// let values = kvlines!(&text; comment = ";", trim = false);
+///
+/// assert_eq!(values.len(), 1);
+/// assert_eq!(values["name"], " padded ");
+/// ```
+#[macro_export]
+macro_rules! kvlines {
+    ($input:expr $(; $($option:ident = $value:expr),* $(,)?)?) => {
+        {
+            #[allow(unused_mut)]
+            let mut options = $crate::kvlines::Options::default();
+            $($(options.$option = $value;)*)?
+            $crate::kvlines::parse(core::convert::AsRef::<str>::as_ref(&$input), options)
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn both_separators() {
+        let m = kvlines!("a=1\nb: 2\nc = x:y\nd: e=f");
+        assert_eq!(m.len(), 4);
+        assert_eq!(m["a"], "1");
+        assert_eq!(m["b"], "2");
+        assert_eq!(m["c"], "x:y");
+        assert_eq!(m["d"], "e=f");
+    }
+
+    #[test]
+    fn skips_blank_comment_and_invalid_lines() {
+        let m = kvlines!("\n  # comment = ignored\nno separator here\nkey=\n\n");
+        assert_eq!(m.len(), 1);
+        assert_eq!(m["key"], "");
+    }
+
+    #[test]
+    fn last_key_wins() {
+        let m = kvlines!("mode = debug\r\nmode = release\r\n");
+        assert_eq!(m["mode"], "release");
+    }
+
+    #[test]
+    fn custom_comment_and_no_trim() {
+        let input = String::from("// note\n#tag = 1\n key = v ");
+        let m = kvlines!(input; comment = "//", trim = false,);
+        assert_eq!(m.len(), 2);
+        assert_eq!(m["#tag "], " 1");
+        assert_eq!(m[" key "], " v ");
+
+        let m = kvlines!(input; comment = "");
+        assert_eq!(m["#tag"], "1");
+        assert_eq!(m["key"], "v");
+    }
+}
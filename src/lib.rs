@@ -9,6 +9,8 @@
 //! ## Quick Start
 //!
 //! ```rust
+//! # #[cfg(all(feature = "set", feature = "map"))]
+//! # {
 //! use smacro::{s, set, map};
 //!
 //! // String creation
@@ -22,20 +24,323 @@
 //!     "debug" => "true",
 //!     "port" => "8080",
 //! ];
+//! # }
 //! ```
 //!
 //! ## Available Macros
 //!
 //! - [`s!`] - Create `String` instances with various input types
-//! - [`set!`] - Create `HashSet` instances with initial values
-//! - [`map!`] - Create `HashMap` instances with key-value pairs
+//! - [`sarr!`] - Create `[String; N]` or `[&str; N]` arrays from mixed inputs
+//! - [`set!`] - Create `HashSet` instances, or any other collection, with initial values
+//! - [`map!`] - Create `HashMap` instances, insertion-ordered maps or any other map type from key-value pairs
+//! - [`fields_map!`] - Capture local variables in a map keyed by name
+//! - [`tuple_map!`] - Write an array of pairs with `map!` syntax
+//! - [`index_by!`] - Index a collection by a key computed from each element
+//! - [`invert_map!`] - Swap the keys and values of a map
+//! - [`retain_keys!`], [`without_keys!`] - Copy a map keeping or dropping a list of keys
+//! - [`pick!`] - Copy selected keys of a map into a new map
+//! - [`rename_keys!`] - Copy a map with some of its keys renamed
+//! - [`map_values!`], [`map_keys!`] - Transform the values or keys of a map
+//! - [`sum_values!`], [`merge_counts!`] - Total the values of a map or add up maps key by key
+//! - [`scale_map!`] - Multiply every value of a map, with more map arithmetic in [`maps::math`]
+//! - [`anymap!`] - Create a type map holding one value per type
+//! - [`get_path!`] - Look up a value at a path of nested maps
+//! - [`insert_path!`] - Insert a value at a path of nested maps, creating missing levels
+//! - [`partition!`], [`partition_set!`], [`partition_by!`] - Split a collection by a predicate or group it by a key
+//! - [`chunked!`], [`windows_vec!`] - Split any iterable into chunks or overlapping windows
+//! - [`first_duplicate!`], [`has_duplicates!`] - Find repeated elements in an iterable
+//! - [`top_n!`] - Select the largest entries of a map or iterable
+//! - [`weak!`], [`weak_map!`] - Downgrade `Arc`/`Rc` pointers, alone or as map values
+//! - [`csvrow!`], [`tsv!`] - Format values as a correctly escaped CSV or TSV line
+//! - [`sdebug_sorted!`] - Debug-format a `HashMap` or `HashSet` with sorted keys
+//! - [`to_sorted_vec!`] - Convert a `HashMap` or `HashSet` into a `Vec` sorted by key
+//! - [`concat_path!`] - Join path components at compile time
+//! - [`wstr!`], [`utf16!`] - Encode strings as UTF-16 for wide-character APIs
+//! - [`plural!`] - Pick the singular or plural form of a word for a count
+//! - [`human_bytes!`], [`human_num!`] - Format byte counts and large numbers for humans
+//! - [`ordinal!`], [`roman!`] - Format integers as English ordinals or Roman numerals
+//! - [`duration_s!`] - Format a `Duration` with units, such as `2m 13s`
+//! - [`kvlines!`] - Parse `key=value` lines into a map
+//! - [`to_query_map!`] - Parse a percent-encoded query string into a map
+//! - [`fixture_map!`], [`include_fixtures!`] - Load fixture files into a map keyed by file stem
+//! - [`matches_any!`], [`matches_all!`] - Test values against several patterns at once
+//! - [`min_of!`], [`max_of!`], [`minmax!`] - Find the smallest/largest of several values
+//! - [`sum_of!`], [`avg!`] - Add up or average several values
+//! - [`in_range!`], [`clamp_to!`] - Check or clamp a value against any range
+//! - [`interval_set!`] - Merge ranges into a sorted set with fast membership checks
+//! - [`seq!`] - Create stepped, possibly descending, numeric sequences
+//! - [`record!`] - Create anonymous records with named fields
+//! - [`default!`] - Create default values, optionally overriding struct fields
+//! - [`update!`], [`swap_fields!`] - Assign or swap several struct fields at once
+//! - [`newtype!`] - Declare newtype wrappers with conversion and `Deref` impls
+//! - [`display!`] - Implement `Display` from a format string
+//! - [`from_str!`] - Implement `FromStr` for enums from a table of strings
+//! - [`error_set!`] - Declare error enums with `Display`, `Error` and `From` impls
+//! - [`impl_from!`] - Implement `From` for several enum variants at once
+//! - [`bail!`], [`ensure!`] - Return early with a formatted error
+//! - [`guard!`] - Return early unless a pattern matches or a condition holds
+//! - [`let_some!`], [`let_ok!`] - Unwrap an `Option` or `Result`, or run a diverging branch
+//! - [`wrap_err!`] - Add a formatted context message to the error of a `Result`
+//! - [`context!`] - Create a structured key-value context for errors and log lines
+//! - [`attach!`] - Attach a context to the error of a `Result`
+//! - [`ok_or_s!`] - Convert an `Option` into a `Result` with a formatted message
+//! - [`table_s!`] - Format a header and rows into an aligned text table
+//! - [`wrap_text!`] - Word-wrap formatted text to a width, with an optional hang indent
+//! - [`pad_left!`], [`pad_right!`], [`center!`] - Align values to runtime widths with a fill character
+//! - [`version_s!`] - Assemble a `--version` string from the package version, git commit and build date
+//! - [`prompt!`], [`read_line!`] - Prompt for and read trimmed, optionally parsed, lines from stdin
+//! - [`infoln!`], [`warnln!`], [`errln!`] - Write leveled, optionally timestamped messages to stderr
+//! - [`progress!`], [`finish!`] - Rewrite a single progress line on stderr
+//! - [`read_s!`], [`write_s!`] - Read or write files with `s!` formatted paths and content
+//! - [`lines_of!`], [`lines_set!`] - Read the lines of a file into a `Vec` or `HashSet`, at runtime or compile time
+//! - [`cmd!`] - Build a `Command` with arguments, environment and working directory
+//! - [`sh!`] - Run a formatted command line and capture its output as a `String`
+//! - [`retry!`] - Retry fallible operations with a fixed or exponential backoff
+//! - [`singleton!`] - Declare lazily initialized, lock-protected global state
+//! - [`table_test!`] - Generate one named test per row of a table of cases
+//! - [`pipe!`] - Thread a value through a chain of functions
+//! - [`tap!`] - Inspect a value with a side effect and pass it through
+//! - [`aspawn!`] - Spawn `tokio` tasks, cloning the listed captures first
+//! - [`join_all!`], [`try_join_all!`] - Await several futures concurrently
+//! - [`sleep_ms!`], [`timeout!`] - Sleep or bound a future with a deadline, on `tokio` or `async-std`
+//! - [`metrics_tags!`] - Build labels for the `metrics` crate from key-value pairs
+//! - [`sample!`], [`choose!`] - Pick random elements, optionally weighted
+//! - [`shuffle!`], [`shuffled!`] - Shuffle collections, optionally with a seed
+//! - [`rand_string!`], [`rand_map!`] - Generate random strings and maps as test data
+//! - [`gen_range_set!`] - Generate a set of distinct random integers from a range
+//! - [`timestamp_s!`] - Format the current time or an instant as RFC 3339 or with a format string
+//! - [`home!`], [`config_dir!`], [`cache_dir!`] - Locate user directories, joined with a formatted subpath
+//! - [`glob_vec!`] - Collect the paths matching a glob pattern into a sorted `Vec`
+//! - [`f!`] - Format strings with arbitrary embedded expressions
+//! - [`ToMap`], [`FromMap`] - Derivable conversions between structs and string maps
+//! - [`getset!`] - Generate getters, setters and `with_` methods for struct fields
+//! - [`builder!`] - Declare a struct with a builder, defaults and required fields
+//! - [`include_map!`] - Embed a two-column data file as a map at compile time
+//! - [`toml_map!`], [`yaml_map!`] - Declare TOML or YAML fixtures inline, checked at compile time
+//! - [`cs!`] - Color and style strings with ANSI escape codes
+//! - [`base64!`] - Encode or decode Base64 string literals at compile time
+//! - [`tmpfile!`], [`tmpdir!`] - Create temporary fixture files and directories
 //!
 
 // Re-export all macros
+pub mod bail;
+pub mod chunk;
+pub mod cmd;
+pub mod console;
+pub mod context;
+pub mod csv;
+pub mod default;
+pub mod display;
+pub mod duplicate;
+pub mod error_set;
+pub mod from_str;
+pub mod fs;
+pub mod guard;
+pub mod human;
+pub mod impl_from;
+pub mod interval;
+pub mod matches;
+pub mod minmax;
+pub mod newtype;
+pub mod ok_or_s;
+pub mod partition;
+pub mod path;
+pub mod pipe;
+pub mod plural;
+pub mod range;
+pub mod record;
 pub mod s;
+pub mod sarr;
+pub mod seq;
+pub mod singleton;
+pub mod sorted;
+pub mod stats;
+pub mod table;
+pub mod table_test;
+pub mod tap;
+pub mod text;
+pub mod top;
+pub mod update;
+pub mod version;
+pub mod weak;
+pub mod wide;
+pub mod wrap_err;
+
+#[cfg(feature = "map")]
+pub mod anymap;
+
+#[cfg(feature = "tokio")]
+pub mod aspawn;
+
+#[cfg(feature = "base64")]
+pub mod base64;
+
+#[cfg(feature = "derive")]
+pub mod builder;
+
+#[cfg(feature = "color")]
+pub mod color;
+
+#[cfg(feature = "dirs")]
+pub mod dirs;
+
+#[cfg(feature = "map")]
+pub mod fixture;
+
+#[cfg(feature = "derive")]
+pub mod getset;
+
+#[cfg(feature = "glob")]
+pub mod glob;
+
+#[cfg(feature = "async")]
+pub mod join;
+
+#[cfg(feature = "map")]
+pub mod index;
+
+#[cfg(feature = "map")]
+pub mod kvlines;
 
 #[cfg(feature = "map")]
 pub mod map;
 
+#[cfg(feature = "map")]
+pub mod maps;
+
+#[cfg(feature = "map")]
+pub mod ordered;
+
+#[cfg(feature = "map")]
+pub mod query;
+
+#[cfg(feature = "metrics")]
+pub mod metrics_tags;
+
+#[cfg(feature = "rand")]
+pub mod random;
+
+#[cfg(feature = "retry")]
+pub mod retry;
+
 #[cfg(feature = "set")]
 pub mod set;
+
+#[cfg(any(feature = "tokio", feature = "async-std"))]
+pub mod timeout;
+
+#[cfg(feature = "chrono")]
+pub mod timestamp;
+
+#[cfg(feature = "tempfile")]
+pub mod tmp;
+
+#[cfg(feature = "derive")]
+pub mod to_map;
+
+#[cfg(feature = "fstring")]
+pub use smacro_derive::f;
+
+/// A convenience macro for embedding a two-column data file as a map.
+///
+/// Requires the `derive` feature to be enabled.
+///
+/// The path is relative to the file calling the macro, like `include_str!`.
+/// The file is read and parsed at compile time, and the macro expands to a
+/// `HashMap<&'static str, &'static str>` built from string literals, so
+/// nothing is parsed at runtime.
+///
+/// Each line is split at its first `=`, or at its first run of whitespace
+/// if it has none, and both sides are trimmed. Blank lines and lines
+/// starting with `#` are skipped, and later keys override earlier ones. A
+/// line with only one column is a compile error. The file is tracked with
+/// `include_str!`, so editing it triggers a rebuild.
+///
+/// # Examples
+///
+/// ## Embedding a table
+/// ```
+/// use smacro::include_map;
+///
+/// // tests/data/mime.types:
+/// //   html text/html
+/// //   json = application/json
+/// //   css    text/css
+/// //   html application/xhtml+xml
+/// let mime = include_map!("../tests/data/mime.types");
+///
+/// assert_eq!(mime["json"], "application/json");
+/// assert_eq!(mime["html"], "application/xhtml+xml");
+/// ```
+#[cfg(feature = "derive")]
+pub use smacro_derive::include_map;
+#[cfg(feature = "derive")]
+pub use to_map::{FromMap, ToMap};
+
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub use smacro_derive::paste as __paste;
+
+/// A convenience macro for declaring a TOML document inline, checked at compile time.
+///
+/// Requires the `toml` feature to be enabled.
+///
+/// The macro takes a string literal and fails to compile if it is not a
+/// valid TOML document, reporting the parser's message. It expands to an
+/// expression parsing the literal into a `toml::Table` at runtime, which
+/// cannot fail.
+///
+/// # Examples
+///
+/// ## Inline fixture
+/// ```
+/// use smacro::toml_map;
+///
+/// let config = toml_map!(r#"
+///     name = "smacro"
+///     [server]
+///     port = 8080
+///     hosts = ["a", "b"]
+/// "#);
+///
+/// assert_eq!(config["name"].as_str(), Some("smacro"));
+/// assert_eq!(config["server"]["port"].as_integer(), Some(8080));
+/// ```
+#[cfg(feature = "toml")]
+pub use smacro_derive::toml_map;
+#[cfg(feature = "toml")]
+#[doc(hidden)]
+pub use toml as __toml;
+
+/// A convenience macro for declaring a YAML mapping inline, checked at compile time.
+///
+/// Requires the `yaml` feature to be enabled.
+///
+/// The macro takes a string literal and fails to compile if it is not a
+/// valid YAML document whose root is a mapping, reporting the parser's
+/// message. It expands to an expression parsing the literal into a
+/// `serde_yaml::Mapping` at runtime, which cannot fail.
+///
+/// # Examples
+///
+/// ## Inline fixture
+/// ```
+/// use smacro::yaml_map;
+///
+/// let user = yaml_map!("
+/// name: Ada
+/// roles: [admin, dev]
+/// ");
+///
+/// assert_eq!(user["name"].as_str(), Some("Ada"));
+/// assert_eq!(user["roles"][1].as_str(), Some("dev"));
+/// ```
+#[cfg(feature = "yaml")]
+pub use smacro_derive::yaml_map;
+#[cfg(feature = "yaml")]
+#[doc(hidden)]
+pub use serde_yaml as __serde_yaml;
+
+// Lets the procedural macros refer to `::smacro` in this crate's own tests
+#[cfg(test)]
+extern crate self as smacro;
@@ -3,15 +3,24 @@
 //!
 //! The `map!` macro provides a convenient way to create `HashMap` instances
 //! with initial key-value pairs, supporting any types that implement the
-//! required traits for HashMap keys and values.
+//! required traits for HashMap keys and values. The `fields_map!` macro
+//! builds a map of strings from local variables, keyed by their names, and
+//! `tuple_map!` turns the same literal syntax into an array of pairs.
 
 /// A convenience macro for creating `HashMap` instances with initial key-value pairs.
 ///
 /// Requires the `map` feature to be enabled.
 ///
-/// This macro provides two ways to create a `HashMap`:
+/// This macro provides seven ways to create a map:
 /// - Create an empty map
 /// - Create a map with initial key-value pairs
+/// - Create a two-level map of maps, with two keys per entry
+/// - Create a map with identifier keys, written like a struct literal
+/// - Create a map converting every key with `Into`, prefixed with `into;`
+/// - Create an [`OrderedMap`](crate::ordered::OrderedMap) keeping the
+///   literal's order, prefixed with `ordered;`
+/// - Create any other map type implementing `FromIterator`, named after
+///   `in` and followed by `;`
 ///
 /// # Examples
 ///
@@ -80,6 +89,103 @@
 /// assert_eq!(calculations[&7], 20);
 /// ```
 ///
+/// ## Two-level maps
+///
+/// Entries with two arrows build a `HashMap<K1, HashMap<K2, V>>`, merging
+/// the entries that share their first key into the same inner map:
+/// ```
+/// # use smacro::map;
+/// let rates = map![
+///     "EUR" => "USD" => 1.08,
+///     "EUR" => "GBP" => 0.85,
+///     "USD" => "EUR" => 0.93,
+/// ];
+///
+/// assert_eq!(rates.len(), 2);
+/// assert_eq!(rates["EUR"]["GBP"], 0.85);
+/// assert_eq!(rates["USD"].len(), 1);
+/// ```
+///
+/// ## Identifier keys
+///
+/// Bare identifiers followed by `:` are stringified into `&'static str`
+/// keys, so config literals read like struct literals:
+/// ```
+/// # use smacro::map;
+/// let config = map! {
+///     debug: "true",
+///     port: "8080",
+/// };
+///
+/// assert_eq!(config["debug"], "true");
+/// assert_eq!(config["port"], "8080");
+/// ```
+///
+/// ## Converting keys with `Into`
+///
+/// With `into;`, every key is converted with `Into`, so newtype keys can be
+/// written as raw literals while the map stays strongly typed. The key type
+/// can be given after `into`, or inferred from the map's type:
+/// ```
+/// # use smacro::map;
+/// use std::collections::HashMap;
+///
+/// #[derive(Debug, PartialEq, Eq, Hash)]
+/// struct UserId(u32);
+///
+/// impl From<u32> for UserId {
+///     fn from(id: u32) -> Self {
+///         UserId(id)
+///     }
+/// }
+///
+/// let names = map![into UserId; 1 => "ada", 2 => "grace"];
+/// let roles: HashMap<String, &str> = map![into; "ada" => "admin"];
+///
+/// assert_eq!(names[&UserId(2)], "grace");
+/// assert_eq!(roles["ada"], "admin");
+/// ```
+///
+/// ## Keeping the literal's order
+///
+/// `HashMap` iterates in a random order, which makes printed maps unstable.
+/// With `ordered;`, the macro returns an
+/// [`OrderedMap`](crate::ordered::OrderedMap) that iterates and prints in
+/// the order the entries were written:
+/// ```
+/// # use smacro::map;
+/// let steps = map![ordered;
+///     "fetch" => 3,
+///     "build" => 12,
+///     "test" => 7,
+/// ];
+///
+/// assert_eq!(format!("{:?}", steps), r#"{"fetch": 3, "build": 12, "test": 7}"#);
+/// assert_eq!(steps["build"], 12);
+/// ```
+///
+/// ## Choosing the map type
+///
+/// With `in`, the entries are collected into the named type instead of a
+/// `HashMap`, so the same syntax works for `BTreeMap`, maps with a custom
+/// hasher, or third-party maps such as `indexmap::IndexMap`. The type can
+/// be a path, and its generic arguments can be given or inferred:
+/// ```
+/// # use smacro::map;
+/// use std::collections::{BTreeMap, HashMap};
+/// use std::hash::{BuildHasherDefault, DefaultHasher};
+///
+/// type FixedStateMap<K, V> = HashMap<K, V, BuildHasherDefault<DefaultHasher>>;
+///
+/// let sorted = map![in BTreeMap; "b" => 2, "a" => 1];
+/// let fixed = map![in FixedStateMap; "a" => 1];
+/// let typed = map![in std::collections::BTreeMap<&str, f64>; "pi" => 3.14];
+///
+/// assert_eq!(sorted.keys().collect::<Vec<_>>(), [&"a", &"b"]);
+/// assert_eq!(fixed["a"], 1);
+/// assert_eq!(typed["pi"], 3.14);
+/// ```
+///
 /// # Duplicate Keys
 ///
 /// If duplicate keys are provided, the last value wins (same behavior as `HashMap::insert`):
@@ -93,6 +199,27 @@
 /// assert_eq!(overrides["key"], "second");
 /// ```
 ///
+/// # Performance Note
+///
+/// The map is always allocated once with room for every pair, which is
+/// about twice as fast as growing it with `HashMap::new` and `insert`. How
+/// the pairs get there depends on the size of the literal:
+///
+/// - Up to 16 pairs are collected with `FromIterator` from an array, which
+///   was the fastest or within noise of it at 4 and 16 pairs.
+/// - Larger literals use `HashMap::with_capacity` followed by one `insert`
+///   per pair, which was fastest at 64 and 256 pairs and avoids building a
+///   temporary array of every pair on the stack.
+///
+/// The `collections` benchmark (`cargo bench --features map,set`) measured:
+///
+/// | pairs | `new` + `insert` | `with_capacity` + `insert` | `from_iter` |
+/// |------:|-----------------:|---------------------------:|------------:|
+/// | 4     | 137 ns           | 85 ns                      | 77 ns       |
+/// | 16    | 599 ns           | 260 ns                     | 262 ns      |
+/// | 64    | 2.42 µs          | 0.98 µs                    | 1.06 µs     |
+/// | 256   | 10.2 µs          | 4.03 µs                    | 4.63 µs     |
+///
 /// # Type Inference
 ///
 /// When creating an empty map, you may need to specify the types explicitly:
@@ -106,18 +233,157 @@
 /// ```
 #[macro_export]
 macro_rules! map {
+    (@sized [$($pair:tt)+] $_0:tt $_1:tt $_2:tt $_3:tt $_4:tt $_5:tt $_6:tt $_7:tt $_8:tt $_9:tt $_10:tt $_11:tt $_12:tt $_13:tt $_14:tt $_15:tt $($_more:tt)+) => {
+        {
+            let mut map = std::collections::HashMap::with_capacity(
+                <[()]>::len(&[$($crate::map!(@unit $pair)),+]),
+            );
+            $(
+                let (key, value) = $pair;
+                map.insert(key, value);
+            )+
+            map
+        }
+    };
+    (@sized [$($pair:tt)+] $($_all:tt)+) => {
+        <std::collections::HashMap<_, _> as core::iter::FromIterator<_>>::from_iter([$($pair),+])
+    };
+    (@unit $_pair:tt) => {
+        ()
+    };
     [] => {
         std::collections::HashMap::new()
     };
-    [$($key:expr => $value:expr),+ $(,)?] => {
+    [in $($ty:ident)::+ $(<$($arg:ty),* $(,)?>)?; $($key:expr => $value:expr),* $(,)?] => {
         {
-            let mut map = std::collections::HashMap::new();
+            #[allow(unused_imports)]
+            use core::iter::FromIterator as _;
+            $($ty)::+ $(::<$($arg),*>)? ::from_iter([$(($key, $value)),*])
+        }
+    };
+    [ordered; $($key:expr => $value:expr),* $(,)?] => {
+        {
+            #[allow(unused_mut)]
+            let mut map = $crate::ordered::OrderedMap::new();
             $(
                 map.insert($key, $value);
+            )*
+            map
+        }
+    };
+    [into; $($key:expr => $value:expr),+ $(,)?] => {
+        $crate::map![into _; $($key => $value),+]
+    };
+    [into $key_ty:ty; $($key:expr => $value:expr),+ $(,)?] => {
+        <std::collections::HashMap<$key_ty, _> as core::iter::FromIterator<_>>::from_iter([
+            $((core::convert::Into::into($key), $value)),+
+        ])
+    };
+    [$($key:ident : $value:expr),+ $(,)?] => {
+        $crate::map![$(stringify!($key) => $value),+]
+    };
+    [$($outer:expr => $inner:expr => $value:expr),+ $(,)?] => {
+        {
+            let mut map = std::collections::HashMap::new();
+            $(
+                map.entry($outer)
+                    .or_insert_with(std::collections::HashMap::new)
+                    .insert($inner, $value);
             )+
             map
         }
     };
+    [$($key:expr => $value:expr),+ $(,)?] => {
+        $crate::map!(@sized [$(($key, $value))+] $(($key, $value))+)
+    };
+}
+
+/// A convenience macro for capturing local variables in a map keyed by name.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// Each identifier becomes a `&'static str` key through `stringify!`, and
+/// its value is converted with `ToString`, producing a
+/// `HashMap<&'static str, String>`. This is useful for building structured
+/// log or debug context from the variables in scope.
+///
+/// # Examples
+///
+/// ## Capturing locals
+/// ```
+/// # use smacro::fields_map;
+/// let host = "localhost";
+/// let port = 8080;
+/// let retries = 3;
+///
+/// let context = fields_map!(host, port, retries);
+///
+/// assert_eq!(context.len(), 3);
+/// assert_eq!(context["host"], "localhost");
+/// assert_eq!(context["port"], "8080");
+/// assert_eq!(context["retries"], "3");
+/// ```
+///
+/// ## Trailing commas are supported
+/// ```
+/// # use smacro::fields_map;
+/// let user = "ada";
+/// let context = fields_map!(user,);
+///
+/// assert_eq!(context["user"], "ada");
+/// ```
+#[macro_export]
+macro_rules! fields_map {
+    ($($field:ident),+ $(,)?) => {
+        $crate::map![$(stringify!($field) => $field.to_string()),+]
+    };
+}
+
+/// A convenience macro for writing an array of pairs with `map!` syntax.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// Accepts the `key => value` and identifier key forms of
+/// [`map!`](macro@crate::map), but yields a `[(K, V); N]` instead of a
+/// `HashMap`. The array can be passed to anything taking pairs, such as
+/// `HashMap::from`, `BTreeMap::from` or `Vec::from`, and keeps the order and
+/// duplicates of the literal.
+///
+/// # Examples
+///
+/// ## Building other collections
+/// ```
+/// # use smacro::tuple_map;
+/// use std::collections::BTreeMap;
+///
+/// let pairs = tuple_map!["b" => 2, "a" => 1];
+/// assert_eq!(pairs, [("b", 2), ("a", 1)]);
+///
+/// let sorted = BTreeMap::from(pairs);
+/// assert_eq!(sorted.keys().collect::<Vec<_>>(), [&"a", &"b"]);
+/// ```
+///
+/// ## Identifier keys
+/// ```
+/// # use smacro::tuple_map;
+/// let headers = tuple_map! {
+///     accept: "*/*",
+///     host: "example.com",
+/// };
+///
+/// assert_eq!(headers[1], ("host", "example.com"));
+/// ```
+#[macro_export]
+macro_rules! tuple_map {
+    [] => {
+        []
+    };
+    [$($key:ident : $value:expr),+ $(,)?] => {
+        [$((stringify!($key), $value)),+]
+    };
+    [$($key:expr => $value:expr),+ $(,)?] => {
+        [$(($key, $value)),+]
+    };
 }
 
 #[cfg(test)]
@@ -154,6 +420,25 @@ mod tests {
         assert_eq!(m[&3], "three");
     }
 
+    #[test]
+    fn map_size_classes() {
+        let small: HashMap<u8, char> = map![
+            0 => 'a', 1 => 'b', 2 => 'c', 3 => 'd', 4 => 'e', 5 => 'f', 6 => 'g', 7 => 'h',
+            8 => 'i', 9 => 'j', 10 => 'k', 11 => 'l', 12 => 'm', 13 => 'n', 14 => 'o', 15 => 'p',
+        ];
+        assert_eq!(small.len(), 16);
+
+        let large = map![
+            0 => 'a', 1 => 'b', 2 => 'c', 3 => 'd', 4 => 'e', 5 => 'f', 6 => 'g', 7 => 'h',
+            8 => 'i', 9 => 'j', 10 => 'k', 11 => 'l', 12 => 'm', 13 => 'n', 14 => 'o', 15 => 'p',
+            16 => 'q', 0 => 'z',
+        ];
+        assert_eq!(large.len(), 17);
+        assert_eq!(large[&16], 'q');
+        assert_eq!(large[&0], 'z');
+        assert!(large.capacity() >= 18);
+    }
+
     #[test]
     fn map_with_expressions() {
         let x = 10;
@@ -191,4 +476,111 @@ mod tests {
         assert_eq!(m.len(), 3);
         assert_eq!(m[&1], "one");
     }
+
+    #[test]
+    fn map_with_ident_keys() {
+        let port = 80;
+        let m = map! {
+            port: port + 8000,
+            workers: 4,
+        };
+        assert_eq!(m.len(), 2);
+        assert_eq!(m["port"], 8080);
+        assert_eq!(m["workers"], 4);
+
+        let m: HashMap<&str, bool> = map! { debug: true };
+        assert!(m["debug"]);
+    }
+
+    #[test]
+    fn map_with_into_keys() {
+        #[derive(Debug, PartialEq, Eq, Hash)]
+        struct Id(u64);
+
+        impl From<u64> for Id {
+            fn from(id: u64) -> Self {
+                Id(id)
+            }
+        }
+
+        let m = map![into Id; 1 => 'a', 2 => 'b',];
+        assert_eq!(m.len(), 2);
+        assert_eq!(m[&Id(1)], 'a');
+
+        let m: HashMap<Box<str>, i32> = map![into; "x" => 1, String::from("y") => 2];
+        assert_eq!(m["y"], 2);
+    }
+
+    #[test]
+    fn fields_map_uses_variable_names() {
+        let name = "ada";
+        let age = 36;
+        let admin = false;
+        let m = fields_map!(name, age, admin);
+        assert_eq!(m.len(), 3);
+        assert_eq!(m["name"], "ada");
+        assert_eq!(m["age"], "36");
+        assert_eq!(m["admin"], "false");
+    }
+
+    #[test]
+    fn fields_map_does_not_move_values() {
+        let tags = String::from("a,b");
+        let m: HashMap<&str, String> = fields_map!(tags,);
+        assert_eq!(m["tags"], tags);
+    }
+
+    #[test]
+    fn nested_arrows_merge_outer_keys() {
+        let m = map![
+            "en" => "hello" => "hello",
+            "it" => "hello" => "ciao",
+            "en" => "bye" => "goodbye",
+            "it" => "hello" => "salve",
+        ];
+        assert_eq!(m.len(), 2);
+        assert_eq!(m["en"], map!["hello" => "hello", "bye" => "goodbye"]);
+        assert_eq!(m["it"], map!["hello" => "salve"]);
+
+        let single: HashMap<u8, HashMap<char, bool>> = map![1 => 'a' => true];
+        assert!(single[&1][&'a']);
+    }
+
+    #[test]
+    fn map_in_other_types() {
+        use crate::ordered::OrderedMap;
+        use std::collections::BTreeMap;
+
+        let sorted = map![in BTreeMap; 3 => 'c', 1 => 'a', 2 => 'b',];
+        assert_eq!(
+            sorted.into_iter().collect::<Vec<_>>(),
+            [(1, 'a'), (2, 'b'), (3, 'c')]
+        );
+
+        let ordered = map![in OrderedMap; "z" => 1, "a" => 2];
+        assert_eq!(ordered.keys().collect::<Vec<_>>(), [&"z", &"a"]);
+
+        let typed = map![in std::collections::HashMap<String, u8>; "x".to_string() => 1];
+        assert_eq!(typed["x"], 1);
+
+        let empty = map![in BTreeMap<u8, u8>;];
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn tuple_map_keeps_order_and_duplicates() {
+        let pairs = tuple_map![3 => 'c', 1 => 'a', 3 => 'z',];
+        assert_eq!(pairs, [(3, 'c'), (1, 'a'), (3, 'z')]);
+        assert_eq!(HashMap::from(pairs)[&3], 'z');
+    }
+
+    #[test]
+    fn tuple_map_forms() {
+        let empty: [(u8, u8); 0] = tuple_map![];
+        assert!(empty.is_empty());
+
+        let idents = tuple_map! { x: 1.5, y: -2.0 };
+        assert_eq!(idents, [("x", 1.5), ("y", -2.0)]);
+        assert_eq!(Vec::from(idents).len(), 2);
+    }
 }
@@ -0,0 +1,801 @@
+//! Map transformation utilities.
+//!
+//! Requires the `map` feature to be enabled.
+//!
+//! The macros in this module build new maps out of existing ones, such as
+//! [`invert_map!`](crate::invert_map) or [`without_keys!`](crate::without_keys),
+//! or work with maps nested inside each other, like
+//! [`insert_path!`](crate::insert_path) and [`get_path!`](crate::get_path), replacing the manual loops and entry
+//! API chains usually written for these operations.
+//! Arithmetic on maps with numeric values lives in the [`math`] submodule.
+
+pub mod math;
+
+use std::borrow::Borrow;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::{BuildHasher, Hash};
+use std::iter::Sum;
+use std::ops::AddAssign;
+
+#[doc(hidden)]
+pub fn invert<I, K, V>(entries: I) -> HashMap<V, K>
+where
+    I: IntoIterator<Item = (K, V)>,
+    V: Eq + Hash,
+{
+    entries.into_iter().map(|(k, v)| (v, k)).collect()
+}
+
+#[doc(hidden)]
+pub fn invert_strict<I, K, V>(entries: I) -> Result<HashMap<V, K>, String>
+where
+    I: IntoIterator<Item = (K, V)>,
+    V: Eq + Hash + Debug,
+{
+    let mut inverted = HashMap::new();
+    for (k, v) in entries {
+        match inverted.entry(v) {
+            Entry::Occupied(entry) => return Err(format!("duplicate value {:?}", entry.key())),
+            Entry::Vacant(entry) => {
+                entry.insert(k);
+            }
+        }
+    }
+    Ok(inverted)
+}
+
+#[doc(hidden)]
+pub fn invert_multi<I, K, V>(entries: I) -> HashMap<V, Vec<K>>
+where
+    I: IntoIterator<Item = (K, V)>,
+    V: Eq + Hash,
+{
+    let mut inverted: HashMap<V, Vec<K>> = HashMap::new();
+    for (k, v) in entries {
+        inverted.entry(v).or_default().push(k);
+    }
+    inverted
+}
+
+/// A convenience macro for swapping the keys and values of a map.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// This macro provides three ways to handle values shared by several keys:
+/// - Keep one of the keys, with no guarantee about which
+/// - Return an `Err` naming the value, with `strict;`
+/// - Keep all of the keys in a `Vec`, with `multi;`
+///
+/// The input can be anything yielding `(key, value)` pairs. Passing a
+/// `HashMap` by value gives a `HashMap<V, K>`, while passing it by reference
+/// gives a `HashMap<&V, &K>`.
+///
+/// # Examples
+///
+/// ## Bidirectional lookup
+/// ```
+/// # use smacro::{invert_map, map};
+/// let codes = map!["en" => 1, "fr" => 2];
+/// let languages = invert_map!(&codes);
+///
+/// assert_eq!(languages[&2], &"fr");
+/// assert_eq!(codes["fr"], 2);
+/// ```
+///
+/// ## Collisions
+/// ```
+/// # use smacro::{invert_map, map};
+/// let roles = map!["ada" => "admin", "alan" => "user", "grace" => "admin"];
+///
+/// assert_eq!(
+///     invert_map!(strict; roles.clone()).unwrap_err(),
+///     "duplicate value \"admin\""
+/// );
+///
+/// let mut by_role = invert_map!(multi; roles);
+/// by_role.get_mut("admin").unwrap().sort();
+/// assert_eq!(by_role["admin"], ["ada", "grace"]);
+/// assert_eq!(by_role["user"], ["alan"]);
+/// ```
+#[macro_export]
+macro_rules! invert_map {
+    (strict; $map:expr $(,)?) => {
+        $crate::maps::invert_strict($map)
+    };
+    (multi; $map:expr $(,)?) => {
+        $crate::maps::invert_multi($map)
+    };
+    ($map:expr $(,)?) => {
+        $crate::maps::invert($map)
+    };
+}
+
+#[doc(hidden)]
+pub fn retain<'a, K, V, S, Q, I>(map: &HashMap<K, V, S>, keys: I) -> HashMap<K, V>
+where
+    K: Borrow<Q> + Eq + Hash + Clone,
+    V: Clone,
+    S: BuildHasher,
+    Q: Eq + Hash + ?Sized + 'a,
+    I: IntoIterator<Item = &'a Q>,
+{
+    keys.into_iter()
+        .filter_map(|key| map.get_key_value(key))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect()
+}
+
+#[doc(hidden)]
+pub fn without<'a, K, V, S, Q, I>(map: &HashMap<K, V, S>, keys: I) -> HashMap<K, V>
+where
+    K: Borrow<Q> + Eq + Hash + Clone,
+    V: Clone,
+    Q: Eq + Hash + ?Sized + 'a,
+    I: IntoIterator<Item = &'a Q>,
+{
+    let removed: HashSet<&Q> = keys.into_iter().collect();
+    map.iter()
+        .filter(|(k, _)| !removed.contains((*k).borrow()))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect()
+}
+
+/// A convenience macro for keeping only an allow-list of keys in a map.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// The allow-list can be any iterable of key references, such as a slice,
+/// a `HashSet<&str>` for `String` keys, or `&HashSet<K>`. The result is a
+/// new map containing the entries whose keys are listed, cloned from the
+/// original, which is left untouched. Listed keys missing from the map are
+/// ignored.
+///
+/// # Examples
+///
+/// ## Sanitizing a response
+/// ```
+/// # use smacro::{map, retain_keys};
+/// use std::collections::HashSet;
+///
+/// let user = map![
+///     "id".to_string() => "42",
+///     "name".to_string() => "ada",
+///     "password".to_string() => "hunter2",
+/// ];
+/// let allowed = HashSet::from(["id", "name", "avatar"]);
+/// let public = retain_keys!(user, allowed);
+///
+/// assert_eq!(public.len(), 2);
+/// assert!(!public.contains_key("password"));
+/// ```
+#[macro_export]
+macro_rules! retain_keys {
+    ($map:expr, $keys:expr $(,)?) => {
+        $crate::maps::retain(&$map, $keys)
+    };
+}
+
+/// A convenience macro for removing a list of keys from a map.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// The keys are given like the argument of `HashMap::remove`, so `&str`
+/// works for `String` keys and other keys are passed by reference. The
+/// result is a new map without those keys, cloned from the original, which
+/// is left untouched.
+///
+/// # Examples
+///
+/// ## Dropping secrets
+/// ```
+/// # use smacro::{map, without_keys};
+/// let headers = map![
+///     "accept".to_string() => "*/*",
+///     "authorization".to_string() => "Bearer abc",
+///     "cookie".to_string() => "session=1",
+/// ];
+/// let loggable = without_keys!(headers, "authorization", "cookie");
+///
+/// assert_eq!(loggable.len(), 1);
+/// assert_eq!(headers.len(), 3);
+/// ```
+///
+/// ## Non-string keys
+/// ```
+/// # use smacro::{map, without_keys};
+/// let squares = map![1 => 1, 2 => 4, 3 => 9];
+///
+/// assert_eq!(without_keys!(squares, &1, &3), map![2 => 4]);
+/// ```
+#[macro_export]
+macro_rules! without_keys {
+    ($map:expr, $($key:expr),+ $(,)?) => {
+        $crate::maps::without(&$map, [$($key),+])
+    };
+}
+
+#[doc(hidden)]
+pub fn pick_strict<'a, K, V, S, Q, I>(
+    map: &HashMap<K, V, S>,
+    keys: I,
+) -> Result<HashMap<K, V>, String>
+where
+    K: Borrow<Q> + Eq + Hash + Clone,
+    V: Clone,
+    S: BuildHasher,
+    Q: Eq + Hash + Debug + ?Sized + 'a,
+    I: IntoIterator<Item = &'a Q>,
+{
+    keys.into_iter()
+        .map(|key| match map.get_key_value(key) {
+            Some((k, v)) => Ok((k.clone(), v.clone())),
+            None => Err(format!("missing key {:?}", key)),
+        })
+        .collect()
+}
+
+/// A convenience macro for copying selected keys of a map into a new map.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// This macro provides two ways to handle listed keys missing from the map:
+/// - Skip them
+/// - Return an `Err` naming the first one, with `strict;`
+///
+/// Keys are given like the argument of `HashMap::get`, and the selected
+/// entries are cloned, leaving the original map untouched. It is the
+/// counterpart of [`without_keys!`](crate::without_keys), like `pick` and
+/// `omit` in JavaScript utility libraries.
+///
+/// # Examples
+///
+/// ## Picking keys
+/// ```
+/// # use smacro::{map, pick};
+/// let config = map![
+///     "host".to_string() => "localhost",
+///     "port".to_string() => "8080",
+///     "debug".to_string() => "true",
+/// ];
+/// let address = pick!(config, "host", "port", "scheme");
+///
+/// assert_eq!(address.len(), 2);
+/// assert_eq!(address["port"], "8080");
+/// ```
+///
+/// ## Requiring keys
+/// ```
+/// # use smacro::{map, pick};
+/// let config = map!["host" => "localhost"];
+///
+/// assert_eq!(pick!(strict; config, &"host").unwrap().len(), 1);
+/// assert_eq!(pick!(strict; config, &"host", &"port").unwrap_err(), "missing key \"port\"");
+/// ```
+#[macro_export]
+macro_rules! pick {
+    (strict; $map:expr, $($key:expr),+ $(,)?) => {
+        $crate::maps::pick_strict(&$map, [$($key),+])
+    };
+    ($map:expr, $($key:expr),+ $(,)?) => {
+        $crate::maps::retain(&$map, [$($key),+])
+    };
+}
+
+#[doc(hidden)]
+pub fn cloned<K: Clone, V: Clone, S: Clone>(map: &HashMap<K, V, S>) -> HashMap<K, V, S> {
+    map.clone()
+}
+
+/// A convenience macro for renaming keys of a map.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// Each `old => new` pair moves the value of `old`, given like the argument
+/// of `HashMap::remove`, to `new`, which is converted with `Into`. The
+/// result is a new map with the other entries copied unchanged, and the
+/// original map is left untouched.
+///
+/// Renames are applied together, so keys can be swapped. Listed keys
+/// missing from the map are ignored, and a new name that already exists
+/// overwrites its value.
+///
+/// # Examples
+///
+/// ## Adapting a payload
+/// ```
+/// # use smacro::{map, rename_keys};
+/// let payload = map![
+///     "usr".to_string() => "ada",
+///     "e_mail".to_string() => "ada@example.com",
+///     "role".to_string() => "admin",
+/// ];
+/// let user = rename_keys!(payload, "usr" => "user", "e_mail" => "email");
+///
+/// assert_eq!(user["user"], "ada");
+/// assert_eq!(user["email"], "ada@example.com");
+/// assert_eq!(user["role"], "admin");
+/// assert!(!user.contains_key("usr"));
+/// ```
+///
+/// ## Swapping keys
+/// ```
+/// # use smacro::{map, rename_keys};
+/// let point = map!['x' => 1, 'y' => 2];
+///
+/// assert_eq!(rename_keys!(point, &'x' => 'y', &'y' => 'x'), map!['x' => 2, 'y' => 1]);
+/// ```
+#[macro_export]
+macro_rules! rename_keys {
+    ($map:expr, $($old:expr => $new:expr),+ $(,)?) => {
+        {
+            let mut renamed = $crate::maps::cloned(&$map);
+            let moved = [$(renamed.remove($old).map(|value| (core::convert::Into::into($new), value))),+];
+            renamed.extend(moved.into_iter().flatten());
+            renamed
+        }
+    };
+}
+
+#[doc(hidden)]
+pub fn map_values<K, V, S, U, F>(map: &HashMap<K, V, S>, mut f: F) -> HashMap<K, U>
+where
+    K: Eq + Hash + Clone,
+    F: FnMut(&V) -> U,
+{
+    map.iter().map(|(k, v)| (k.clone(), f(v))).collect()
+}
+
+#[doc(hidden)]
+pub fn map_values_mut<K, V, S, F>(map: &mut HashMap<K, V, S>, f: F)
+where
+    F: FnMut(&mut V),
+{
+    map.values_mut().for_each(f);
+}
+
+#[doc(hidden)]
+pub fn map_keys<K, V, S, J, F>(map: &HashMap<K, V, S>, mut f: F) -> HashMap<J, V>
+where
+    V: Clone,
+    J: Eq + Hash,
+    F: FnMut(&K) -> J,
+{
+    map.iter().map(|(k, v)| (f(k), v.clone())).collect()
+}
+
+#[doc(hidden)]
+pub fn map_keys_mut<K, V, S, F>(map: &mut HashMap<K, V, S>, mut f: F)
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+    F: FnMut(K) -> K,
+{
+    let entries = std::mem::take(map);
+    map.extend(entries.into_iter().map(|(k, v)| (f(k), v)));
+}
+
+/// A convenience macro for transforming the values of a map.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// This macro provides two ways to transform values:
+/// - Into a new map, with a closure taking `&V` and returning any type
+/// - In place (`mut` prefix), with a closure taking `&mut V`
+///
+/// The new map has the keys of the original cloned, and the original is
+/// left untouched.
+///
+/// # Examples
+///
+/// ## New map
+/// ```
+/// # use smacro::{map, map_values};
+/// let prices = map!["tea" => 250, "cake" => 400];
+/// let labels = map_values!(prices, |cents| format!("${:.2}", *cents as f64 / 100.0));
+///
+/// assert_eq!(labels["tea"], "$2.50");
+/// assert_eq!(prices["tea"], 250);
+/// ```
+///
+/// ## In place
+/// ```
+/// # use smacro::{map, map_values};
+/// let mut stock = map!["apples" => 3, "pears" => 5];
+/// map_values!(mut stock, |n| *n *= 2);
+///
+/// assert_eq!(stock, map!["apples" => 6, "pears" => 10]);
+/// ```
+#[macro_export]
+macro_rules! map_values {
+    (mut $map:expr, $f:expr $(,)?) => {
+        $crate::maps::map_values_mut(&mut $map, $f)
+    };
+    ($map:expr, $f:expr $(,)?) => {
+        $crate::maps::map_values(&$map, $f)
+    };
+}
+
+/// A convenience macro for transforming the keys of a map.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// This macro provides two ways to transform keys:
+/// - Into a new map, with a closure taking `&K` and returning any key type
+/// - In place (`mut` prefix), with a closure taking and returning `K`
+///
+/// The new map has the values of the original cloned, and the original is
+/// left untouched. When several keys map to the same new key, only one of
+/// their values is kept, with no guarantee about which.
+///
+/// # Examples
+///
+/// ## New map
+/// ```
+/// # use smacro::{map, map_keys};
+/// let headers = map!["Content-Type" => "text/html", "X-Request-Id" => "42"];
+/// let normalized = map_keys!(headers, |k| k.to_lowercase());
+///
+/// assert_eq!(normalized["content-type"], "text/html");
+/// ```
+///
+/// ## In place
+/// ```
+/// # use smacro::{map, map_keys};
+/// let mut offsets = map![1 => 'a', 2 => 'b'];
+/// map_keys!(mut offsets, |k| k * 10);
+///
+/// assert_eq!(offsets, map![10 => 'a', 20 => 'b']);
+/// ```
+#[macro_export]
+macro_rules! map_keys {
+    (mut $map:expr, $f:expr $(,)?) => {
+        $crate::maps::map_keys_mut(&mut $map, $f)
+    };
+    ($map:expr, $f:expr $(,)?) => {
+        $crate::maps::map_keys(&$map, $f)
+    };
+}
+
+#[doc(hidden)]
+pub fn sum_values<K, V, S>(map: &HashMap<K, V, S>) -> V
+where
+    V: for<'a> Sum<&'a V>,
+{
+    map.values().sum()
+}
+
+#[doc(hidden)]
+pub fn merge_counts<K, V, S>(maps: &[&HashMap<K, V, S>]) -> HashMap<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone + for<'a> AddAssign<&'a V>,
+{
+    let mut merged: HashMap<K, V> = HashMap::new();
+    for (k, v) in maps.iter().flat_map(|map| map.iter()) {
+        match merged.entry(k.clone()) {
+            Entry::Occupied(mut entry) => *entry.get_mut() += v,
+            Entry::Vacant(entry) => {
+                entry.insert(v.clone());
+            }
+        }
+    }
+    merged
+}
+
+/// A convenience macro for adding up the values of a map.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// Works with any value type that can be summed from references, such as
+/// the integer and float types. An empty map sums to zero.
+///
+/// # Examples
+///
+/// ## Totalling counts
+/// ```
+/// # use smacro::{map, sum_values};
+/// let visits = map!["/" => 120, "/about" => 30, "/blog" => 50];
+///
+/// assert_eq!(sum_values!(visits), 200);
+/// ```
+#[macro_export]
+macro_rules! sum_values {
+    ($map:expr $(,)?) => {
+        $crate::maps::sum_values(&$map)
+    };
+}
+
+/// A convenience macro for adding up several maps key by key.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// The result is a new map with every key found in any of the maps, whose
+/// value is the sum of its values across them. The maps must have the same
+/// type and are left untouched.
+///
+/// # Examples
+///
+/// ## Combining word counts
+/// ```
+/// # use smacro::{map, merge_counts};
+/// let monday = map!["error" => 3, "warn" => 10];
+/// let tuesday = map!["error" => 1, "info" => 7];
+///
+/// assert_eq!(
+///     merge_counts!(monday, tuesday),
+///     map!["error" => 4, "warn" => 10, "info" => 7]
+/// );
+/// ```
+#[macro_export]
+macro_rules! merge_counts {
+    ($($map:expr),+ $(,)?) => {
+        $crate::maps::merge_counts(&[$(&$map),+])
+    };
+}
+
+/// A convenience macro for inserting a value at a path of nested maps.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// The map is followed by the path of keys in brackets and the value. Every
+/// key but the last walks into the map under that key, creating an empty
+/// one with `Default` if it is missing, and the last key is inserted into
+/// the innermost map. Keys are converted with `Into`, so `&str` works for
+/// `String` keys. Like `HashMap::insert`, it returns the previous value at
+/// the path, if any.
+///
+/// Works with any map type having an `entry` API, such as `HashMap` and
+/// `BTreeMap`, at every level.
+///
+/// # Examples
+///
+/// ## Building nested configuration
+/// ```
+/// # use smacro::insert_path;
+/// use std::collections::HashMap;
+///
+/// let mut config: HashMap<String, HashMap<String, HashMap<String, String>>> = HashMap::new();
+///
+/// insert_path!(config, ["server", "tls", "cert"], "cert.pem".to_string());
+/// insert_path!(config, ["server", "tls", "key"], "key.pem".to_string());
+///
+/// assert_eq!(config["server"]["tls"].len(), 2);
+/// assert_eq!(config["server"]["tls"]["cert"], "cert.pem");
+/// ```
+///
+/// ## Replacing a value
+/// ```
+/// # use smacro::insert_path;
+/// use std::collections::BTreeMap;
+///
+/// let mut limits: BTreeMap<&str, BTreeMap<&str, u32>> = BTreeMap::new();
+///
+/// assert_eq!(insert_path!(limits, ["api", "rps"], 10), None);
+/// assert_eq!(insert_path!(limits, ["api", "rps"], 20), Some(10));
+/// ```
+#[macro_export]
+macro_rules! insert_path {
+    (@walk $node:expr; $last:expr; $value:expr) => {
+        $node.insert(core::convert::Into::into($last), $value)
+    };
+    (@walk $node:expr; $head:expr, $($rest:expr),+; $value:expr) => {
+        $crate::insert_path!(
+            @walk $node.entry(core::convert::Into::into($head)).or_default();
+            $($rest),+;
+            $value
+        )
+    };
+    ($map:expr, [$($key:expr),+ $(,)?], $value:expr $(,)?) => {
+        $crate::insert_path!(@walk $map; $($key),+; $value)
+    };
+}
+
+/// A convenience macro for looking up a value at a path of nested maps.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// The map is followed by the keys to walk, which are passed like the
+/// argument of `HashMap::get`. The lookups are chained with
+/// `Option::and_then`, so the result is `None` as soon as a key is missing,
+/// or `Some(&value)` for the innermost value.
+///
+/// Only a `get` method returning an `Option` of a reference is needed at
+/// each level, so this also walks `BTreeMap`s and document values such as
+/// `toml::Value` or `serde_json::Value`.
+///
+/// # Examples
+///
+/// ## Reading nested configuration
+/// ```
+/// # use smacro::{get_path, insert_path};
+/// use std::collections::HashMap;
+///
+/// let mut config: HashMap<String, HashMap<String, HashMap<String, String>>> = HashMap::new();
+/// insert_path!(config, ["server", "tls", "cert"], "cert.pem".to_string());
+///
+/// assert_eq!(get_path!(config, "server", "tls", "cert").unwrap(), "cert.pem");
+/// assert_eq!(get_path!(config, "server", "http", "port"), None);
+/// ```
+///
+/// ## Non-string keys
+/// ```
+/// # use smacro::get_path;
+/// use std::collections::BTreeMap;
+///
+/// let grid = BTreeMap::from([(0, BTreeMap::from([(1, 'x')]))]);
+///
+/// assert_eq!(get_path!(grid, &0, &1), Some(&'x'));
+/// ```
+#[macro_export]
+macro_rules! get_path {
+    ($map:expr, $first:expr $(, $key:expr)* $(,)?) => {
+        $map.get($first)$(.and_then(|node| node.get($key)))*
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::map;
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn invert_unique_values() {
+        let m = map![1 => 'a', 2 => 'b'];
+        let inverted: HashMap<char, i32> = invert_map!(m);
+        assert_eq!(inverted, map!['a' => 1, 'b' => 2]);
+    }
+
+    #[test]
+    fn invert_borrowed_and_pairs() {
+        let m = map!["x".to_string() => 10];
+        assert_eq!(invert_map!(&m)[&10], "x");
+        assert_eq!(invert_map!(vec![(1, "one"), (2, "two")])["two"], 2);
+    }
+
+    #[test]
+    fn invert_strict_reports_collisions() {
+        assert_eq!(invert_map!(strict; [(1, 'a'), (2, 'b')]).unwrap().len(), 2);
+        assert_eq!(
+            invert_map!(strict; [(1, 'a'), (2, 'a')]).unwrap_err(),
+            "duplicate value 'a'"
+        );
+    }
+
+    #[test]
+    fn invert_multi_keeps_all_keys() {
+        let inverted = invert_map!(multi; [(1, "odd"), (2, "even"), (3, "odd")]);
+        assert_eq!(inverted["odd"], [1, 3]);
+        assert_eq!(inverted["even"], [2]);
+    }
+
+    #[test]
+    fn retain_allow_list() {
+        let m = map!["a".to_string() => 1, "b".to_string() => 2, "c".to_string() => 3];
+        assert_eq!(
+            retain_keys!(m, ["a", "c", "z"]),
+            map!["a".to_string() => 1, "c".to_string() => 3]
+        );
+
+        let allowed: HashSet<String> = HashSet::from(["b".to_string()]);
+        assert_eq!(retain_keys!(&m, &allowed), map!["b".to_string() => 2]);
+        assert!(retain_keys!(m, Vec::<&str>::new()).is_empty());
+        assert_eq!(m.len(), 3);
+    }
+
+    #[test]
+    fn without_listed_keys() {
+        let m = map!["a" => 1, "b" => 2, "c" => 3];
+        assert_eq!(
+            without_keys!(m, &"a", &"missing",),
+            map!["b" => 2, "c" => 3]
+        );
+        assert_eq!(without_keys!(m, &"a", &"b", &"c"), HashMap::new());
+        assert_eq!(m.len(), 3);
+    }
+
+    #[test]
+    fn pick_skips_missing() {
+        let m = map![1 => "one", 2 => "two", 3 => "three"];
+        assert_eq!(pick!(m, &1, &3, &5,), map![1 => "one", 3 => "three"]);
+        assert_eq!(pick!(m, &7), HashMap::new());
+    }
+
+    #[test]
+    fn pick_strict_requires_keys() {
+        let m = map!["a".to_string() => 1, "b".to_string() => 2];
+        assert_eq!(pick!(strict; m, "b"), Ok(map!["b".to_string() => 2]));
+        assert_eq!(
+            pick!(strict; m, "a", "x", "y"),
+            Err("missing key \"x\"".to_string())
+        );
+    }
+
+    #[test]
+    fn rename_listed_keys() {
+        let m = map!["a".to_string() => 1, "b".to_string() => 2];
+        let renamed = rename_keys!(&m, "a" => "alpha", "missing" => "ignored",);
+        assert_eq!(
+            renamed,
+            map!["alpha".to_string() => 1, "b".to_string() => 2]
+        );
+        assert_eq!(m.len(), 2);
+    }
+
+    #[test]
+    fn rename_overwrites_and_swaps() {
+        let m = map![1 => "one", 2 => "two", 3 => "three"];
+        assert_eq!(rename_keys!(m, &1 => 2), map![2 => "one", 3 => "three"]);
+        assert_eq!(
+            rename_keys!(m, &1 => 2, &2 => 3, &3 => 1),
+            map![1 => "three", 2 => "one", 3 => "two"]
+        );
+    }
+
+    #[test]
+    fn map_values_new_and_in_place() {
+        let mut m = map!["a" => 1, "b" => 2];
+        let doubled: HashMap<&str, i64> = map_values!(m, |v| i64::from(*v) * 2);
+        assert_eq!(doubled, map!["a" => 2, "b" => 4]);
+
+        map_values!(mut m, |v| *v += 10,);
+        assert_eq!(m, map!["a" => 11, "b" => 12]);
+    }
+
+    #[test]
+    fn map_keys_new_and_in_place() {
+        let mut m = map!["A".to_string() => 1, "b".to_string() => 2];
+        assert_eq!(map_keys!(&m, |k| k.len()).len(), 1);
+        assert_eq!(map_keys!(m, |k| k.to_lowercase())["a"], 1);
+
+        map_keys!(mut m, |k| k + "!");
+        assert_eq!(m, map!["A!".to_string() => 1, "b!".to_string() => 2]);
+    }
+
+    #[test]
+    fn sum_values_of_maps() {
+        assert_eq!(sum_values!(map!['a' => 1.5, 'b' => 2.25]), 3.75);
+        assert_eq!(sum_values!(HashMap::<&str, u64>::new()), 0);
+    }
+
+    #[test]
+    fn merge_counts_adds_shared_keys() {
+        let a = map!["x" => 1, "y" => 2];
+        let b = map!["y" => 3, "z" => 4];
+        let c = map!["x" => 10];
+        assert_eq!(merge_counts!(a, b, c,), map!["x" => 11, "y" => 5, "z" => 4]);
+        assert_eq!(merge_counts!(a), a);
+        assert_eq!(
+            sum_values!(merge_counts!(&a, &b)),
+            sum_values!(a) + sum_values!(b)
+        );
+    }
+
+    #[test]
+    fn insert_path_creates_levels() {
+        let mut m: HashMap<String, HashMap<String, HashMap<u8, bool>>> = HashMap::new();
+        assert_eq!(insert_path!(m, ["a", "b", 1], true), None);
+        assert_eq!(insert_path!(m, ["a", "c", 2,], false,), None);
+        assert_eq!(insert_path!(&mut m, ["a", "b", 1], false), Some(true));
+        assert_eq!(m["a"].len(), 2);
+        assert!(!m["a"]["b"][&1]);
+    }
+
+    #[test]
+    fn insert_path_single_key() {
+        let mut m: HashMap<&str, i32> = HashMap::new();
+        insert_path!(m, ["x"], 1);
+        assert_eq!(m, map!["x" => 1]);
+    }
+
+    #[test]
+    fn get_path_walks_levels() {
+        let mut m: HashMap<String, HashMap<u8, i32>> = HashMap::new();
+        insert_path!(m, ["a", 1], 10);
+        assert_eq!(get_path!(m, "a", &1), Some(&10));
+        assert_eq!(get_path!(&m, "a", &2,), None);
+        assert_eq!(get_path!(m, "b", &1), None);
+        assert_eq!(get_path!(m, "a").map(HashMap::len), Some(1));
+    }
+}
@@ -0,0 +1,283 @@
+//! Arithmetic on numeric maps.
+//!
+//! Requires the `map` feature to be enabled.
+//!
+//! These functions treat a `HashMap` with numeric values as a sparse
+//! vector, which covers common metrics post-processing such as normalizing,
+//! diffing two snapshots or keeping the peak of each key. Scaling is also
+//! available as the [`scale_map!`](crate::scale_map) macro.
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+use std::ops::Mul;
+
+/// Returns a new map with every value multiplied by `factor`.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::map;
+/// use smacro::maps::math::scale;
+///
+/// let seconds = map!["build" => 2.5, "test" => 4.0];
+///
+/// assert_eq!(scale(&seconds, 1000.0), map!["build" => 2500.0, "test" => 4000.0]);
+/// ```
+pub fn scale<K, V, S>(map: &HashMap<K, V, S>, factor: V) -> HashMap<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Mul<Output = V> + Copy,
+{
+    map.iter().map(|(k, &v)| (k.clone(), v * factor)).collect()
+}
+
+/// Multiplies every value of `map` by `factor` in place.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::map;
+/// use smacro::maps::math::scale_mut;
+///
+/// let mut weights = map!['a' => 1, 'b' => 3];
+/// scale_mut(&mut weights, 2);
+///
+/// assert_eq!(weights, map!['a' => 2, 'b' => 6]);
+/// ```
+pub fn scale_mut<K, V, S>(map: &mut HashMap<K, V, S>, factor: V)
+where
+    V: Mul<Output = V> + Copy,
+{
+    for v in map.values_mut() {
+        *v = *v * factor;
+    }
+}
+
+/// Subtraction that stops at the numeric bounds instead of overflowing.
+///
+/// Integers use their `saturating_sub`, so an unsigned difference that would
+/// go below zero is zero. Floats subtract normally, since they already
+/// saturate to infinity.
+pub trait SaturatingSub: Copy {
+    /// Returns `self - rhs`, clamped to the bounds of the type.
+    fn saturating_sub(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_saturating_sub {
+    (int $($t:ty),*) => {
+        $(
+            impl SaturatingSub for $t {
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    <$t>::saturating_sub(self, rhs)
+                }
+            }
+        )*
+    };
+    (float $($t:ty),*) => {
+        $(
+            impl SaturatingSub for $t {
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    self - rhs
+                }
+            }
+        )*
+    };
+}
+
+impl_saturating_sub!(int i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_saturating_sub!(float f32, f64);
+
+/// Returns `a - b` key by key.
+///
+/// Keys missing from one of the maps count as zero there, so the result has
+/// every key of both maps.
+///
+/// Values are subtracted with [`SaturatingSub`], so the result is the same
+/// in debug and release builds: with unsigned values such as `u64`
+/// counters, a key whose value in `b` is larger than in `a`, including a
+/// key only present in `b`, becomes zero instead of overflowing.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::map;
+/// use smacro::maps::math::subtract;
+///
+/// let before = map!["requests" => 100, "errors" => 4];
+/// let after = map!["requests" => 180, "errors" => 4, "timeouts" => 2];
+///
+/// assert_eq!(
+///     subtract(&after, &before),
+///     map!["requests" => 80, "errors" => 0, "timeouts" => 2]
+/// );
+/// ```
+pub fn subtract<K, V, S>(a: &HashMap<K, V, S>, b: &HashMap<K, V, S>) -> HashMap<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: SaturatingSub + Default,
+    S: BuildHasher,
+{
+    let mut result: HashMap<K, V> = a.iter().map(|(k, &v)| (k.clone(), v)).collect();
+    for (k, &v) in b {
+        let entry = result.entry(k.clone()).or_default();
+        *entry = entry.saturating_sub(v);
+    }
+    result
+}
+
+/// Returns the largest value of each key across several maps.
+///
+/// The result has every key found in any of the maps.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::map;
+/// use smacro::maps::math::max_values;
+///
+/// let eu = map!["cpu" => 0.7, "mem" => 0.4];
+/// let us = map!["cpu" => 0.5, "mem" => 0.9, "disk" => 0.2];
+///
+/// assert_eq!(max_values(&[&eu, &us]), map!["cpu" => 0.7, "mem" => 0.9, "disk" => 0.2]);
+/// ```
+pub fn max_values<K, V, S>(maps: &[&HashMap<K, V, S>]) -> HashMap<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: PartialOrd + Copy,
+{
+    combine(maps, |current, v| v > current)
+}
+
+/// Returns the smallest value of each key across several maps.
+///
+/// The result has every key found in any of the maps.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::map;
+/// use smacro::maps::math::min_values;
+///
+/// let first = map!["p50" => 12, "p99" => 80];
+/// let second = map!["p50" => 9, "p99" => 95];
+///
+/// assert_eq!(min_values(&[&first, &second]), map!["p50" => 9, "p99" => 80]);
+/// ```
+pub fn min_values<K, V, S>(maps: &[&HashMap<K, V, S>]) -> HashMap<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: PartialOrd + Copy,
+{
+    combine(maps, |current, v| v < current)
+}
+
+/// Merges `maps`, replacing the value of a key when `replace` returns true.
+fn combine<K, V, S>(maps: &[&HashMap<K, V, S>], replace: impl Fn(V, V) -> bool) -> HashMap<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Copy,
+{
+    let mut result: HashMap<K, V> = HashMap::new();
+    for (k, &v) in maps.iter().flat_map(|map| map.iter()) {
+        result
+            .entry(k.clone())
+            .and_modify(|current| {
+                if replace(*current, v) {
+                    *current = v;
+                }
+            })
+            .or_insert(v);
+    }
+    result
+}
+
+/// A convenience macro for multiplying every value of a map by a factor.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// This macro provides two ways to scale a map:
+/// - Into a new map, leaving the original untouched
+/// - In place (`mut` prefix)
+///
+/// # Examples
+///
+/// ## New map
+/// ```
+/// # use smacro::{map, scale_map};
+/// let shares = map!["a" => 0.25, "b" => 0.75];
+///
+/// assert_eq!(scale_map!(shares, 100.0), map!["a" => 25.0, "b" => 75.0]);
+/// ```
+///
+/// ## In place
+/// ```
+/// # use smacro::{map, scale_map};
+/// let mut limits = map!["reads" => 100, "writes" => 10];
+/// scale_map!(mut limits, 2);
+///
+/// assert_eq!(limits["writes"], 20);
+/// ```
+#[macro_export]
+macro_rules! scale_map {
+    (mut $map:expr, $factor:expr $(,)?) => {
+        $crate::maps::math::scale_mut(&mut $map, $factor)
+    };
+    ($map:expr, $factor:expr $(,)?) => {
+        $crate::maps::math::scale(&$map, $factor)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{max_values, min_values, subtract};
+    use crate::map;
+    use std::collections::HashMap;
+
+    #[test]
+    fn scale_new_and_in_place() {
+        let mut m = map!['a' => 1, 'b' => -2];
+        assert_eq!(scale_map!(m, 3), map!['a' => 3, 'b' => -6]);
+        scale_map!(mut m, -1,);
+        assert_eq!(m, map!['a' => -1, 'b' => 2]);
+        assert!(scale_map!(HashMap::<u8, u8>::new(), 2).is_empty());
+    }
+
+    #[test]
+    fn subtract_treats_missing_as_zero() {
+        let a = map!["x" => 5, "y" => 1];
+        let b = map!["y" => 3, "z" => 2];
+        assert_eq!(subtract(&a, &b), map!["x" => 5, "y" => -2, "z" => -2]);
+        assert_eq!(subtract(&a, &a), map!["x" => 0, "y" => 0]);
+    }
+
+    #[test]
+    fn subtract_unsigned_snapshots() {
+        let before: HashMap<&str, u64> = map!["requests" => 100, "errors" => 4];
+        let after: HashMap<&str, u64> = map!["requests" => 180, "errors" => 4, "timeouts" => 2];
+        assert_eq!(
+            subtract(&after, &before),
+            map!["requests" => 80, "errors" => 0, "timeouts" => 2]
+        );
+    }
+
+    #[test]
+    fn subtract_saturates() {
+        let a: HashMap<&str, u64> = map!["requests" => 3];
+        let b: HashMap<&str, u64> = map!["requests" => 5, "errors" => 1];
+        assert_eq!(subtract(&a, &b), map!["requests" => 0, "errors" => 0]);
+
+        let low = map!["x" => i8::MIN];
+        assert_eq!(subtract(&low, &map!["x" => 1]), map!["x" => i8::MIN]);
+        assert_eq!(subtract(&map![1 => 0.5], &map![1 => 2.0]), map![1 => -1.5]);
+    }
+
+    #[test]
+    fn max_and_min_across_maps() {
+        let a = map![1 => 10, 2 => 20];
+        let b = map![2 => 5, 3 => 30];
+        let c = map![1 => 15];
+        assert_eq!(max_values(&[&a, &b, &c]), map![1 => 15, 2 => 20, 3 => 30]);
+        assert_eq!(min_values(&[&a, &b, &c]), map![1 => 10, 2 => 5, 3 => 30]);
+        assert!(max_values::<u8, u8, std::hash::RandomState>(&[]).is_empty());
+    }
+}
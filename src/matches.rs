@@ -0,0 +1,143 @@
+//! Pattern testing utilities.
+//!
+//! The `matches_any!` and `matches_all!` macros test values against several
+//! patterns at once, returning a `bool` without writing out a full `match`.
+
+/// A convenience macro for testing a value against several patterns.
+///
+/// `matches_any!(value, A, B | C)` is equivalent to
+/// `matches!(value, A | B | C)`: it returns `true` if the value matches any
+/// of the comma-separated patterns. Each pattern can itself be an
+/// `|`-alternative, and bindings are allowed but unused.
+///
+/// # Examples
+///
+/// ## Multiple patterns
+/// ```
+/// # use smacro::matches_any;
+/// enum Token {
+///     Plus,
+///     Minus,
+///     Star,
+///     Number(i64),
+/// }
+///
+/// let token = Token::Star;
+///
+/// assert!(matches_any!(token, Token::Plus | Token::Minus, Token::Star));
+/// assert!(!matches_any!(Token::Number(1), Token::Plus, Token::Number(2..)));
+/// ```
+///
+/// ## Ranges and literals
+/// ```
+/// # use smacro::matches_any;
+/// let c = 'x';
+///
+/// assert!(matches_any!(c, 'a'..='z', 'A'..='Z', '_'));
+/// assert!(!matches_any!(c, '0'..='9'));
+/// ```
+#[macro_export]
+macro_rules! matches_any {
+    ($value:expr, $($pat:pat),+ $(,)?) => {
+        match $value {
+            $($pat)|+ => true,
+            _ => false,
+        }
+    };
+}
+
+/// A convenience macro for testing that every item of an iterable matches.
+///
+/// `matches_all!(items, A, B)` returns `true` if every item yielded by
+/// `items` matches at least one of the patterns, stopping at the first item
+/// that doesn't. An empty iterable returns `true`, like [`Iterator::all`].
+///
+/// Iterating over a borrowed collection yields references, which patterns
+/// match through as usual.
+///
+/// # Examples
+///
+/// ## Checking every item
+/// ```
+/// # use smacro::matches_all;
+/// let results = vec![Ok(1), Ok(2), Err("bad")];
+///
+/// assert!(!matches_all!(&results, Ok(_)));
+/// assert!(matches_all!(&results[..2], Ok(1..=2)));
+/// ```
+///
+/// ## Multiple patterns
+/// ```
+/// # use smacro::matches_all;
+/// let input = "user_42";
+///
+/// assert!(matches_all!(input.chars(), 'a'..='z', '0'..='9', '_'));
+/// assert!(!matches_all!("Hello".chars(), 'a'..='z'));
+/// ```
+#[macro_export]
+macro_rules! matches_all {
+    ($items:expr, $($pat:pat),+ $(,)?) => {
+        core::iter::IntoIterator::into_iter($items).all(|item| match item {
+            $($pat)|+ => true,
+            _ => false,
+        })
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[derive(Debug)]
+    enum Token {
+        Plus,
+        Minus,
+        Star,
+        Number(i64),
+        Ident(&'static str),
+    }
+
+    #[test]
+    fn any_of_several_patterns() {
+        assert!(matches_any!(
+            Token::Minus,
+            Token::Plus | Token::Minus,
+            Token::Star
+        ));
+        assert!(matches_any!(Token::Star, Token::Plus, Token::Star,));
+        assert!(!matches_any!(Token::Number(3), Token::Plus, Token::Star));
+    }
+
+    #[test]
+    fn patterns_with_data() {
+        let token = Token::Ident("let");
+        assert!(matches_any!(
+            &token,
+            Token::Ident("let" | "fn"),
+            Token::Number(_)
+        ));
+        assert!(matches_any!(
+            Token::Number(-4),
+            Token::Number(i64::MIN..=-1)
+        ));
+        assert!(!matches_any!(token, Token::Ident("const")));
+    }
+
+    #[test]
+    fn all_items_match() {
+        let tokens = [Token::Plus, Token::Minus, Token::Star];
+        assert!(matches_all!(
+            &tokens,
+            Token::Plus | Token::Minus,
+            Token::Star
+        ));
+        assert!(!matches_all!(&tokens, Token::Plus, Token::Minus));
+        assert!(matches_all!(Vec::<Token>::new(), Token::Plus));
+    }
+
+    #[test]
+    fn all_stops_at_first_mismatch() {
+        let mut seen = 0;
+        let all = matches_all!([1, 2, -3, 4].into_iter().inspect(|_| seen += 1), 1..);
+        assert!(!all);
+        assert_eq!(seen, 3);
+    }
+}
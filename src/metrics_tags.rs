@@ -0,0 +1,118 @@
+//! Metric label utilities.
+//!
+//! Requires the `metrics` feature to be enabled.
+//!
+//! The `metrics_tags!` macro turns `map!` style pairs into the labels taken
+//! by the `metrics` crate's `counter!`, `gauge!` and `histogram!` macros, or
+//! into plain string pairs for other metrics backends.
+
+use std::fmt::Display;
+
+use metrics::{Label, SharedString};
+
+#[doc(hidden)]
+pub fn label(key: impl Into<SharedString>, value: impl Display) -> Label {
+    Label::new(key, value.to_string())
+}
+
+#[doc(hidden)]
+pub fn labels<const N: usize>(labels: [Label; N]) -> Vec<Label> {
+    labels.into()
+}
+
+#[doc(hidden)]
+pub fn pairs<const N: usize>(pairs: [(String, String); N]) -> Vec<(String, String)> {
+    pairs.into()
+}
+
+#[doc(hidden)]
+pub fn pair(key: impl Into<String>, value: impl Display) -> (String, String) {
+    (key.into(), value.to_string())
+}
+
+/// A convenience macro for building metric labels from key-value pairs.
+///
+/// Requires the `metrics` feature to be enabled.
+///
+/// This macro provides two ways to build labels:
+/// - Build a `Vec<metrics::Label>`, ready to pass to the `metrics` macros
+/// - Build a plain `Vec<(String, String)>` with `plain;`
+///
+/// Pairs are written as in `map!`, either `key => value` or with
+/// identifier keys as `key: value`. Values are formatted with `Display`,
+/// so numbers and other types need no manual `to_string`. Keys of
+/// `metrics::Label`s must be `&'static str` or owned strings.
+///
+/// # Examples
+///
+/// ## Tagging a counter
+/// ```
+/// # use smacro::metrics_tags;
+/// let (method, status) = ("GET", 200);
+/// let labels = metrics_tags!["method" => method, "status" => status];
+///
+/// assert_eq!(labels[1].value(), "200");
+/// metrics::counter!("http_requests_total", labels.iter()).increment(1);
+/// ```
+///
+/// ## Plain string pairs
+/// ```
+/// # use smacro::metrics_tags;
+/// let tags = metrics_tags![plain; region: "eu-west-1", shard: 3];
+///
+/// assert_eq!(
+///     tags,
+///     [("region".to_string(), "eu-west-1".to_string()), ("shard".to_string(), "3".to_string())]
+/// );
+/// ```
+#[macro_export]
+macro_rules! metrics_tags {
+    (plain; $($key:ident : $value:expr),+ $(,)?) => {
+        $crate::metrics_tags![plain; $(stringify!($key) => $value),+]
+    };
+    (plain; $($key:expr => $value:expr),* $(,)?) => {
+        $crate::metrics_tags::pairs([$($crate::metrics_tags::pair($key, $value)),*])
+    };
+    ($($key:ident : $value:expr),+ $(,)?) => {
+        $crate::metrics_tags![$(stringify!($key) => $value),+]
+    };
+    ($($key:expr => $value:expr),* $(,)?) => {
+        $crate::metrics_tags::labels([$($crate::metrics_tags::label($key, $value)),*])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn labels() {
+        let id = String::from("worker-1");
+        let labels = metrics_tags!["worker" => id, "queue" => "jobs", "retries" => 2,];
+        let parts: Vec<_> = labels.iter().map(|l| (l.key(), l.value())).collect();
+        assert_eq!(
+            parts,
+            [("worker", "worker-1"), ("queue", "jobs"), ("retries", "2")]
+        );
+        assert!(metrics_tags![].is_empty());
+    }
+
+    #[test]
+    fn ident_keys() {
+        let labels = metrics_tags! { route: "/users", cached: true };
+        assert_eq!(labels[0].key(), "route");
+        assert_eq!(labels[1].value(), "true");
+    }
+
+    #[test]
+    fn plain_pairs() {
+        let key = String::from("owned");
+        let tags = metrics_tags![plain; key => 1.5, "b" => 'x'];
+        assert_eq!(
+            tags,
+            [
+                ("owned".to_string(), "1.5".to_string()),
+                ("b".to_string(), "x".to_string())
+            ]
+        );
+        assert!(metrics_tags![plain;].is_empty());
+    }
+}
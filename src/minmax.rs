@@ -0,0 +1,290 @@
+//! Variadic comparison utilities.
+//!
+//! The `min_of!`, `max_of!` and `minmax!` macros compute the smallest and/or
+//! largest value of a list of expressions or of an iterator, replacing nested
+//! `std::cmp::max(a, std::cmp::max(b, c))` chains. A `total;` prefix switches
+//! the comparison to `total_cmp`, so floats work where `Ord` isn't available.
+
+use core::cmp::Ordering;
+
+/// Types that can be compared with a total order through `total_cmp`.
+///
+/// This is what the `total;` forms of [`min_of!`](crate::min_of),
+/// [`max_of!`](crate::max_of) and [`minmax!`](macro@crate::minmax) use, and it is
+/// implemented for `f32` and `f64`.
+pub trait TotalCmp {
+    /// Compares `self` and `other` following the IEEE 754 `totalOrder` predicate.
+    fn total_cmp(&self, other: &Self) -> Ordering;
+}
+
+impl TotalCmp for f32 {
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        f32::total_cmp(self, other)
+    }
+}
+
+impl TotalCmp for f64 {
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        f64::total_cmp(self, other)
+    }
+}
+
+/// A convenience macro for finding the smallest of several values.
+///
+/// This macro provides four ways to compute a minimum:
+/// - Over a list of expressions whose type implements `Ord`
+/// - Over a list of floats, using `total_cmp` (`total;` prefix)
+/// - Over an iterator, returning an `Option`
+/// - Over an iterator of floats, returning an `Option`
+///
+/// # Examples
+///
+/// ## Minimum of a list of values
+/// ```
+/// # use smacro::min_of;
+/// let smallest = min_of!(7, 3, 9, 4);
+/// assert_eq!(smallest, 3);
+///
+/// let first = min_of!("pear", "apple", "fig");
+/// assert_eq!(first, "apple");
+/// ```
+///
+/// ## Minimum of floats
+/// ```
+/// # use smacro::min_of;
+/// let smallest = min_of!(total; 2.5, -1.0, 0.3);
+/// assert_eq!(smallest, -1.0);
+/// ```
+///
+/// ## Minimum of an iterator
+/// ```
+/// # use smacro::min_of;
+/// let ages = vec![31, 25, 47];
+///
+/// assert_eq!(min_of!(in ages), Some(25));
+/// assert_eq!(min_of!(in Vec::<i32>::new()), None);
+/// assert_eq!(min_of!(total; in [0.5, 0.25]), Some(0.25));
+/// ```
+///
+/// # Evaluation
+///
+/// Each expression is evaluated exactly once, from left to right. When
+/// several values compare equal, the first one is returned.
+#[macro_export]
+macro_rules! min_of {
+    (total; in $iter:expr) => {
+        core::iter::IntoIterator::into_iter($iter).min_by(|a, b| $crate::minmax::TotalCmp::total_cmp(a, b))
+    };
+    (total; $($e:expr),+ $(,)?) => {
+        $crate::min_of!(total; in [$($e),+]).unwrap()
+    };
+    (in $iter:expr) => {
+        core::iter::Iterator::min(core::iter::IntoIterator::into_iter($iter))
+    };
+    ($($e:expr),+ $(,)?) => {
+        $crate::min_of!(in [$($e),+]).unwrap()
+    };
+}
+
+/// A convenience macro for finding the largest of several values.
+///
+/// This macro accepts the same four forms as [`min_of!`]:
+/// - Over a list of expressions whose type implements `Ord`
+/// - Over a list of floats, using `total_cmp` (`total;` prefix)
+/// - Over an iterator, returning an `Option`
+/// - Over an iterator of floats, returning an `Option`
+///
+/// # Examples
+///
+/// ## Maximum of a list of values
+/// ```
+/// # use smacro::max_of;
+/// let largest = max_of!(7, 3, 9, 4);
+/// assert_eq!(largest, 9);
+/// ```
+///
+/// ## Maximum of floats
+/// ```
+/// # use smacro::max_of;
+/// let largest = max_of!(total; 2.5, -1.0, 0.3);
+/// assert_eq!(largest, 2.5);
+/// ```
+///
+/// ## Maximum of an iterator
+/// ```
+/// # use smacro::max_of;
+/// let words = ["a", "abc", "ab"];
+///
+/// assert_eq!(max_of!(in words.iter().map(|w| w.len())), Some(3));
+/// assert_eq!(max_of!(total; in Vec::<f64>::new()), None);
+/// ```
+///
+/// # Evaluation
+///
+/// Each expression is evaluated exactly once, from left to right. When
+/// several values compare equal, the last one is returned.
+#[macro_export]
+macro_rules! max_of {
+    (total; in $iter:expr) => {
+        core::iter::IntoIterator::into_iter($iter).max_by(|a, b| $crate::minmax::TotalCmp::total_cmp(a, b))
+    };
+    (total; $($e:expr),+ $(,)?) => {
+        $crate::max_of!(total; in [$($e),+]).unwrap()
+    };
+    (in $iter:expr) => {
+        core::iter::Iterator::max(core::iter::IntoIterator::into_iter($iter))
+    };
+    ($($e:expr),+ $(,)?) => {
+        $crate::max_of!(in [$($e),+]).unwrap()
+    };
+}
+
+/// A convenience macro for finding both the smallest and the largest value at once.
+///
+/// This macro accepts the same four forms as [`min_of!`] and [`max_of!`],
+/// returning a `(min, max)` tuple. Because the same value may be both the
+/// minimum and the maximum, the element type must implement `Clone`.
+///
+/// # Examples
+///
+/// ## Bounds of a list of values
+/// ```
+/// # use smacro::minmax;
+/// let (lo, hi) = minmax!(7, 3, 9, 4);
+///
+/// assert_eq!(lo, 3);
+/// assert_eq!(hi, 9);
+/// ```
+///
+/// ## Bounds of floats
+/// ```
+/// # use smacro::minmax;
+/// let (lo, hi) = minmax!(total; 2.5, -1.0, 0.3);
+///
+/// assert_eq!(lo, -1.0);
+/// assert_eq!(hi, 2.5);
+/// ```
+///
+/// ## Bounds of an iterator
+/// ```
+/// # use smacro::minmax;
+/// let latencies = vec![120, 80, 310];
+///
+/// assert_eq!(minmax!(in latencies), Some((80, 310)));
+/// assert_eq!(minmax!(in Vec::<u32>::new()), None);
+/// ```
+///
+/// # Performance Note
+///
+/// The input is traversed a single time, performing two comparisons per element.
+#[macro_export]
+macro_rules! minmax {
+    (total; in $iter:expr) => {
+        core::iter::Iterator::fold(
+            core::iter::IntoIterator::into_iter($iter),
+            None,
+            |acc, item| match acc {
+                None => Some((core::clone::Clone::clone(&item), item)),
+                Some((min, max)) => {
+                    let min = if $crate::minmax::TotalCmp::total_cmp(&item, &min).is_lt() {
+                        core::clone::Clone::clone(&item)
+                    } else {
+                        min
+                    };
+                    let max = if $crate::minmax::TotalCmp::total_cmp(&item, &max).is_ge() {
+                        item
+                    } else {
+                        max
+                    };
+                    Some((min, max))
+                }
+            },
+        )
+    };
+    (total; $($e:expr),+ $(,)?) => {
+        $crate::minmax!(total; in [$($e),+]).unwrap()
+    };
+    (in $iter:expr) => {
+        core::iter::Iterator::fold(
+            core::iter::IntoIterator::into_iter($iter),
+            None,
+            |acc, item| match acc {
+                None => Some((core::clone::Clone::clone(&item), item)),
+                Some((min, max)) => {
+                    let min = if item < min {
+                        core::clone::Clone::clone(&item)
+                    } else {
+                        min
+                    };
+                    let max = if item >= max { item } else { max };
+                    Some((min, max))
+                }
+            },
+        )
+    };
+    ($($e:expr),+ $(,)?) => {
+        $crate::minmax!(in [$($e),+]).unwrap()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn min_of_values() {
+        assert_eq!(min_of!(4, 2, 8), 2);
+        assert_eq!(min_of!(5), 5);
+        assert_eq!(min_of!(3, 1, 2,), 1);
+    }
+
+    #[test]
+    fn max_of_values() {
+        assert_eq!(max_of!(4, 2, 8), 8);
+        assert_eq!(max_of!("b", "c", "a"), "c");
+    }
+
+    #[test]
+    fn min_max_of_floats() {
+        assert_eq!(min_of!(total; 1.5, -0.5, 3.0), -0.5);
+        assert_eq!(max_of!(total; 1.5, -0.5, 3.0), 3.0);
+        assert!(max_of!(total; 1.0f32, f32::NAN, 2.0).is_nan());
+    }
+
+    #[test]
+    fn min_max_of_iterators() {
+        let v = vec![10, 30, 20];
+        assert_eq!(min_of!(in v.iter()), Some(&10));
+        assert_eq!(max_of!(in v), Some(30));
+        assert_eq!(min_of!(in core::iter::empty::<u8>()), None);
+        assert_eq!(max_of!(total; in [0.1, 0.7, 0.4]), Some(0.7));
+    }
+
+    #[test]
+    fn minmax_values() {
+        assert_eq!(minmax!(5, 1, 9, 3), (1, 9));
+        assert_eq!(minmax!(42), (42, 42));
+        assert_eq!(minmax!(total; 0.5, -2.0, 8.25), (-2.0, 8.25));
+    }
+
+    #[test]
+    fn minmax_iterators() {
+        let words = vec!["kiwi".to_string(), "apple".to_string(), "plum".to_string()];
+        assert_eq!(
+            minmax!(in words),
+            Some(("apple".to_string(), "plum".to_string()))
+        );
+        assert_eq!(minmax!(in Vec::<i32>::new()), None);
+        assert_eq!(minmax!(total; in Vec::<f64>::new()), None);
+    }
+
+    #[test]
+    fn evaluates_each_expression_once() {
+        let mut calls = 0;
+        let mut next = |v: i32| {
+            calls += 1;
+            v
+        };
+        let (lo, hi) = minmax!(next(3), next(1), next(2));
+        assert_eq!((lo, hi), (1, 3));
+        assert_eq!(calls, 3);
+    }
+}
@@ -0,0 +1,139 @@
+//! Newtype declaration utilities.
+//!
+//! The `newtype!` macro declares a single-field tuple struct together with
+//! the conversion, `Deref` and `Display` impls that newtypes almost always
+//! need, so wrapping a primitive in a dedicated type costs one line.
+
+/// A convenience macro for declaring newtype wrappers.
+///
+/// The macro declares a tuple struct wrapping a single value and generates:
+/// - `From<Inner> for Name`, and therefore `Into<Name> for Inner`
+/// - `From<Name> for Inner`, and therefore `Into<Inner> for Name`
+/// - `Deref<Target = Inner>`, giving read access to the inner value's methods
+/// - `Display`, forwarding to the inner value
+///
+/// Derives, attributes and visibility are written as usual. Since `Display`
+/// is always generated, the inner type must implement `Display`.
+///
+/// # Examples
+///
+/// ## Declaring a newtype
+/// ```
+/// # use smacro::newtype;
+/// newtype!(UserId(u64); derive(Debug, Clone, Copy, PartialEq, Eq, Hash));
+///
+/// let id = UserId::from(42);
+/// let raw: u64 = id.into();
+///
+/// assert_eq!(raw, 42);
+/// assert_eq!(id.to_string(), "42");
+/// assert!(!id.is_power_of_two()); // Deref to `u64`
+/// ```
+///
+/// ## Attributes and visibility
+/// ```
+/// # use smacro::newtype;
+/// mod model {
+///     # use smacro::newtype;
+///     newtype!(
+///         /// A validated e-mail address.
+///         pub Email(pub String); derive(Debug, Clone, PartialEq)
+///     );
+/// }
+///
+/// let email = model::Email("ada@example.com".to_string());
+///
+/// assert_eq!(email.len(), 15);
+/// assert_eq!(format!("<{}>", email), "<ada@example.com>");
+/// ```
+///
+/// ## Without derives
+/// ```
+/// # use smacro::newtype;
+/// newtype!(Meters(f64));
+///
+/// let distance: Meters = 12.5.into();
+/// assert_eq!(*distance, 12.5);
+/// ```
+#[macro_export]
+macro_rules! newtype {
+    (
+        $(#[$meta:meta])*
+        $vis:vis $name:ident($inner_vis:vis $inner:ty)
+        $(; derive($($derive:path),* $(,)?))? $(;)?
+    ) => {
+        $(#[$meta])*
+        $(#[derive($($derive),*)])?
+        $vis struct $name($inner_vis $inner);
+
+        impl core::convert::From<$inner> for $name {
+            fn from(value: $inner) -> Self {
+                $name(value)
+            }
+        }
+
+        impl core::convert::From<$name> for $inner {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl core::ops::Deref for $name {
+            type Target = $inner;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Display::fmt(&self.0, f)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    newtype!(UserId(u64); derive(Debug, Clone, Copy, PartialEq, Eq, Hash));
+    newtype!(Name(String); derive(Debug, Clone));
+    newtype!(Ratio(f32));
+
+    #[test]
+    fn conversions() {
+        let id: UserId = 7.into();
+        assert_eq!(id, UserId(7));
+        assert_eq!(u64::from(id), 7);
+    }
+
+    #[test]
+    fn derives_are_applied() {
+        let ids: HashSet<UserId> = [UserId(1), UserId(2), UserId(1)].into_iter().collect();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(format!("{:?}", UserId(3)), "UserId(3)");
+    }
+
+    #[test]
+    fn deref_to_inner() {
+        let name = Name::from("Ada".to_string());
+        assert_eq!(name.len(), 3);
+        assert!(name.starts_with('A'));
+    }
+
+    #[test]
+    fn display_forwards_format_spec() {
+        assert_eq!(UserId(5).to_string(), "5");
+        assert_eq!(format!("{:>4}", UserId(5)), "   5");
+        assert_eq!(format!("{:.1}", Ratio(0.25)), "0.2");
+    }
+
+    #[test]
+    fn without_derives() {
+        let r = Ratio::from(0.5);
+        let inner: f32 = r.into();
+        assert_eq!(inner, 0.5);
+    }
+}
@@ -0,0 +1,100 @@
+//! Option to Result conversion utilities.
+//!
+//! The `ok_or_s!` macro converts an `Option` into a `Result<_, String>`,
+//! formatting the error message with the `s!` syntax only when the value is
+//! missing.
+
+/// A convenience macro for converting an `Option` into a `Result` with a formatted message.
+///
+/// `ok_or_s!(opt, "...", args...)` is equivalent to
+/// `opt.ok_or_else(|| format!("...", args...))`, so the message is only
+/// built when the option is `None`.
+///
+/// # Examples
+///
+/// ## Formatted message
+/// ```
+/// # use smacro::ok_or_s;
+/// use std::collections::HashMap;
+///
+/// let users: HashMap<u32, &str> = HashMap::from([(1, "ada")]);
+/// let id = 7;
+///
+/// let found = ok_or_s!(users.get(&1), "user {} not found", 1);
+/// let missing = ok_or_s!(users.get(&id), "user {} not found", id);
+///
+/// assert_eq!(found, Ok(&"ada"));
+/// assert_eq!(missing, Err("user 7 not found".to_string()));
+/// ```
+///
+/// ## With the `?` operator
+/// ```
+/// # use smacro::ok_or_s;
+/// fn first_char(text: &str) -> Result<char, String> {
+///     let c = ok_or_s!(text.chars().next(), "empty input")?;
+///     Ok(c)
+/// }
+///
+/// assert_eq!(first_char("rust"), Ok('r'));
+/// assert_eq!(first_char(""), Err("empty input".to_string()));
+/// ```
+///
+/// ## Any value convertible with `ToString`
+/// ```
+/// # use smacro::ok_or_s;
+/// let code = 404;
+/// let result: Result<(), String> = ok_or_s!(None, code);
+///
+/// assert_eq!(result, Err("404".to_string()));
+/// ```
+#[macro_export]
+macro_rules! ok_or_s {
+    ($opt:expr, $fmt:literal $(, $($arg:tt)*)?) => {
+        core::option::Option::ok_or_else($opt, || format!($fmt $(, $($arg)*)?))
+    };
+    ($opt:expr, $msg:expr $(,)?) => {
+        core::option::Option::ok_or_else($opt, || $crate::s!($msg))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn some_passes_through() {
+        let v: Result<i32, String> = ok_or_s!(Some(3), "missing");
+        assert_eq!(v, Ok(3));
+    }
+
+    #[test]
+    fn none_formats_message() {
+        let key = "port";
+        let v: Result<u16, String> = ok_or_s!(None, "missing key `{}`", key);
+        assert_eq!(v, Err("missing key `port`".to_string()));
+    }
+
+    #[test]
+    fn inline_captures() {
+        let id = 12;
+        let v: Result<(), String> = ok_or_s!(None, "user {id} not found");
+        assert_eq!(v, Err("user 12 not found".to_string()));
+    }
+
+    #[test]
+    fn message_is_lazy() {
+        let mut built = false;
+        let mut build = || {
+            built = true;
+            "never"
+        };
+        let v = ok_or_s!(Some(1), "{}", build());
+        assert_eq!(v, Ok(1));
+        assert!(!built);
+    }
+
+    #[test]
+    fn non_literal_message() {
+        let msg = String::from("nothing here");
+        let v: Result<u8, String> = ok_or_s!(None, msg);
+        assert_eq!(v, Err("nothing here".to_string()));
+    }
+}
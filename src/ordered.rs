@@ -0,0 +1,222 @@
+//! Insertion-ordered map utilities.
+//!
+//! Requires the `map` feature to be enabled.
+//!
+//! [`OrderedMap`] is a small `Vec`-backed map that iterates in insertion
+//! order. It is what `map![ordered; ...]` returns, so maps written as
+//! literals print and iterate in the order they were written, which keeps
+//! snapshot tests stable.
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::ops::Index;
+
+/// A map that keeps its entries in insertion order.
+///
+/// Lookups are linear scans, which is fast for the small maps written as
+/// literals but not meant for large collections. Keys only need `Eq`.
+/// Replacing the value of an existing key keeps its position.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct OrderedMap<K, V> {
+    entries: Vec<(K, V)>,
+}
+
+impl<K, V> OrderedMap<K, V> {
+    /// Creates an empty map.
+    pub const fn new() -> Self {
+        OrderedMap {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Creates an empty map with room for `capacity` entries.
+    pub fn with_capacity(capacity: usize) -> Self {
+        OrderedMap {
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the entries in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+
+    /// Iterates over the keys in insertion order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    /// Iterates over the values in insertion order.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.entries.iter().map(|(_, v)| v)
+    }
+}
+
+impl<K: Eq, V> OrderedMap<K, V> {
+    fn position<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.entries.iter().position(|(k, _)| k.borrow() == key)
+    }
+
+    /// Inserts a value, returning the previous value of the key if any.
+    ///
+    /// A new key is appended at the end; an existing key keeps its position.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.position(&key) {
+            Some(i) => Some(core::mem::replace(&mut self.entries[i].1, value)),
+            None => {
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    /// Returns a reference to the value of the key.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position(key).map(|i| &self.entries[i].1)
+    }
+
+    /// Returns a mutable reference to the value of the key.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position(key).map(|i| &mut self.entries[i].1)
+    }
+
+    /// Returns `true` if the map contains the key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position(key).is_some()
+    }
+
+    /// Removes the key, shifting later entries back, and returns its value.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position(key).map(|i| self.entries.remove(i).1)
+    }
+}
+
+impl<K, V> Default for OrderedMap<K, V> {
+    fn default() -> Self {
+        OrderedMap::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for OrderedMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, Q, V> Index<&Q> for OrderedMap<K, V>
+where
+    K: Eq + Borrow<Q>,
+    Q: Eq + ?Sized,
+{
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("key not found in OrderedMap")
+    }
+}
+
+impl<K: Eq, V> Extend<(K, V)> for OrderedMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Eq, V> FromIterator<(K, V)> for OrderedMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = OrderedMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V> IntoIterator for OrderedMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = std::vec::IntoIter<(K, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OrderedMap;
+
+    #[test]
+    fn keeps_insertion_order() {
+        let mut map = OrderedMap::new();
+        map.insert("b", 1);
+        map.insert("a", 2);
+        map.insert("c", 3);
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), ["b", "a", "c"]);
+        assert_eq!(format!("{:?}", map), r#"{"b": 1, "a": 2, "c": 3}"#);
+    }
+
+    #[test]
+    fn replacing_keeps_position() {
+        let mut map: OrderedMap<String, i32> =
+            [("x".into(), 1), ("y".into(), 2)].into_iter().collect();
+        assert_eq!(map.insert("x".into(), 10), Some(1));
+        assert_eq!(map.values().copied().collect::<Vec<_>>(), [10, 2]);
+        assert_eq!(map["x"], 10);
+    }
+
+    #[test]
+    fn lookup_and_remove() {
+        let mut map: OrderedMap<String, u8> = OrderedMap::with_capacity(3);
+        map.extend([
+            ("a".to_string(), 1),
+            ("b".to_string(), 2),
+            ("c".to_string(), 3),
+        ]);
+        assert!(map.contains_key("b"));
+        *map.get_mut("b").unwrap() += 1;
+        assert_eq!(map.remove("a"), Some(1));
+        assert_eq!(map.remove("a"), None);
+        assert_eq!(
+            map.into_iter().collect::<Vec<_>>(),
+            [("b".to_string(), 3), ("c".to_string(), 3)]
+        );
+    }
+
+    #[test]
+    fn literal_form() {
+        let map = crate::map![ordered; "z" => 26, "a" => 1, "m" => 13,];
+        assert_eq!(map.iter().map(|(k, _)| *k).collect::<String>(), "zam");
+
+        let empty: OrderedMap<u8, u8> = crate::map![ordered;];
+        assert!(empty.is_empty());
+    }
+}
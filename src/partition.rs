@@ -0,0 +1,170 @@
+//! Collection partitioning utilities.
+//!
+//! The `partition!`, `partition_set!` and `partition_by!` macros split the
+//! elements of any iterable into groups, either in two by a predicate or
+//! in many by a classifier closure.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+#[doc(hidden)]
+pub fn partition<I, F>(items: I, predicate: F) -> (Vec<I::Item>, Vec<I::Item>)
+where
+    I: IntoIterator,
+    F: FnMut(&I::Item) -> bool,
+{
+    items.into_iter().partition(predicate)
+}
+
+#[doc(hidden)]
+pub fn partition_set<I, F>(items: I, predicate: F) -> (HashSet<I::Item>, HashSet<I::Item>)
+where
+    I: IntoIterator,
+    I::Item: Eq + Hash,
+    F: FnMut(&I::Item) -> bool,
+{
+    items.into_iter().partition(predicate)
+}
+
+#[doc(hidden)]
+pub fn partition_by<I, K, F>(items: I, mut classify: F) -> HashMap<K, Vec<I::Item>>
+where
+    I: IntoIterator,
+    K: Eq + Hash,
+    F: FnMut(&I::Item) -> K,
+{
+    let mut groups: HashMap<K, Vec<I::Item>> = HashMap::new();
+    for item in items {
+        groups.entry(classify(&item)).or_default().push(item);
+    }
+    groups
+}
+
+/// A convenience macro for splitting a collection in two by a predicate.
+///
+/// The collection can be anything implementing `IntoIterator`, and the
+/// predicate receives a reference to each element. The result is a pair of
+/// `Vec`s: the elements for which the predicate holds, then the others,
+/// both in their original order.
+///
+/// # Examples
+///
+/// ## Splitting structs
+/// ```
+/// # use smacro::partition;
+/// struct Account {
+///     name: &'static str,
+///     is_admin: bool,
+/// }
+///
+/// let accounts = vec![
+///     Account { name: "root", is_admin: true },
+///     Account { name: "ada", is_admin: false },
+/// ];
+///
+/// let (admins, users) = partition!(accounts, |a| a.is_admin);
+/// assert_eq!(admins[0].name, "root");
+/// assert_eq!(users[0].name, "ada");
+/// ```
+///
+/// ## Borrowing
+/// ```
+/// # use smacro::partition;
+/// let numbers = [1, 2, 3, 4, 5];
+/// let (even, odd) = partition!(&numbers, |&&n| n % 2 == 0);
+///
+/// assert_eq!(even, [&2, &4]);
+/// assert_eq!(odd, [&1, &3, &5]);
+/// ```
+#[macro_export]
+macro_rules! partition {
+    ($items:expr, $predicate:expr $(,)?) => {
+        $crate::partition::partition($items, $predicate)
+    };
+}
+
+/// A convenience macro for splitting a collection into two `HashSet`s.
+///
+/// Works like [`partition!`](macro@crate::partition), but collects the
+/// elements into a pair of `HashSet`s, dropping duplicates.
+///
+/// # Examples
+///
+/// ## Splitting into sets
+/// ```
+/// # use smacro::partition_set;
+/// let ports = vec![80, 443, 8080, 80, 22];
+/// let (privileged, unprivileged) = partition_set!(ports, |&p| p < 1024);
+///
+/// assert_eq!(privileged.len(), 3);
+/// assert!(unprivileged.contains(&8080));
+/// ```
+#[macro_export]
+macro_rules! partition_set {
+    ($items:expr, $predicate:expr $(,)?) => {
+        $crate::partition::partition_set($items, $predicate)
+    };
+}
+
+/// A convenience macro for grouping a collection by a classifier.
+///
+/// The classifier receives a reference to each element and returns its
+/// group key. The result is a `HashMap` from each key to a `Vec` of the
+/// elements in that group, in their original order.
+///
+/// # Examples
+///
+/// ## Grouping by a computed key
+/// ```
+/// # use smacro::partition_by;
+/// let files = ["main.rs", "lib.rs", "README.md", "Cargo.toml"];
+/// let by_extension = partition_by!(files, |f| f.rsplit('.').next().unwrap());
+///
+/// assert_eq!(by_extension["rs"], ["main.rs", "lib.rs"]);
+/// assert_eq!(by_extension.len(), 3);
+/// ```
+#[macro_export]
+macro_rules! partition_by {
+    ($items:expr, $classify:expr $(,)?) => {
+        $crate::partition::partition_by($items, $classify)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    #[test]
+    fn partition_keeps_order() {
+        let (small, large) = partition!(vec![5, 1, 9, 2, 7], |&n| n < 5);
+        assert_eq!(small, [1, 2]);
+        assert_eq!(large, [5, 9, 7]);
+    }
+
+    #[test]
+    fn partition_iterators_and_empty() {
+        let (upper, lower) = partition!("aBcD".chars(), |c| c.is_uppercase(),);
+        assert_eq!(upper, ['B', 'D']);
+        assert_eq!(lower, ['a', 'c']);
+
+        let (yes, no) = partition!(Vec::<u8>::new(), |_| true);
+        assert!(yes.is_empty() && no.is_empty());
+    }
+
+    #[test]
+    fn partition_set_dedups() {
+        let words = ["a", "bb", "a", "cc", "bb"];
+        let (short, long) = partition_set!(words.iter(), |w| w.len() == 1);
+        assert_eq!(short, HashSet::from([&"a"]));
+        assert_eq!(long, HashSet::from([&"bb", &"cc"]));
+    }
+
+    #[test]
+    fn partition_by_groups() {
+        let groups = partition_by!(1..=10, |n| n % 3);
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[&0], [3, 6, 9]);
+        assert_eq!(groups[&1], [1, 4, 7, 10]);
+        assert_eq!(groups[&2], [2, 5, 8]);
+    }
+}
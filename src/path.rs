@@ -0,0 +1,89 @@
+//! Path construction utilities.
+//!
+//! The `concat_path!` macro joins path components at compile time into a
+//! `&'static str`, using the separator of the target platform.
+
+/// A convenience macro for joining path components at compile time.
+///
+/// This macro provides two ways to join components:
+/// - With the target platform's separator, `\` on Windows and `/` elsewhere
+/// - Always with `/` (`/;` prefix), for URLs, archives or `include_str!`
+///
+/// Components can be string literals or macros expanding to one, such as
+/// `env!`. The result is a `&'static str` that can be used in `const` items.
+///
+/// # Examples
+///
+/// ## Platform separator
+/// ```
+/// # use smacro::concat_path;
+/// const CONFIG: &str = concat_path!("etc", "app", "config.toml");
+///
+/// if cfg!(windows) {
+///     assert_eq!(CONFIG, "etc\\app\\config.toml");
+/// } else {
+///     assert_eq!(CONFIG, "etc/app/config.toml");
+/// }
+/// ```
+///
+/// ## Forced forward slashes
+/// ```
+/// # use smacro::concat_path;
+/// let manifest = concat_path!(/; env!("CARGO_MANIFEST_DIR"), "Cargo.toml");
+///
+/// assert!(manifest.ends_with("/Cargo.toml"));
+/// assert!(std::path::Path::new(manifest).exists());
+/// ```
+///
+/// Components are joined as-is, so they should not start or end with a
+/// separator themselves.
+#[macro_export]
+macro_rules! concat_path {
+    (/; $first:expr $(, $rest:expr)* $(,)?) => {
+        concat!($first $(, "/", $rest)*)
+    };
+    ($first:expr $(, $rest:expr)* $(,)?) => {
+        if cfg!(windows) {
+            concat!($first $(, "\\", $rest)*)
+        } else {
+            concat!($first $(, "/", $rest)*)
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{MAIN_SEPARATOR_STR, Path};
+
+    #[test]
+    fn single_component() {
+        assert_eq!(concat_path!("file.txt"), "file.txt");
+        assert_eq!(concat_path!(/; "file.txt"), "file.txt");
+    }
+
+    #[test]
+    fn uses_platform_separator() {
+        let joined = concat_path!("a", "b", "c",);
+        assert_eq!(joined, ["a", "b", "c"].join(MAIN_SEPARATOR_STR));
+    }
+
+    #[test]
+    fn forced_slash() {
+        assert_eq!(
+            concat_path!(/; "assets", "img", "logo.png"),
+            "assets/img/logo.png"
+        );
+    }
+
+    #[test]
+    fn const_and_env_components() {
+        const SRC: &str = concat_path!(env!("CARGO_MANIFEST_DIR"), "src", "path.rs");
+        assert!(Path::new(SRC).is_file());
+    }
+
+    #[test]
+    fn matches_path_join() {
+        let joined = Path::new("x").join("y").join("z.rs");
+        assert_eq!(Path::new(concat_path!("x", "y", "z.rs")), joined);
+    }
+}
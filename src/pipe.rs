@@ -0,0 +1,109 @@
+//! Expression pipelining utilities.
+//!
+//! The `pipe!` macro threads a value through a series of functions and
+//! closures from left to right, replacing deeply nested calls such as
+//! `validate(parse(input))` in data-transformation code.
+
+#[doc(hidden)]
+pub fn apply<T, R>(value: T, stage: impl FnOnce(T) -> R) -> R {
+    stage(value)
+}
+
+/// A convenience macro for passing a value through a chain of functions.
+///
+/// `pipe!(x => f => g => h)` is equivalent to `h(g(f(x)))`. Each stage can be
+/// any expression that can be called with a single argument: a function
+/// path, a method path, or a closure.
+///
+/// # Examples
+///
+/// ## Chaining functions and closures
+/// ```
+/// # use smacro::pipe;
+/// fn parse(input: &str) -> i32 {
+///     input.trim().parse().unwrap()
+/// }
+///
+/// fn validate(n: i32) -> i32 {
+///     n.max(0)
+/// }
+///
+/// let result = pipe!(" 21 " => parse => validate => |x| x * 2);
+/// assert_eq!(result, 42);
+/// ```
+///
+/// ## Method paths
+/// ```
+/// # use smacro::pipe;
+/// let shout = pipe!("  hello  " => str::trim => str::to_uppercase);
+/// assert_eq!(shout, "HELLO");
+/// ```
+///
+/// ## Fallible stages
+/// ```
+/// # use smacro::pipe;
+/// fn double(input: &str) -> Result<i32, std::num::ParseIntError> {
+///     let n = pipe!(input => str::parse::<i32>)?;
+///     Ok(n * 2)
+/// }
+///
+/// assert_eq!(double("4"), Ok(8));
+/// assert!(double("four").is_err());
+/// ```
+///
+/// # Evaluation
+///
+/// The input and each stage are evaluated exactly once, from left to right.
+#[macro_export]
+macro_rules! pipe {
+    ($input:expr $(=> $stage:expr)* $(,)?) => {
+        {
+            let value = $input;
+            $(
+                let value = $crate::pipe::apply(value, $stage);
+            )*
+            value
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    fn inc(x: i32) -> i32 {
+        x + 1
+    }
+
+    #[test]
+    fn pipe_identity() {
+        assert_eq!(pipe!(5), 5);
+    }
+
+    #[test]
+    fn pipe_functions() {
+        assert_eq!(pipe!(1 => inc => inc => inc), 4);
+    }
+
+    #[test]
+    fn pipe_closures_and_type_changes() {
+        let v = pipe!("a,b,c" => |s| s.split(',').count() => |n| n * 10);
+        assert_eq!(v, 30);
+    }
+
+    #[test]
+    fn pipe_captures_environment() {
+        let factor = 3;
+        let mut log = vec![];
+        let v = pipe!(2 => |x| x * factor => |x| {
+            log.push(x);
+            x
+        });
+        assert_eq!(v, 6);
+        assert_eq!(log, vec![6]);
+    }
+
+    #[test]
+    fn pipe_method_paths() {
+        let v = pipe!(" 7 " => str::trim => str::parse::<i32> => Result::unwrap => inc);
+        assert_eq!(v, 8);
+    }
+}
@@ -0,0 +1,158 @@
+//! Count-aware wording utilities.
+//!
+//! The `plural!` macro picks the singular or plural form of a word for a
+//! count, so status messages read "1 file" and "3 files" instead of
+//! "1 files".
+
+use crate::stats::ToF64;
+use std::borrow::Cow;
+
+#[doc(hidden)]
+pub fn choose<'a>(n: impl ToF64, one: &'a str, many: &'a str) -> Cow<'a, str> {
+    if n.to_f64() == 1.0 {
+        Cow::Borrowed(one)
+    } else {
+        Cow::Borrowed(many)
+    }
+}
+
+#[doc(hidden)]
+pub fn plural(n: impl ToF64, one: &str) -> Cow<'_, str> {
+    if n.to_f64() == 1.0 {
+        Cow::Borrowed(one)
+    } else {
+        Cow::Owned(pluralize(one))
+    }
+}
+
+/// Applies the regular English plural rules to `word`.
+///
+/// Words ending in `s`, `x`, `z`, `ch` or `sh` take `es`, a consonant
+/// followed by `y` becomes `ies`, and everything else takes `s`. The suffix
+/// is uppercased when the word is written in capitals.
+fn pluralize(word: &str) -> String {
+    let lower = word.to_lowercase();
+    let shouting = word.chars().any(char::is_alphabetic) && !word.chars().any(char::is_lowercase);
+
+    let (stem, suffix) = if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|end| lower.ends_with(end))
+    {
+        (word, "es")
+    } else if lower.len() > 1
+        && lower.ends_with('y')
+        && !lower[..lower.len() - 1].ends_with(['a', 'e', 'i', 'o', 'u'])
+    {
+        (&word[..word.len() - 1], "ies")
+    } else {
+        (word, "s")
+    };
+
+    let mut out = String::with_capacity(stem.len() + suffix.len());
+    out.push_str(stem);
+    if shouting {
+        out.push_str(&suffix.to_uppercase());
+    } else {
+        out.push_str(suffix);
+    }
+    out
+}
+
+/// A convenience macro for choosing the singular or plural form of a word.
+///
+/// This macro provides two ways to pluralize:
+/// - With explicit singular and plural forms, for irregular words
+/// - With the singular form only, using simple English rules for the plural
+///
+/// The singular form is used when the count is exactly one. The count can
+/// be any primitive number, or a reference to one, and is not consumed.
+///
+/// The result is a `Cow<str>`, which can be used directly in format strings
+/// or compared with string slices.
+///
+/// # Examples
+///
+/// ## Explicit forms
+/// ```
+/// # use smacro::plural;
+/// let count = 1;
+///
+/// assert_eq!(plural!(count, "child", "children"), "child");
+/// assert_eq!(plural!(3, "child", "children"), "children");
+/// ```
+///
+/// ## Default English rules
+/// ```
+/// # use smacro::plural;
+/// assert_eq!(plural!(2, "file"), "files");
+/// assert_eq!(plural!(2, "match"), "matches");
+/// assert_eq!(plural!(2, "entry"), "entries");
+/// assert_eq!(plural!(0, "day"), "days");
+/// ```
+///
+/// ## In a message
+/// ```
+/// # use smacro::{plural, s};
+/// let files = vec!["a.rs"];
+/// let n = files.len();
+///
+/// let status = s!("{} {} changed", n, plural!(n, "file"));
+/// assert_eq!(status, "1 file changed");
+/// ```
+#[macro_export]
+macro_rules! plural {
+    ($n:expr, $one:expr, $many:expr $(,)?) => {
+        $crate::plural::choose(&$n, $one, $many)
+    };
+    ($n:expr, $one:expr $(,)?) => {
+        $crate::plural::plural(&$n, $one)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pluralize;
+
+    #[test]
+    fn singular_only_for_one() {
+        assert_eq!(plural!(1, "file"), "file");
+        assert_eq!(plural!(0, "file"), "files");
+        assert_eq!(plural!(2u64, "file"), "files");
+        assert_eq!(plural!(1.0, "point"), "point");
+        assert_eq!(plural!(1.5, "point"), "points");
+    }
+
+    #[test]
+    fn explicit_forms() {
+        let n = 2usize;
+        assert_eq!(plural!(n, "mouse", "mice"), "mice");
+        assert_eq!(plural!(&1, "mouse", "mice"), "mouse");
+        // The count is not moved
+        assert_eq!(n, 2);
+    }
+
+    #[test]
+    fn english_rules() {
+        assert_eq!(pluralize("bus"), "buses");
+        assert_eq!(pluralize("box"), "boxes");
+        assert_eq!(pluralize("branch"), "branches");
+        assert_eq!(pluralize("dish"), "dishes");
+        assert_eq!(pluralize("city"), "cities");
+        assert_eq!(pluralize("key"), "keys");
+        assert_eq!(pluralize("y"), "ys");
+        assert_eq!(pluralize("error"), "errors");
+    }
+
+    #[test]
+    fn keeps_capitalization() {
+        assert_eq!(pluralize("File"), "Files");
+        assert_eq!(pluralize("QUERY"), "QUERIES");
+        assert_eq!(pluralize("URL"), "URLS");
+    }
+
+    #[test]
+    fn in_format_string() {
+        let msg = crate::s!("{} {}", 3, plural!(3, "warning"));
+        assert_eq!(msg, "3 warnings");
+    }
+}
@@ -0,0 +1,150 @@
+//! Query string parsing utilities.
+//!
+//! Requires the `map` feature to be enabled.
+//!
+//! The `to_query_map!` macro parses a URL query string into a `HashMap`,
+//! percent-decoding keys and values, so tests and small servers don't need
+//! a full URL crate.
+
+use std::collections::HashMap;
+
+/// Decodes `+` as a space and `%XX` escapes as bytes, replacing invalid
+/// UTF-8 with `U+FFFD`. Malformed escapes are kept as-is.
+fn decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' => {
+                let hex = bytes
+                    .get(i + 1..i + 3)
+                    .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+                    .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+                match hex {
+                    Some(byte) => {
+                        out.push(byte);
+                        i += 2;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Splits a query string into decoded pairs, ignoring a leading `?` and
+/// empty segments. A key without `=` gets an empty value.
+fn pairs(query: &str) -> impl Iterator<Item = (String, String)> + '_ {
+    query
+        .strip_prefix('?')
+        .unwrap_or(query)
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(key), decode(value))
+        })
+}
+
+#[doc(hidden)]
+pub fn parse(query: &str) -> HashMap<String, String> {
+    pairs(query).collect()
+}
+
+#[doc(hidden)]
+pub fn parse_multi(query: &str) -> HashMap<String, Vec<String>> {
+    let mut map: HashMap<String, Vec<String>> = HashMap::new();
+    for (key, value) in pairs(query) {
+        map.entry(key).or_default().push(value);
+    }
+    map
+}
+
+/// A convenience macro for parsing a query string into a map.
+///
+/// Requires the `map` feature to be enabled.
+///
+/// This macro provides two ways to parse a query:
+/// - Into a `HashMap<String, String>`, where the last value of a repeated key wins
+/// - Into a `HashMap<String, Vec<String>>` keeping every value in order, with `multi;`
+///
+/// Keys and values are percent-decoded, with `+` decoded as a space. A
+/// leading `?` is ignored, and a key without `=` gets an empty value.
+///
+/// # Examples
+///
+/// ## Last value wins
+/// ```
+/// # use smacro::to_query_map;
+/// let params = to_query_map!("?q=rust+macros&page=2&page=3&lang=%C3%A9");
+///
+/// assert_eq!(params["q"], "rust macros");
+/// assert_eq!(params["page"], "3");
+/// assert_eq!(params["lang"], "é");
+/// ```
+///
+/// ## All values
+/// ```
+/// # use smacro::to_query_map;
+/// let url = String::from("tag=a&tag=b%26c&debug");
+/// let params = to_query_map!(multi; url);
+///
+/// assert_eq!(params["tag"], ["a", "b&c"]);
+/// assert_eq!(params["debug"], [""]);
+/// ```
+#[macro_export]
+macro_rules! to_query_map {
+    (multi; $query:expr) => {
+        $crate::query::parse_multi(core::convert::AsRef::<str>::as_ref(&$query))
+    };
+    ($query:expr) => {
+        $crate::query::parse(core::convert::AsRef::<str>::as_ref(&$query))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decode;
+
+    #[test]
+    fn percent_decoding() {
+        assert_eq!(decode("a%20b+c"), "a b c");
+        assert_eq!(decode("%e2%9C%93"), "✓");
+        assert_eq!(decode("100%"), "100%");
+        assert_eq!(decode("%zz%4"), "%zz%4");
+        assert_eq!(decode("%FF"), "\u{FFFD}");
+        assert_eq!(decode("%+A%-1"), "% A%-1");
+    }
+
+    #[test]
+    fn last_value_wins() {
+        let m = to_query_map!("a=1&b=2&b=3");
+        assert_eq!(m.len(), 2);
+        assert_eq!(m["a"], "1");
+        assert_eq!(m["b"], "3");
+    }
+
+    #[test]
+    fn edge_cases() {
+        let m = to_query_map!("?&flag&=empty&x=1=2&&");
+        assert_eq!(m.len(), 3);
+        assert_eq!(m["flag"], "");
+        assert_eq!(m[""], "empty");
+        assert_eq!(m["x"], "1=2");
+        assert!(to_query_map!("").is_empty());
+    }
+
+    #[test]
+    fn multi_values() {
+        let query = String::from("b=2&a=1&b=3&c%5B%5D=x");
+        let m = to_query_map!(multi; query);
+        assert_eq!(m["a"], ["1"]);
+        assert_eq!(m["b"], ["2", "3"]);
+        assert_eq!(m["c[]"], ["x"]);
+    }
+}
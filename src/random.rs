@@ -0,0 +1,557 @@
+//! Random selection utilities.
+//!
+//! Requires the `rand` feature to be enabled.
+//!
+//! The macros in this module pick random elements from collections,
+//! shuffle them and generate random test data using the thread-local
+//! generator of the `rand` crate, or a seeded one for reproducible results,
+//! which is handy for fuzz-style tests, simulations and benchmarks.
+
+use rand::distributions::uniform::{SampleBorrow, SampleRange, SampleUniform};
+use rand::distributions::{Alphanumeric, Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::seq::{IteratorRandom, SliceRandom};
+use rand::{Rng, RngCore, SeedableRng};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::{AddAssign, Range, RangeInclusive};
+
+#[doc(hidden)]
+pub fn sample<I: IntoIterator>(items: I, n: usize) -> Vec<I::Item> {
+    items
+        .into_iter()
+        .choose_multiple(&mut rand::thread_rng(), n)
+}
+
+#[doc(hidden)]
+pub fn choose<I: IntoIterator>(items: I) -> Option<I::Item> {
+    items.into_iter().choose(&mut rand::thread_rng())
+}
+
+#[doc(hidden)]
+pub fn choose_weighted<I, T, W, X>(entries: I) -> Option<T>
+where
+    I: IntoIterator<Item = (T, W)>,
+    W: SampleBorrow<X>,
+    X: SampleUniform + PartialOrd + Default + Clone + for<'a> AddAssign<&'a X>,
+{
+    let (mut items, weights): (Vec<T>, Vec<W>) = entries.into_iter().unzip();
+    let index = WeightedIndex::new(weights).ok()?;
+    Some(items.swap_remove(index.sample(&mut rand::thread_rng())))
+}
+
+/// A convenience macro for picking several distinct random elements.
+///
+/// Requires the `rand` feature to be enabled.
+///
+/// The collection can be anything implementing `IntoIterator`, and the
+/// result is a `Vec` of `n` elements taken from different positions, in
+/// random order. When the collection has fewer than `n` elements, all of
+/// them are returned. Passing a collection by reference gives references
+/// and leaves it untouched.
+///
+/// # Examples
+///
+/// ## Sampling a set
+/// ```
+/// # use smacro::sample;
+/// use std::collections::HashSet;
+///
+/// let servers = HashSet::from(["eu-1", "eu-2", "us-1", "us-2", "ap-1"]);
+/// let picked = sample!(&servers, 3);
+///
+/// assert_eq!(picked.len(), 3);
+/// assert!(picked.iter().all(|s| servers.contains(*s)));
+/// ```
+#[macro_export]
+macro_rules! sample {
+    ($items:expr, $n:expr $(,)?) => {
+        $crate::random::sample($items, $n)
+    };
+}
+
+/// A convenience macro for picking a random element.
+///
+/// Requires the `rand` feature to be enabled.
+///
+/// This macro provides two ways to pick an element:
+/// - Uniformly, from anything implementing `IntoIterator`
+/// - Weighted (`weighted;` prefix), from `(element, weight)` pairs such as
+///   a `HashMap` of weights
+///
+/// It returns `None` when the collection is empty, or in the weighted form
+/// when the weights are all zero or any is negative.
+///
+/// # Examples
+///
+/// ## Uniform choice
+/// ```
+/// # use smacro::choose;
+/// let colors = vec!["red", "green", "blue"];
+/// let color = choose!(&colors).unwrap();
+///
+/// assert!(colors.contains(color));
+/// assert_eq!(choose!(Vec::<u8>::new()), None);
+/// ```
+///
+/// ## Weighted choice
+/// ```
+/// # use smacro::choose;
+/// use std::collections::HashMap;
+///
+/// let weights = HashMap::from([("common", 90), ("rare", 10), ("never", 0)]);
+/// let rarity = choose!(weighted; weights).unwrap();
+///
+/// assert_ne!(rarity, "never");
+/// ```
+#[macro_export]
+macro_rules! choose {
+    (weighted; $entries:expr $(,)?) => {
+        $crate::random::choose_weighted($entries)
+    };
+    ($items:expr $(,)?) => {
+        $crate::random::choose($items)
+    };
+}
+
+#[doc(hidden)]
+pub fn shuffle<T>(items: &mut [T], seed: Option<u64>) {
+    match seed {
+        Some(seed) => items.shuffle(&mut StdRng::seed_from_u64(seed)),
+        None => items.shuffle(&mut rand::thread_rng()),
+    }
+}
+
+#[doc(hidden)]
+pub fn shuffled<I: IntoIterator>(items: I, seed: Option<u64>) -> Vec<I::Item> {
+    let mut items: Vec<I::Item> = items.into_iter().collect();
+    shuffle(&mut items, seed);
+    items
+}
+
+/// A convenience macro for shuffling a slice or `Vec` in place.
+///
+/// Requires the `rand` feature to be enabled.
+///
+/// This macro provides two ways to shuffle:
+/// - Randomly, with the thread-local generator
+/// - Reproducibly, with `seed:` followed by a `u64`
+///
+/// The same seed gives the same order for the same input, as long as the
+/// version of the `rand` crate doesn't change.
+///
+/// # Examples
+///
+/// ## Random order
+/// ```
+/// # use smacro::shuffle;
+/// let mut deck: Vec<u8> = (1..=52).collect();
+/// shuffle!(deck);
+///
+/// assert_eq!(deck.len(), 52);
+/// ```
+///
+/// ## Seeded order
+/// ```
+/// # use smacro::shuffle;
+/// let mut a = [1, 2, 3, 4, 5];
+/// let mut b = [1, 2, 3, 4, 5];
+/// shuffle!(a, seed: 42);
+/// shuffle!(b, seed: 42);
+///
+/// assert_eq!(a, b);
+/// ```
+#[macro_export]
+macro_rules! shuffle {
+    ($items:expr, seed: $seed:expr $(,)?) => {
+        $crate::random::shuffle(&mut $items, Some($seed))
+    };
+    ($items:expr $(,)?) => {
+        $crate::random::shuffle(&mut $items, None)
+    };
+}
+
+/// A convenience macro for collecting an iterable into a shuffled `Vec`.
+///
+/// Requires the `rand` feature to be enabled.
+///
+/// Works like [`shuffle!`](macro@crate::shuffle), including the `seed:`
+/// form, but takes anything implementing `IntoIterator` and returns a new
+/// `Vec`. Passing a collection by reference gives references and leaves it
+/// untouched.
+///
+/// # Examples
+///
+/// ## Reproducible test ordering
+/// ```
+/// # use smacro::shuffled;
+/// let cases = ["empty", "unicode", "long", "nested"];
+///
+/// let order = shuffled!(&cases, seed: 7);
+/// assert_eq!(order, shuffled!(&cases, seed: 7));
+/// assert_eq!(order.len(), 4);
+/// ```
+#[macro_export]
+macro_rules! shuffled {
+    ($items:expr, seed: $seed:expr $(,)?) => {
+        $crate::random::shuffled($items, Some($seed))
+    };
+    ($items:expr $(,)?) => {
+        $crate::random::shuffled($items, None)
+    };
+}
+
+#[doc(hidden)]
+pub fn string(len: usize, charset: Option<&str>) -> String {
+    let mut rng = rand::thread_rng();
+    match charset {
+        Some(charset) => {
+            let chars: Vec<char> = charset.chars().collect();
+            assert!(!chars.is_empty(), "character set must not be empty");
+            (0..len)
+                .map(|_| chars[rng.gen_range(0..chars.len())])
+                .collect()
+        }
+        None => (0..len).map(|_| rng.sample(Alphanumeric) as char).collect(),
+    }
+}
+
+/// Wraps a `rand_map!` generator to pick how it produces a value.
+///
+/// Ranges are sampled through [`SampleGen`], which method resolution tries
+/// first since it is implemented on the value itself. Anything else falls
+/// back to [`ValueGen`], implemented on `&mut Gen`, which returns it as is.
+#[doc(hidden)]
+pub struct Gen<T>(pub Option<T>);
+
+#[doc(hidden)]
+pub trait SampleGen {
+    type Output;
+
+    fn generate(self) -> Self::Output;
+}
+
+impl<T: SampleUniform + PartialOrd> SampleGen for Gen<Range<T>> {
+    type Output = T;
+
+    fn generate(self) -> T {
+        rand::thread_rng().gen_range(self.0.expect("generator already used"))
+    }
+}
+
+impl<T: SampleUniform + PartialOrd> SampleGen for Gen<RangeInclusive<T>> {
+    type Output = T;
+
+    fn generate(self) -> T {
+        rand::thread_rng().gen_range(self.0.expect("generator already used"))
+    }
+}
+
+#[doc(hidden)]
+pub trait ValueGen {
+    type Output;
+
+    fn generate(self) -> Self::Output;
+}
+
+impl<T> ValueGen for &mut Gen<T> {
+    type Output = T;
+
+    fn generate(self) -> T {
+        self.0.take().expect("generator already used")
+    }
+}
+
+#[doc(hidden)]
+pub fn map<K, V>(
+    n: usize,
+    mut key: impl FnMut() -> K,
+    mut value: impl FnMut() -> V,
+) -> HashMap<K, V>
+where
+    K: Eq + Hash,
+{
+    let mut map = HashMap::with_capacity(n);
+    let mut attempts = 0;
+    while map.len() < n {
+        attempts += 1;
+        if attempts > n.saturating_mul(100).max(1000) {
+            panic!("could not generate {} distinct keys", n);
+        }
+        map.insert(key(), value());
+    }
+    map
+}
+
+/// A convenience macro for generating a random string.
+///
+/// Requires the `rand` feature to be enabled.
+///
+/// This macro provides two ways to pick characters:
+/// - From ASCII letters and digits
+/// - From a custom character set given as a `&str`
+///
+/// # Examples
+///
+/// ## Alphanumeric
+/// ```
+/// # use smacro::rand_string;
+/// let token = rand_string!(16);
+///
+/// assert_eq!(token.len(), 16);
+/// assert!(token.chars().all(|c| c.is_ascii_alphanumeric()));
+/// ```
+///
+/// ## Custom character set
+/// ```
+/// # use smacro::rand_string;
+/// let hex = rand_string!(8, "0123456789abcdef");
+///
+/// assert!(hex.chars().all(|c| c.is_ascii_hexdigit()));
+/// ```
+///
+/// # Panics
+///
+/// Panics if the character set is empty.
+#[macro_export]
+macro_rules! rand_string {
+    ($len:expr $(,)?) => {
+        $crate::random::string($len, None)
+    };
+    ($len:expr, $charset:expr $(,)?) => {
+        $crate::random::string($len, Some($charset))
+    };
+}
+
+/// A convenience macro for generating a map of random test data.
+///
+/// Requires the `rand` feature to be enabled.
+///
+/// The number of entries is followed by a generator for the keys and one
+/// for the values. A generator that is a range, like `0..100` or
+/// `'a'..='z'`, is sampled uniformly, and any other expression, like
+/// `rand_string!(8)`, is evaluated again for every entry.
+///
+/// Keys are generated until the map has the requested number of distinct
+/// keys.
+///
+/// # Examples
+///
+/// ## Fixture data
+/// ```
+/// # use smacro::{rand_map, rand_string};
+/// let stock = rand_map!(10; keys: rand_string!(8), values: 0..100);
+///
+/// assert_eq!(stock.len(), 10);
+/// assert!(stock.values().all(|v| (0..100).contains(v)));
+/// ```
+///
+/// ## Constant values
+/// ```
+/// # use smacro::rand_map;
+/// let flags = rand_map!(3; keys: 1..=1000, values: false);
+///
+/// assert!(flags.keys().all(|k| (1..=1000).contains(k)));
+/// ```
+///
+/// # Panics
+///
+/// Panics if enough distinct keys cannot be generated, for example when
+/// the key range is smaller than the number of entries.
+#[macro_export]
+macro_rules! rand_map {
+    ($n:expr; keys: $key:expr, values: $value:expr $(,)?) => {{
+        #[allow(unused_imports)]
+        use $crate::random::{SampleGen as _, ValueGen as _};
+        $crate::random::map(
+            $n,
+            || $crate::random::Gen(Some($key)).generate(),
+            || $crate::random::Gen(Some($value)).generate(),
+        )
+    }};
+}
+
+#[doc(hidden)]
+pub fn range_set<T, R>(n: usize, range: R, seed: Option<u64>) -> HashSet<T>
+where
+    T: SampleUniform + Eq + Hash,
+    R: SampleRange<T> + Clone,
+{
+    let mut rng: Box<dyn RngCore> = match seed {
+        Some(seed) => Box::new(StdRng::seed_from_u64(seed)),
+        None => Box::new(rand::thread_rng()),
+    };
+    let mut set = HashSet::with_capacity(n);
+    let mut attempts = 0;
+    while set.len() < n {
+        attempts += 1;
+        if attempts > n.saturating_mul(100).max(1000) {
+            panic!("could not generate {} distinct values", n);
+        }
+        set.insert(rng.gen_range(range.clone()));
+    }
+    set
+}
+
+/// A convenience macro for generating a set of distinct random integers.
+///
+/// Requires the `rand` feature to be enabled.
+///
+/// The number of values is followed by the range to draw them from, either
+/// half-open or inclusive, and the element type of the `HashSet` is the one
+/// of the range. A `seed:` can be given for reproducible results, as long as
+/// the version of the `rand` crate doesn't change.
+///
+/// # Examples
+///
+/// ## Unique IDs
+/// ```
+/// # use smacro::gen_range_set;
+/// let ids = gen_range_set!(100; 0..1_000_000u64);
+///
+/// assert_eq!(ids.len(), 100);
+/// assert!(ids.iter().all(|id| *id < 1_000_000));
+/// ```
+///
+/// ## Seeded
+/// ```
+/// # use smacro::gen_range_set;
+/// let a = gen_range_set!(5; 1..=50, seed: 42);
+///
+/// assert_eq!(a, gen_range_set!(5; 1..=50, seed: 42));
+/// ```
+///
+/// # Panics
+///
+/// Panics if enough distinct values cannot be generated, for example when
+/// the range is smaller than the number of values.
+#[macro_export]
+macro_rules! gen_range_set {
+    ($n:expr; $range:expr, seed: $seed:expr $(,)?) => {
+        $crate::random::range_set($n, $range, Some($seed))
+    };
+    ($n:expr; $range:expr $(,)?) => {
+        $crate::random::range_set($n, $range, None)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn sample_distinct_elements() {
+        let picked = sample!(0..100, 10);
+        assert_eq!(picked.len(), 10);
+        assert_eq!(picked.iter().collect::<HashSet<_>>().len(), 10);
+        assert!(picked.iter().all(|n| (0..100).contains(n)));
+    }
+
+    #[test]
+    fn sample_more_than_available() {
+        let mut picked = sample!(vec!['a', 'b'], 5,);
+        picked.sort();
+        assert_eq!(picked, ['a', 'b']);
+        assert!(sample!(vec![1, 2, 3], 0).is_empty());
+    }
+
+    #[test]
+    fn choose_uniform() {
+        let items = [10, 20, 30];
+        for _ in 0..20 {
+            assert!(items.contains(choose!(&items).unwrap()));
+        }
+        assert_eq!(choose!(std::iter::once(7)), Some(7));
+    }
+
+    #[test]
+    fn choose_weighted() {
+        let weights = HashMap::from([("always", 1.0), ("never", 0.0)]);
+        for _ in 0..20 {
+            assert_eq!(choose!(weighted; &weights), Some(&"always"));
+        }
+        assert_eq!(choose!(weighted; [("x", 0), ("y", 0)]), None);
+        assert_eq!(choose!(weighted; Vec::<(u8, u32)>::new()), None);
+    }
+
+    #[test]
+    fn shuffle_keeps_elements() {
+        let mut v: Vec<u32> = (0..50).collect();
+        shuffle!(v);
+        let mut sorted = v.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..50).collect::<Vec<_>>());
+
+        let mut empty: [u8; 0] = [];
+        shuffle!(empty, seed: 1);
+    }
+
+    #[test]
+    fn seeded_shuffles_are_reproducible() {
+        let a = shuffled!(0..100, seed: 42,);
+        assert_eq!(a, shuffled!((0..100).collect::<Vec<_>>(), seed: 42));
+        assert_ne!(a, shuffled!(0..100, seed: 43));
+
+        let mut v: Vec<u32> = (0..100).collect();
+        shuffle!(v, seed: 42);
+        assert_eq!(v, a);
+    }
+
+    #[test]
+    fn shuffled_borrows() {
+        let words = vec!["a".to_string(), "b".to_string()];
+        let mut refs = shuffled!(&words);
+        refs.sort();
+        assert_eq!(refs, [&words[0], &words[1]]);
+    }
+
+    #[test]
+    fn rand_strings() {
+        assert_eq!(rand_string!(0), "");
+        let s = rand_string!(64);
+        assert_eq!(s.len(), 64);
+        assert!(s.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert_eq!(rand_string!(5, "é"), "ééééé");
+    }
+
+    #[test]
+    #[should_panic(expected = "character set must not be empty")]
+    fn empty_charset_panics() {
+        rand_string!(3, "");
+    }
+
+    #[test]
+    fn rand_maps() {
+        let m = rand_map!(20; keys: rand_string!(4), values: -5..=5,);
+        assert_eq!(m.len(), 20);
+        assert!(m.values().all(|v| (-5..=5).contains(v)));
+
+        let n: u32 = 10;
+        let full: HashMap<u32, char> = rand_map!(n as usize; keys: 0..n, values: 'a'..'c');
+        assert_eq!(full.len(), 10);
+        assert!(full.values().all(|c| *c == 'a' || *c == 'b'));
+    }
+
+    #[test]
+    #[should_panic(expected = "could not generate 3 distinct keys")]
+    fn too_few_keys_panics() {
+        rand_map!(3; keys: 0..2, values: ());
+    }
+
+    #[test]
+    fn range_sets() {
+        let ids: HashSet<u64> = gen_range_set!(50; 0..1_000);
+        assert_eq!(ids.len(), 50);
+        assert!(ids.iter().all(|id| *id < 1_000));
+
+        let all = gen_range_set!(10; 1..=10, seed: 7,);
+        assert_eq!(all, (1..=10).collect());
+        assert!(gen_range_set!(0; 0..1).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "could not generate 5 distinct values")]
+    fn range_too_small_panics() {
+        gen_range_set!(5; 0..4u8);
+    }
+}
@@ -0,0 +1,240 @@
+//! Range checking and clamping utilities.
+//!
+//! The `in_range!` and `clamp_to!` macros accept any range syntax
+//! (`a..b`, `a..=b`, `a..`, `..b`, `..=b`, `..`), replacing hand-written
+//! `x >= lo && x <= hi` comparisons and their off-by-one mistakes.
+
+use core::ops::{Bound, RangeBounds};
+
+/// Numeric types with a well-defined previous and next value.
+///
+/// This is what allows [`clamp_to!`](crate::clamp_to) to honor exclusive
+/// bounds: clamping into `0..10` yields at most `9`, and clamping into
+/// `0.0..1.0` yields at most the largest `f64` below `1.0`. It is
+/// implemented for all primitive integer and floating point types.
+pub trait Adjacent {
+    /// Returns the closest value strictly smaller than `self`, saturating at the minimum.
+    fn pred(&self) -> Self;
+
+    /// Returns the closest value strictly greater than `self`, saturating at the maximum.
+    fn succ(&self) -> Self;
+}
+
+macro_rules! impl_adjacent_int {
+    ($($t:ty),+) => {
+        $(
+            impl Adjacent for $t {
+                fn pred(&self) -> Self {
+                    self.saturating_sub(1)
+                }
+
+                fn succ(&self) -> Self {
+                    self.saturating_add(1)
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_adjacent_float {
+    ($($t:ty),+) => {
+        $(
+            impl Adjacent for $t {
+                fn pred(&self) -> Self {
+                    self.next_down()
+                }
+
+                fn succ(&self) -> Self {
+                    self.next_up()
+                }
+            }
+        )+
+    };
+}
+
+impl_adjacent_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_adjacent_float!(f32, f64);
+
+#[doc(hidden)]
+pub fn clamp_to<T, R>(value: T, range: R) -> T
+where
+    T: PartialOrd + Adjacent + Clone,
+    R: RangeBounds<T>,
+{
+    const EMPTY: &str = "clamp_to! called with an empty range";
+
+    // `succ` and `pred` saturate, so an excluded bound at the type's limit
+    // leaves nothing inside the range, e.g. `..i32::MIN`
+    let lower = match range.start_bound() {
+        Bound::Included(start) => Some(start.clone()),
+        Bound::Excluded(start) => {
+            let lower = start.succ();
+            assert!(lower != *start, "{}", EMPTY);
+            Some(lower)
+        }
+        Bound::Unbounded => None,
+    };
+    let upper = match range.end_bound() {
+        Bound::Included(end) => Some(end.clone()),
+        Bound::Excluded(end) => {
+            let upper = end.pred();
+            assert!(upper != *end, "{}", EMPTY);
+            Some(upper)
+        }
+        Bound::Unbounded => None,
+    };
+
+    if let (Some(lower), Some(upper)) = (&lower, &upper) {
+        assert!(lower <= upper, "{}", EMPTY);
+    }
+
+    match (lower, upper) {
+        (Some(lower), _) if value < lower => lower,
+        (_, Some(upper)) if value > upper => upper,
+        _ => value,
+    }
+}
+
+/// A convenience macro for checking whether a value falls within a range.
+///
+/// Any range syntax is accepted, and the value can be of any type that is
+/// `PartialOrd` with the range bounds. This expands to `RangeBounds::contains`.
+///
+/// # Examples
+///
+/// ## Inclusive and exclusive ranges
+/// ```
+/// # use smacro::in_range;
+/// let level = 7;
+///
+/// assert!(in_range!(level, 1..=10));
+/// assert!(in_range!(level, 0..8));
+/// assert!(!in_range!(level, 0..7));
+/// ```
+///
+/// ## Half-open ranges
+/// ```
+/// # use smacro::in_range;
+/// let temperature = -4.5;
+///
+/// assert!(in_range!(temperature, ..0.0));
+/// assert!(!in_range!(temperature, 0.0..));
+/// ```
+///
+/// ## Works with any `PartialOrd` type
+/// ```
+/// # use smacro::in_range;
+/// assert!(in_range!('m', 'a'..='z'));
+/// assert!(in_range!("kiwi", "apple".."melon"));
+/// ```
+#[macro_export]
+macro_rules! in_range {
+    ($value:expr, $range:expr $(,)?) => {
+        core::ops::RangeBounds::contains(&($range), &($value))
+    };
+}
+
+/// A convenience macro for clamping a numeric value into a range.
+///
+/// Any range syntax is accepted. Exclusive bounds are honored by clamping to
+/// the closest value inside the range, e.g. `9` for `0..10`.
+///
+/// # Examples
+///
+/// ## Clamping into an inclusive range
+/// ```
+/// # use smacro::clamp_to;
+/// assert_eq!(clamp_to!(1.7, 0.0..=1.0), 1.0);
+/// assert_eq!(clamp_to!(-3, 0..=10), 0);
+/// assert_eq!(clamp_to!(5, 0..=10), 5);
+/// ```
+///
+/// ## Exclusive and half-open ranges
+/// ```
+/// # use smacro::clamp_to;
+/// assert_eq!(clamp_to!(42, 0..10), 9);
+/// assert_eq!(clamp_to!(42u8, ..=16), 16);
+/// assert_eq!(clamp_to!(-1, 0..), 0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the range is empty, e.g. `10..=0`, `3..3` or `..i32::MIN`.
+#[macro_export]
+macro_rules! clamp_to {
+    ($value:expr, $range:expr $(,)?) => {
+        $crate::range::clamp_to($value, $range)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn in_range_inclusive() {
+        assert!(in_range!(1, 1..=10));
+        assert!(in_range!(10, 1..=10));
+        assert!(!in_range!(11, 1..=10));
+    }
+
+    #[test]
+    fn in_range_exclusive_and_open() {
+        assert!(!in_range!(10, 1..10));
+        assert!(in_range!(-100, ..0));
+        assert!(in_range!(100, 0..));
+        assert!(in_range!(0.5, ..=0.5));
+    }
+
+    #[test]
+    fn in_range_with_expressions() {
+        let x = 4;
+        let lo = 2;
+        assert!(in_range!(x * 2, lo..lo * 5));
+        assert!(!in_range!(x + 10, lo..lo * 5));
+    }
+
+    #[test]
+    fn clamp_to_inclusive() {
+        assert_eq!(clamp_to!(-0.5, 0.0..=1.0), 0.0);
+        assert_eq!(clamp_to!(0.25, 0.0..=1.0), 0.25);
+        assert_eq!(clamp_to!(300, 0..=255), 255);
+    }
+
+    #[test]
+    fn clamp_to_exclusive() {
+        assert_eq!(clamp_to!(10, 0..10), 9);
+        assert!(clamp_to!(2.0, 0.0..1.0) < 1.0);
+        assert_eq!(clamp_to!(0u32, ..1), 0);
+    }
+
+    #[test]
+    fn clamp_to_open() {
+        assert_eq!(clamp_to!(-7, 0..), 0);
+        assert_eq!(clamp_to!(7, ..=3), 3);
+        assert_eq!(clamp_to!(7, ..), 7);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty range")]
+    fn clamp_to_empty_range() {
+        clamp_to!(1, 5..5);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty range")]
+    fn clamp_to_empty_below_min() {
+        clamp_to!(0, ..i32::MIN);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty range")]
+    fn clamp_to_empty_above_max() {
+        use core::ops::Bound;
+        clamp_to!(0u8, (Bound::Excluded(u8::MAX), Bound::Unbounded));
+    }
+
+    #[test]
+    fn clamp_to_excluded_near_limits() {
+        assert_eq!(clamp_to!(0, ..i32::MIN + 1), i32::MIN);
+        assert_eq!(clamp_to!(f64::INFINITY, ..f64::INFINITY), f64::MAX);
+    }
+}
@@ -0,0 +1,99 @@
+//! Anonymous record utilities.
+//!
+//! The `record!` macro declares and instantiates an ad-hoc struct in one
+//! expression, giving intermediate data named fields without a formal
+//! struct declaration.
+
+/// A convenience macro for creating anonymous records with named fields.
+///
+/// The macro expands to a block declaring a local struct named `Record`,
+/// with one generic type parameter per field, and returns an instance of it.
+/// Field types are therefore inferred from the values. The struct derives
+/// `Debug` and `Clone`, so those are available whenever every field
+/// implements them.
+///
+/// Field init shorthand is supported, as in a struct literal. Since the
+/// struct is local to the expansion, its type cannot be named: records are
+/// meant to be bound with `let`, or returned as `impl Debug`.
+///
+/// # Examples
+///
+/// ## Creating a record
+/// ```
+/// # use smacro::record;
+/// let person = record! { name: "Ada", age: 36 };
+///
+/// assert_eq!(person.name, "Ada");
+/// assert_eq!(person.age, 36);
+/// assert_eq!(format!("{:?}", person), r#"Record { name: "Ada", age: 36 }"#);
+/// ```
+///
+/// ## Field init shorthand
+/// ```
+/// # use smacro::record;
+/// let words = vec!["a", "bb", "ccc"];
+/// let longest = words.iter().map(|w| w.len()).max().unwrap();
+///
+/// let stats = record! { longest, count: words.len() };
+/// let copy = stats.clone();
+///
+/// assert_eq!(copy.longest, 3);
+/// assert_eq!(copy.count, 3);
+/// ```
+#[macro_export]
+macro_rules! record {
+    {$($field:ident $(: $value:expr)?),* $(,)?} => {
+        {
+            #[allow(non_camel_case_types, dead_code)]
+            #[derive(Debug, Clone)]
+            struct Record<$($field),*> {
+                $($field: $field),*
+            }
+
+            Record { $($field $(: $value)?),* }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn fields_are_accessible() {
+        let r = record! { name: "Ada", age: 36, admin: false };
+        assert_eq!(r.name, "Ada");
+        assert_eq!(r.age, 36);
+        assert!(!r.admin);
+    }
+
+    #[test]
+    fn debug_and_clone() {
+        let r = record! { id: 7u8, tags: vec!["a", "b"], };
+        let copy = r.clone();
+        assert_eq!(copy.tags, r.tags);
+        assert_eq!(format!("{:?}", r), r#"Record { id: 7, tags: ["a", "b"] }"#);
+    }
+
+    #[test]
+    fn shorthand_fields() {
+        let total = 10;
+        let r#type = "sum";
+        let r = record! { total, r#type, average: total as f64 / 4.0 };
+        assert_eq!(r.total, 10);
+        assert_eq!(r.r#type, "sum");
+        assert_eq!(r.average, 2.5);
+    }
+
+    #[test]
+    fn empty_record() {
+        let r = record! {};
+        assert_eq!(format!("{:?}", r), "Record");
+    }
+
+    #[test]
+    fn records_are_independent_types() {
+        let a = record! { x: 1 };
+        let b = record! { x: "one" };
+        assert_eq!(a.x, 1);
+        assert_eq!(b.x, "one");
+    }
+}
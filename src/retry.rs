@@ -0,0 +1,214 @@
+//! Retry utilities.
+//!
+//! Requires the `retry` feature to be enabled.
+//!
+//! The `retry!` macro re-evaluates a fallible expression until it succeeds
+//! or the attempts run out, sleeping between attempts with a fixed or
+//! exponential backoff. An async variant, sleeping with `tokio`, is
+//! available with the `tokio` feature.
+
+use std::time::Duration;
+
+#[doc(hidden)]
+pub fn sleep(delay: Duration) {
+    if !delay.is_zero() {
+        std::thread::sleep(delay);
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[doc(hidden)]
+pub async fn sleep_async(delay: Duration) {
+    if !delay.is_zero() {
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// A convenience macro for retrying fallible operations.
+///
+/// Requires the `retry` feature to be enabled.
+///
+/// The expression must evaluate to a `Result`, and is evaluated again after
+/// each `Err` until it returns `Ok` or the given number of attempts has been
+/// made, in which case the last error is returned.
+///
+/// This macro provides four ways to retry:
+/// - Without waiting between attempts
+/// - With a fixed delay between attempts
+/// - With an exponential backoff, doubling the delay after each attempt
+/// - In async code, sleeping with `tokio` (`async` prefix, requires the `tokio` feature)
+///
+/// # Examples
+///
+/// ## Fixed backoff
+/// ```
+/// # use smacro::retry;
+/// use std::time::Duration;
+///
+/// let mut calls = 0;
+/// let result = retry!(3, Duration::from_millis(1), {
+///     calls += 1;
+///     if calls < 3 { Err("busy") } else { Ok(calls) }
+/// });
+///
+/// assert_eq!(result, Ok(3));
+/// ```
+///
+/// ## Exponential backoff
+/// ```
+/// # use smacro::retry;
+/// use std::time::Duration;
+///
+/// // Waits 1ms, then 2ms, then 4ms between the four attempts
+/// let result: Result<(), &str> = retry!(4, exponential Duration::from_millis(1), {
+///     Err("connection refused")
+/// });
+///
+/// assert_eq!(result, Err("connection refused"));
+/// ```
+///
+/// ## Without delay
+/// ```
+/// # use smacro::retry;
+/// let mut inputs = ["x", "y", "42"].into_iter();
+/// let parsed = retry!(5, inputs.next().unwrap().parse::<u8>());
+///
+/// assert_eq!(parsed, Ok(42));
+/// ```
+///
+/// ## Async code
+/// ```
+/// # #[cfg(feature = "tokio")]
+/// # async fn example() {
+/// # use smacro::retry;
+/// use std::time::Duration;
+///
+/// async fn fetch(url: &str) -> Result<String, String> {
+///     Err(format!("{} is unreachable", url))
+/// }
+///
+/// let response = retry!(async 3, exponential Duration::from_millis(100), {
+///     fetch("https://example.com").await
+/// });
+/// # }
+/// ```
+///
+/// # Notes
+///
+/// At least one attempt is always made, even when `attempts` is `0`. A `?`
+/// inside the retried expression returns from the enclosing function rather
+/// than triggering another attempt.
+#[macro_export]
+macro_rules! retry {
+    (@sleep sync $delay:ident) => {
+        $crate::retry::sleep($delay)
+    };
+    (@sleep async $delay:ident) => {
+        $crate::retry::sleep_async($delay).await
+    };
+    (@run $mode:ident $attempts:expr, $delay:expr, $factor:expr, $body:expr) => {
+        {
+            let attempts: usize = $attempts;
+            let mut delay: std::time::Duration = $delay;
+            let mut attempt = 1;
+            loop {
+                match $body {
+                    Ok(value) => break Ok(value),
+                    Err(err) if attempt >= attempts => break Err(err),
+                    Err(_) => {
+                        $crate::retry!(@sleep $mode delay);
+                        delay = delay.saturating_mul($factor);
+                        attempt += 1;
+                    }
+                }
+            }
+        }
+    };
+    (async $attempts:expr, exponential $delay:expr, $body:expr $(,)?) => {
+        $crate::retry!(@run async $attempts, $delay, 2, $body)
+    };
+    (async $attempts:expr, $delay:expr, $body:expr $(,)?) => {
+        $crate::retry!(@run async $attempts, $delay, 1, $body)
+    };
+    (async $attempts:expr, $body:expr $(,)?) => {
+        $crate::retry!(async $attempts, std::time::Duration::ZERO, $body)
+    };
+    ($attempts:expr, exponential $delay:expr, $body:expr $(,)?) => {
+        $crate::retry!(@run sync $attempts, $delay, 2, $body)
+    };
+    ($attempts:expr, $delay:expr, $body:expr $(,)?) => {
+        $crate::retry!(@run sync $attempts, $delay, 1, $body)
+    };
+    ($attempts:expr, $body:expr $(,)?) => {
+        $crate::retry!($attempts, std::time::Duration::ZERO, $body)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn succeeds_first_try() {
+        let mut calls = 0;
+        let r: Result<i32, ()> = retry!(3, {
+            calls += 1;
+            Ok(7)
+        });
+        assert_eq!(r, Ok(7));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn returns_last_error() {
+        let mut calls = 0;
+        let r: Result<(), String> = retry!(3, Duration::ZERO, {
+            calls += 1;
+            Err(format!("attempt {}", calls))
+        });
+        assert_eq!(r, Err("attempt 3".to_string()));
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn zero_attempts_still_runs_once() {
+        let mut calls = 0;
+        let r: Result<(), ()> = retry!(0, {
+            calls += 1;
+            Err(())
+        });
+        assert!(r.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn fixed_backoff_waits() {
+        let start = Instant::now();
+        let r: Result<(), ()> = retry!(3, Duration::from_millis(5), Err(()));
+        assert!(r.is_err());
+        assert!(start.elapsed() >= Duration::from_millis(10));
+    }
+
+    #[test]
+    fn exponential_backoff_waits() {
+        let start = Instant::now();
+        let r: Result<(), ()> = retry!(3, exponential Duration::from_millis(5), Err(()));
+        assert!(r.is_err());
+        assert!(start.elapsed() >= Duration::from_millis(15));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn async_retry() {
+        async fn flaky(calls: &mut u32) -> Result<u32, &'static str> {
+            *calls += 1;
+            if *calls < 2 { Err("flaky") } else { Ok(*calls) }
+        }
+
+        let mut calls = 0;
+        let r = retry!(async 3, exponential Duration::from_millis(1), {
+            flaky(&mut calls).await
+        });
+        assert_eq!(r, Ok(2));
+    }
+}
@@ -2,7 +2,168 @@
 //!
 //! The `s!` macro provides a convenient way to create `String` instances
 //! with minimal boilerplate, supporting empty strings, direct conversion,
-//! and format string syntax.
+//! and format string syntax, optionally followed by a case, whitespace or
+//! escaping transformation.
+
+/// Splits `input` into words for the snake and kebab case modes.
+///
+/// Words are separated by any non-alphanumeric character and by case
+/// changes, so `"HTTPServer"`, `"http server"` and `"http_server"` all split
+/// into `["HTTP", "Server"]` or equivalent.
+fn words(input: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let chars: Vec<(usize, char)> = input.char_indices().collect();
+
+    for (i, &(offset, c)) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if let Some(begin) = start.take() {
+                words.push(&input[begin..offset]);
+            }
+            continue;
+        }
+
+        if let Some(begin) = start {
+            let prev = chars[i - 1].1;
+            let next_lower = chars.get(i + 1).is_some_and(|&(_, n)| n.is_lowercase());
+            // `aB` starts a new word, and so does the `S` in `HTTPServer`.
+            if c.is_uppercase()
+                && (prev.is_lowercase() || prev.is_numeric() || prev.is_uppercase() && next_lower)
+            {
+                words.push(&input[begin..offset]);
+                start = Some(offset);
+            }
+        } else {
+            start = Some(offset);
+        }
+    }
+
+    if let Some(begin) = start {
+        words.push(&input[begin..]);
+    }
+    words
+}
+
+#[doc(hidden)]
+pub fn to_title(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut word_start = true;
+    for c in input.chars() {
+        if c.is_alphanumeric() {
+            if word_start {
+                out.extend(c.to_uppercase());
+            } else {
+                out.extend(c.to_lowercase());
+            }
+            word_start = false;
+        } else {
+            out.push(c);
+            word_start = c.is_whitespace();
+        }
+    }
+    out
+}
+
+#[doc(hidden)]
+pub fn trim(mut s: String) -> String {
+    s.truncate(s.trim_end().len());
+    let start = s.len() - s.trim_start().len();
+    s.drain(..start);
+    s
+}
+
+#[doc(hidden)]
+pub fn squish(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for word in input.split_whitespace() {
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        out.push_str(word);
+    }
+    out
+}
+
+#[doc(hidden)]
+pub fn truncate(mut s: String, max: usize, ellipsis: &str) -> String {
+    if s.char_indices().nth(max).is_none() {
+        return s;
+    }
+    let keep = max.saturating_sub(ellipsis.chars().count());
+    let end = s.char_indices().nth(keep).map_or(s.len(), |(i, _)| i);
+    s.truncate(end);
+    s.push_str(ellipsis);
+    s
+}
+
+#[doc(hidden)]
+pub fn escape_html(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#x27;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[doc(hidden)]
+pub fn escape_json(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            c if c < ' ' || c == '\u{7f}' => {
+                out.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[doc(hidden)]
+pub fn escape_shell(input: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "_@%+=:,./-".contains(c);
+    if !input.is_empty() && input.chars().all(safe) {
+        return input.to_string();
+    }
+
+    let mut out = String::with_capacity(input.len() + 2);
+    out.push('\'');
+    for c in input.chars() {
+        if c == '\'' {
+            out.push_str("'\\''");
+        } else {
+            out.push(c);
+        }
+    }
+    out.push('\'');
+    out
+}
+
+#[doc(hidden)]
+pub fn to_separated(input: &str, separator: char) -> String {
+    let mut out = String::with_capacity(input.len() + 4);
+    for (i, word) in words(input).into_iter().enumerate() {
+        if i > 0 {
+            out.push(separator);
+        }
+        out.extend(word.chars().flat_map(char::to_lowercase));
+    }
+    out
+}
 
 /// A convenience macro for creating `String` instances with various input types.
 ///
@@ -11,6 +172,28 @@
 /// - Convert any type implementing `ToString` to a `String`
 /// - Use format string syntax with arguments
 ///
+/// Any of the non-empty forms can be prefixed with a mode, which is applied
+/// to the final string:
+/// - `upper` and `lower` change the case of every character
+/// - `title` capitalizes the first letter of each word and lowercases the rest
+/// - `snake` and `kebab` split the string into words and join them with `_` or `-`;
+///   `snake ident Name` converts the name of an identifier instead
+/// - `trim` removes leading and trailing whitespace
+/// - `squish` trims and collapses every internal run of whitespace to one space
+/// - `repeat n;` repeats the string `n` times
+/// - `max n;` truncates the string to `n` characters, ending with `…` when
+///   anything was cut, and `max n, ellipsis;` uses a custom ellipsis
+/// - `escape html;`, `escape json;` and `escape shell;` escape the string for
+///   use in HTML text or attributes, inside a JSON string literal, or as a
+///   single POSIX shell word
+///
+/// Modes can be chained, and are applied from right to left.
+///
+/// With the `const` prefix, the arguments must all be literals, and they
+/// are concatenated at compile time into a `&'static str` instead of a
+/// `String`. The result never allocates and can be used in `const` and
+/// `static` items.
+///
 /// # Examples
 ///
 /// ## Creating an empty string
@@ -51,16 +234,175 @@
 /// assert_eq!(formatted, "Pi is approximately 3.14".to_string());
 /// ```
 ///
+/// ## Case modes
+/// ```
+/// # use smacro::s;
+/// let name = "world";
+///
+/// assert_eq!(s!(upper "hello {}", name), "HELLO WORLD");
+/// assert_eq!(s!(lower "Hello {}", "THERE"), "hello there");
+/// assert_eq!(s!(title "the {} of rust", "BOOK"), "The Book Of Rust");
+/// assert_eq!(s!(kebab "{}Handler", "UserAccount"), "user-account-handler");
+/// ```
+///
+/// ## Normalizing whitespace
+/// ```
+/// # use smacro::s;
+/// let input = "  a   b \t\n c  ";
+///
+/// assert_eq!(s!(trim input), "a   b \t\n c");
+/// assert_eq!(s!(squish input), "a b c");
+/// assert_eq!(s!(squish "[{}]", input), "[ a b c ]");
+/// ```
+///
+/// ## Repeating
+/// ```
+/// # use smacro::s;
+/// let width = 5;
+///
+/// assert_eq!(s!(repeat 3; "-"), "---");
+/// assert_eq!(s!(repeat width - 2; "{}|", 0), "0|0|0|");
+/// assert_eq!(s!(upper repeat 2; "ab"), "ABAB");
+/// ```
+///
+/// ## Truncating
+/// ```
+/// # use smacro::s;
+/// let message = "connection reset by peer";
+///
+/// assert_eq!(s!(max 10; "error: {}", message), "error: co…");
+/// assert_eq!(s!(max 10, "..."; message), "connect...");
+/// assert_eq!(s!(max 10; "short"), "short");
+///
+/// // Lengths are counted in characters, never splitting one in half
+/// assert_eq!(s!(max 4; "héllo wörld"), "hél…");
+/// ```
+///
+/// The ellipsis counts towards the limit, so the result is never longer than
+/// `n` characters unless the ellipsis itself is.
+///
+/// ## Escaping
+/// ```
+/// # use smacro::s;
+/// let comment = "<script>alert('hi')</script>";
+/// let path = "my file's.txt";
+///
+/// assert_eq!(
+///     s!(escape html; "<p>{}</p>", comment),
+///     "&lt;p&gt;&lt;script&gt;alert(&#x27;hi&#x27;)&lt;/script&gt;&lt;/p&gt;"
+/// );
+/// assert_eq!(s!(escape json; "line 1\n\"quoted\""), r#"line 1\n\"quoted\""#);
+/// assert_eq!(s!("rm {}", s!(escape shell; path)), r#"rm 'my file'\''s.txt'"#);
+/// ```
+///
+/// Escaping applies to the whole formatted string, so only user input should
+/// usually be escaped, before it is inserted into a larger template. The
+/// `json` mode does not add the surrounding quotes.
+///
+/// ## Identifiers in code generation
+///
+/// Like every other mode, `snake` and `kebab` convert the value of a
+/// variable. To convert the name of an identifier instead, which is useful
+/// inside other macros, put `ident` before it.
+/// ```
+/// # use smacro::s;
+/// macro_rules! table_name {
+///     ($ty:ident) => {
+///         s!(snake ident $ty)
+///     };
+/// }
+///
+/// let ty = "HTTPRequest";
+///
+/// assert_eq!(table_name!(HTTPRequest), "http_request");
+/// assert_eq!(s!(snake ty), "http_request");
+/// assert_eq!(s!(kebab ident ty), "ty");
+/// ```
+///
+/// ## Compile-time strings
+/// ```
+/// # use smacro::s;
+/// const USER_AGENT: &str = s!(const "smacro/", 1, ".", 0);
+/// const GREETING: &str = s!(const "hello");
+///
+/// assert_eq!(USER_AGENT, "smacro/1.0");
+/// assert_eq!(GREETING, "hello");
+/// ```
+///
 /// # Performance Note
 ///
 /// This macro is a thin wrapper around standard Rust string creation methods:
 /// - `s!()` calls `String::new()`
 /// - `s!(expr)` calls `expr.to_string()`
 /// - `s!(format_str, args...)` calls `format!(format_str, args...)`
+/// - `s!(const literals...)` calls `concat!(literals...)`
 ///
 /// There is no additional overhead compared to calling these methods directly.
+/// Most modes allocate a second string for the transformed result, while
+/// `trim` and `max` reuse the formatted string's buffer.
 #[macro_export]
 macro_rules! s {
+    (const $($lit:literal),* $(,)?) => {
+        concat!($($lit),*)
+    };
+    (@mode upper $($arg:tt)+) => {
+        $crate::s!($($arg)+).to_uppercase()
+    };
+    (@mode lower $($arg:tt)+) => {
+        $crate::s!($($arg)+).to_lowercase()
+    };
+    (@mode title $($arg:tt)+) => {
+        $crate::s::to_title(&$crate::s!($($arg)+))
+    };
+    (@mode snake $($arg:tt)+) => {
+        $crate::s::to_separated(&$crate::s!($($arg)+), '_')
+    };
+    (@mode kebab $($arg:tt)+) => {
+        $crate::s::to_separated(&$crate::s!($($arg)+), '-')
+    };
+    (@mode trim $($arg:tt)+) => {
+        $crate::s::trim($crate::s!($($arg)+))
+    };
+    (@mode repeat $n:expr; $($arg:tt)+) => {
+        $crate::s!($($arg)+).repeat($n)
+    };
+    (@mode max $n:expr, $ellipsis:expr; $($arg:tt)+) => {
+        $crate::s::truncate($crate::s!($($arg)+), $n, $ellipsis)
+    };
+    (@mode max $n:expr; $($arg:tt)+) => {
+        $crate::s::truncate($crate::s!($($arg)+), $n, "…")
+    };
+    (@mode escape html; $($arg:tt)+) => {
+        $crate::s::escape_html(&$crate::s!($($arg)+))
+    };
+    (@mode escape json; $($arg:tt)+) => {
+        $crate::s::escape_json(&$crate::s!($($arg)+))
+    };
+    (@mode escape shell; $($arg:tt)+) => {
+        $crate::s::escape_shell(&$crate::s!($($arg)+))
+    };
+    (@mode squish $($arg:tt)+) => {
+        $crate::s::squish(&$crate::s!($($arg)+))
+    };
+    // Not a mode, e.g. `s!(x as u8)`
+    (@mode $($arg:tt)+) => {
+        ($($arg)+).to_string()
+    };
+    (snake ident $name:ident) => {
+        $crate::s::to_separated(stringify!($name), '_')
+    };
+    (kebab ident $name:ident) => {
+        $crate::s::to_separated(stringify!($name), '-')
+    };
+    ($mode:ident $head:literal $($rest:tt)*) => {
+        $crate::s!(@mode $mode $head $($rest)*)
+    };
+    ($mode:ident $head:ident $($rest:tt)*) => {
+        $crate::s!(@mode $mode $head $($rest)*)
+    };
+    ($mode:ident ($($head:tt)*) $($rest:tt)*) => {
+        $crate::s!(@mode $mode ($($head)*) $($rest)*)
+    };
     () => {
         String::new()
     };
@@ -114,6 +456,151 @@ mod tests {
     }
 
     #[test]
+    fn test_const_concat() {
+        const EMPTY: &str = s!(const);
+        static VERSION: &str = s!(const "v", 1, '.', 2, "-", true,);
+        let literal: &'static str = s!(const "static");
+        assert_eq!(EMPTY, "");
+        assert_eq!(VERSION, "v1.2-true");
+        assert_eq!(literal, "static");
+    }
+
+    #[test]
+    fn test_upper_lower() {
+        let name = "Alice";
+        assert_eq!(s!(upper "hi {}", name), "HI ALICE");
+        assert_eq!(s!(lower name), "alice");
+        assert_eq!(s!(upper 42), "42");
+    }
+
+    #[test]
+    fn test_title() {
+        assert_eq!(s!(title "hello wORLD, it's   me"), "Hello World, It's   Me");
+        assert_eq!(s!(title ""), "");
+    }
+
+    #[test]
+    fn test_snake_and_kebab() {
+        assert_eq!(s!(snake "{} {}", "Hello", "World"), "hello_world");
+        assert_eq!(
+            s!(snake "parseHTTPResponse2Json"),
+            "parse_http_response2_json"
+        );
+        assert_eq!(s!(kebab "  already_snake--case "), "already-snake-case");
+        assert_eq!(s!(snake ident MyStruct), "my_struct");
+        assert_eq!(s!(kebab ident IOError), "io-error");
+
+        let name = "FooBar";
+        assert_eq!(s!(snake name), "foo_bar");
+        assert_eq!(s!(kebab name), "foo-bar");
+        assert_eq!(s!(snake ident name), "name");
+    }
+
+    #[test]
+    fn test_trim() {
+        let padded = String::from("\t hello world \n");
+        assert_eq!(s!(trim padded), "hello world");
+        assert_eq!(s!(trim "  [{}]  ", " x "), "[ x ]");
+        assert_eq!(s!(trim "   "), "");
+    }
+
+    #[test]
+    fn test_squish() {
+        assert_eq!(s!(squish "  a   b  "), "a b");
+        assert_eq!(s!(squish "line\n\n  two\tthree"), "line two three");
+        assert_eq!(s!(squish "{} {}", " x ", " y "), "x y");
+        assert_eq!(s!(squish ""), "");
+    }
+
+    #[test]
+    fn test_repeat() {
+        let n = 4;
+        assert_eq!(s!(repeat 40; "-").len(), 40);
+        assert_eq!(s!(repeat n; "ab"), "abababab");
+        assert_eq!(s!(repeat (n / 2); "{}, ", n), "4, 4, ");
+        assert_eq!(s!(repeat 0; "x"), "");
+    }
+
+    #[test]
+    fn test_repeat_evaluates_format_once() {
+        let mut calls = 0;
+        let mut next = || {
+            calls += 1;
+            calls
+        };
+        assert_eq!(s!(repeat 3; "{}", next()), "111");
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_max() {
+        let text = "abcdefghij";
+        assert_eq!(s!(max 10; text), "abcdefghij");
+        assert_eq!(s!(max 9; text), "abcdefgh…");
+        assert_eq!(s!(max 5, ""; "{}!", text), "abcde");
+        assert_eq!(s!(max 0; text), "…");
+        assert_eq!(s!(max 2, "..."; text), "...");
+    }
+
+    #[test]
+    fn test_max_char_boundaries() {
+        assert_eq!(s!(max 3; "日本語テキスト"), "日本…");
+        assert_eq!(s!(max 3, "→"; "🦀🦀🦀🦀"), "🦀🦀→");
+        assert_eq!(s!(max 3; "🦀🦀🦀"), "🦀🦀🦀");
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(s!(escape html; "a & b"), "a &amp; b");
+        assert_eq!(
+            s!(escape html; "<a href=\"{}\">", "x\"y"),
+            "&lt;a href=&quot;x&quot;y&quot;&gt;"
+        );
+        assert_eq!(s!(escape html; "plain ünïcode"), "plain ünïcode");
+    }
+
+    #[test]
+    fn test_escape_json() {
+        assert_eq!(s!(escape json; "tab\there"), "tab\\there");
+        assert_eq!(s!(escape json; "back\\slash"), "back\\\\slash");
+        assert_eq!(s!(escape json; "\u{1}\u{7f}é"), "\\u0001\\u007fé");
+    }
+
+    #[test]
+    fn test_escape_shell() {
+        assert_eq!(
+            s!(escape shell; "simple-path/file.txt"),
+            "simple-path/file.txt"
+        );
+        assert_eq!(s!(escape shell; ""), "''");
+        assert_eq!(s!(escape shell; "a b"), "'a b'");
+        assert_eq!(s!(escape shell; "$(rm -rf /)"), "'$(rm -rf /)'");
+        assert_eq!(s!(escape shell; "it's"), "'it'\\''s'");
+    }
+
+    #[test]
+    fn test_chained_modes() {
+        assert_eq!(s!(upper squish "  a   b "), "A B");
+        assert_eq!(s!(trim repeat 2; " x "), "x  x");
+    }
+
+    #[test]
+    fn test_mode_names_still_work_as_values() {
+        let upper = "up";
+        let title = String::from("t");
+        let trim = 3;
+        assert_eq!(s!(upper), "up");
+        assert_eq!(s!(title.len()), "1");
+        assert_eq!(s!(trim + 1), "4");
+        assert_eq!(s!(i32::abs(-3)), "3");
+        let max = |a: i32, b: i32| a.max(b);
+        assert_eq!(s!(max(2, 5)), "5");
+        let n = 7u32;
+        assert_eq!(s!(n as u8), "7");
+    }
+
+    #[test]
+    #[allow(clippy::approx_constant)]
     fn test_float_formatting() {
         let pi = 3.14159;
         let formatted = s!("Pi: {:.2}", pi);
@@ -0,0 +1,74 @@
+//! String array utilities.
+//!
+//! The `sarr!` macro builds fixed-size arrays of strings from mixed inputs,
+//! for APIs that take `[String; N]` or `[&str; N]` rather than a `Vec`.
+
+/// A convenience macro for creating fixed-size arrays of strings.
+///
+/// This macro provides two ways to create an array:
+/// - A `[String; N]`, converting each element with `Into<String>`
+/// - A `[&str; N]`, borrowing each element with `AsRef<str>`, prefixed
+///   with `&str;`, so owned strings are passed without `&`
+///
+/// The length of the array is the number of elements, so no allocation
+/// beyond the strings themselves is needed.
+///
+/// # Examples
+///
+/// ## Owned strings
+/// ```
+/// # use smacro::sarr;
+/// let name = String::from("ada");
+/// let args: [String; 3] = sarr!["--user", name, 'x'];
+///
+/// assert_eq!(args, ["--user", "ada", "x"]);
+/// ```
+///
+/// ## Borrowed strings
+/// ```
+/// # use smacro::sarr;
+/// let owned = String::from("b");
+/// let letters = sarr![&str; "a", owned];
+///
+/// assert_eq!(letters, ["a", "b"]);
+/// assert_eq!(owned, "b");
+/// ```
+#[macro_export]
+macro_rules! sarr {
+    [&str; $($e:expr),* $(,)?] => {
+        [$(core::convert::AsRef::<str>::as_ref(&$e)),*]
+    };
+    [$($e:expr),* $(,)?] => {
+        [$(<String as core::convert::From<_>>::from($e)),*]
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn owned_array() {
+        let n = 3;
+        let arr = sarr!["a", String::from("b"), crate::s!("{}", n), 'd',];
+        assert_eq!(arr, ["a", "b", "3", "d"]);
+
+        let empty: [String; 0] = sarr![];
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn borrowed_array() {
+        let owned = String::from("world");
+        let boxed: Box<str> = "boxed".into();
+        let arr: [&str; 3] = sarr![&str; "hello", owned, boxed];
+        assert_eq!(arr.join(" "), "hello world boxed");
+        assert_eq!(owned.len(), 5);
+    }
+
+    #[test]
+    fn passes_to_array_apis() {
+        fn takes(names: [String; 2]) -> usize {
+            names.iter().map(String::len).sum()
+        }
+        assert_eq!(takes(sarr!["ab", "cde"]), 5);
+    }
+}
@@ -0,0 +1,364 @@
+//! Stepped numeric sequence utilities.
+//!
+//! The `seq!` macro produces evenly spaced numbers from a range and a step,
+//! covering descending and stepped sequences that std ranges make awkward,
+//! e.g. `seq!(10..=0; step -2)`.
+
+use core::ops::{Bound, RangeBounds};
+
+/// Numeric types that can be used as `seq!` elements and steps.
+///
+/// It is implemented for all primitive integer and floating point types.
+/// Integer sequences are produced by repeated checked addition, so they stop
+/// instead of overflowing. Float sequences are computed as `start + n * step`
+/// to avoid accumulating rounding errors.
+pub trait SeqStep: Copy + PartialOrd {
+    /// Returns `true` if the value is zero.
+    fn is_zero(self) -> bool;
+
+    /// Returns `true` if the value is strictly below zero.
+    fn is_negative(self) -> bool;
+
+    /// Returns the absolute value, or `None` if it cannot be represented,
+    /// as for the minimum value of a signed integer type.
+    fn magnitude(self) -> Option<Self>;
+
+    /// Returns the `n`-th element after `start`, given the `previous` one, or
+    /// `None` if it cannot be represented.
+    fn advance(start: Self, previous: Self, step: Self, n: usize, descending: bool) -> Option<Self>;
+}
+
+macro_rules! impl_seq_step_int {
+    ($($t:ty => $magnitude:expr),+) => {
+        $(
+            impl SeqStep for $t {
+                fn is_zero(self) -> bool {
+                    self == 0
+                }
+
+                #[allow(unused_comparisons)]
+                fn is_negative(self) -> bool {
+                    self < 0
+                }
+
+                fn magnitude(self) -> Option<Self> {
+                    $magnitude(self)
+                }
+
+                fn advance(_: Self, previous: Self, step: Self, _: usize, descending: bool) -> Option<Self> {
+                    if descending {
+                        previous.checked_sub(step)
+                    } else {
+                        previous.checked_add(step)
+                    }
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_seq_step_float {
+    ($($t:ty),+) => {
+        $(
+            impl SeqStep for $t {
+                fn is_zero(self) -> bool {
+                    self == 0.0
+                }
+
+                fn is_negative(self) -> bool {
+                    self < 0.0
+                }
+
+                fn magnitude(self) -> Option<Self> {
+                    Some(self.abs())
+                }
+
+                fn advance(start: Self, _: Self, step: Self, n: usize, descending: bool) -> Option<Self> {
+                    let delta = step * n as $t;
+                    Some(if descending { start - delta } else { start + delta })
+                }
+            }
+        )+
+    };
+}
+
+impl_seq_step_int!(
+    i8 => i8::checked_abs, i16 => i16::checked_abs, i32 => i32::checked_abs,
+    i64 => i64::checked_abs, i128 => i128::checked_abs, isize => isize::checked_abs,
+    u8 => Some, u16 => Some, u32 => Some, u64 => Some, u128 => Some, usize => Some
+);
+impl_seq_step_float!(f32, f64);
+
+/// An iterator over a stepped sequence, created by `seq!(iter ...)`.
+#[derive(Debug, Clone)]
+pub struct Seq<T> {
+    start: T,
+    previous: Option<T>,
+    step: T,
+    end: Bound<T>,
+    descending: bool,
+    index: usize,
+    done: bool,
+}
+
+impl<T: SeqStep> Iterator for Seq<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.done {
+            return None;
+        }
+
+        let value = match self.previous {
+            None if self.index == 0 => Some(self.start),
+            None => T::advance(self.start, self.start, self.step, self.index, self.descending),
+            Some(previous) => {
+                T::advance(self.start, previous, self.step, self.index, self.descending)
+            }
+        };
+
+        let in_bounds = |value: &T| match (&self.end, self.descending) {
+            (Bound::Included(end), false) => value <= end,
+            (Bound::Excluded(end), false) => value < end,
+            (Bound::Included(end), true) => value >= end,
+            (Bound::Excluded(end), true) => value > end,
+            (Bound::Unbounded, _) => true,
+        };
+
+        match value {
+            Some(value) if in_bounds(&value) => {
+                self.previous = Some(value);
+                self.index += 1;
+                Some(value)
+            }
+            _ => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn seq<T, R>(range: R, step: T, negate: bool) -> Seq<T>
+where
+    T: SeqStep,
+    R: RangeBounds<T>,
+{
+    assert!(!step.is_zero(), "seq! step must not be zero");
+
+    let (start, index) = match range.start_bound() {
+        Bound::Included(start) => (*start, 0),
+        Bound::Excluded(start) => (*start, 1),
+        Bound::Unbounded => panic!("seq! requires a range with a start bound"),
+    };
+
+    Seq {
+        start,
+        previous: None,
+        step: step
+            .magnitude()
+            .expect("seq! step must not be the minimum value of its type"),
+        end: range.end_bound().cloned(),
+        descending: step.is_negative() != negate,
+        index,
+        done: false,
+    }
+}
+
+/// A convenience macro for creating stepped numeric sequences.
+///
+/// This macro provides three ways to create a sequence:
+/// - Every value of a range, like `(a..b).collect()`
+/// - Every `n`-th value of a range (`step n`)
+/// - Counting down from the start to the end of a range (`step -n`)
+///
+/// By default a `Vec` is returned. Prefixing the range with `iter` returns a
+/// lazy [`Seq`] iterator instead, which also works with unbounded ranges.
+///
+/// # Examples
+///
+/// ## Stepping through a range
+/// ```
+/// # use smacro::seq;
+/// let fives = seq!(0..20; step 5);
+/// assert_eq!(fives, vec![0, 5, 10, 15]);
+///
+/// let all = seq!(1..=3);
+/// assert_eq!(all, vec![1, 2, 3]);
+/// ```
+///
+/// ## Descending sequences
+/// ```
+/// # use smacro::seq;
+/// let countdown = seq!(10..=0; step -2);
+/// assert_eq!(countdown, vec![10, 8, 6, 4, 2, 0]);
+///
+/// // Works with unsigned types too
+/// let bytes = seq!(255u8..200; step -25);
+/// assert_eq!(bytes, vec![255, 230, 205]);
+/// ```
+///
+/// ## Floating point steps
+/// ```
+/// # use smacro::seq;
+/// let ticks = seq!(0.0..=1.0; step 0.25);
+/// assert_eq!(ticks, vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+/// ```
+///
+/// ## Lazy iterators
+/// ```
+/// # use smacro::seq;
+/// let odd: Vec<u64> = seq!(iter 1..; step 2).take(4).collect();
+/// assert_eq!(odd, vec![1, 3, 5, 7]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the step is zero, if it is the minimum value of a signed
+/// integer type such as `i32::MIN`, whose magnitude does not fit in the type,
+/// or if the range has no start bound.
+///
+/// Integer sequences stop before overflowing, but collecting an unbounded
+/// float sequence into a `Vec` never terminates: use the `iter` form instead.
+#[macro_export]
+macro_rules! seq {
+    // Splits `start..end` / `start..=end` manually, so reversed ranges such as
+    // `10..=0` never exist as range expressions in the caller's code.
+    (@start $mode:ident [$($start:tt)+] ..= $($rest:tt)*) => {
+        $crate::seq!(@end $mode [$($start)+] Included [] $($rest)*)
+    };
+    (@start $mode:ident [$($start:tt)+] .. $($rest:tt)*) => {
+        $crate::seq!(@end $mode [$($start)+] Excluded [] $($rest)*)
+    };
+    (@start $mode:ident [$($range:tt)+] ; step - $($step:tt)+) => {
+        $crate::seq!(@build $mode ($($range)+) ($($step)+) true)
+    };
+    (@start $mode:ident [$($range:tt)+] ; step $($step:tt)+) => {
+        $crate::seq!(@build $mode ($($range)+) ($($step)+) false)
+    };
+    (@start $mode:ident [$($range:tt)+]) => {
+        $crate::seq!(@build $mode ($($range)+) (1 as _) false)
+    };
+    (@start $mode:ident [$($start:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::seq!(@start $mode [$($start)* $next] $($rest)*)
+    };
+    (@end $mode:ident [$($start:tt)+] $kind:ident [$($end:tt)*] ; step - $($step:tt)+) => {
+        $crate::seq!(@bounds $mode [$($start)+] $kind [$($end)*] ($($step)+) true)
+    };
+    (@end $mode:ident [$($start:tt)+] $kind:ident [$($end:tt)*] ; step $($step:tt)+) => {
+        $crate::seq!(@bounds $mode [$($start)+] $kind [$($end)*] ($($step)+) false)
+    };
+    (@end $mode:ident [$($start:tt)+] $kind:ident [$($end:tt)*]) => {
+        $crate::seq!(@bounds $mode [$($start)+] $kind [$($end)*] (1 as _) false)
+    };
+    (@end $mode:ident [$($start:tt)+] $kind:ident [$($end:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::seq!(@end $mode [$($start)+] $kind [$($end)* $next] $($rest)*)
+    };
+    (@bounds $mode:ident [$($start:tt)+] $kind:ident [] $step:tt $negate:tt) => {
+        $crate::seq!(@build $mode
+            (
+                core::ops::Bound::Included($($start)+),
+                core::ops::Bound::Unbounded,
+            )
+            $step $negate
+        )
+    };
+    (@bounds $mode:ident [$($start:tt)+] $kind:ident [$($end:tt)+] $step:tt $negate:tt) => {
+        $crate::seq!(@build $mode
+            (
+                core::ops::Bound::Included($($start)+),
+                core::ops::Bound::$kind($($end)+),
+            )
+            $step $negate
+        )
+    };
+    (@build iter $range:tt $step:tt $negate:tt) => {
+        $crate::seq::seq($range, $step, $negate)
+    };
+    (@build vec $range:tt $step:tt $negate:tt) => {
+        core::iter::Iterator::collect::<Vec<_>>($crate::seq::seq($range, $step, $negate))
+    };
+    (iter $($tokens:tt)+) => {
+        $crate::seq!(@start iter [] $($tokens)+)
+    };
+    ($($tokens:tt)+) => {
+        $crate::seq!(@start vec [] $($tokens)+)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn ascending_steps() {
+        assert_eq!(seq!(0..100; step 25), vec![0, 25, 50, 75]);
+        assert_eq!(seq!(0..=100; step 25), vec![0, 25, 50, 75, 100]);
+        assert_eq!(seq!(3..6), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn descending_steps() {
+        assert_eq!(seq!(10..=0; step -2), vec![10, 8, 6, 4, 2, 0]);
+        assert_eq!(seq!(10..0; step -5), vec![10, 5]);
+        assert_eq!(seq!(5u32..=0; step -1), vec![5, 4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn runtime_negative_step() {
+        let step = -3;
+        assert_eq!(seq!(9..=0; step step), vec![9, 6, 3, 0]);
+    }
+
+    #[test]
+    fn empty_sequences() {
+        assert!(seq!(5..5; step 1).is_empty());
+        assert!(seq!(0..10; step -1).is_empty());
+        assert!(seq!(10..0; step 1).is_empty());
+    }
+
+    #[test]
+    fn stops_before_overflow() {
+        assert_eq!(seq!(250u8..; step 3), vec![250, 253]);
+        assert_eq!(seq!(5u8..; step -2), vec![5, 3, 1]);
+    }
+
+    #[test]
+    fn float_steps() {
+        let v = seq!(0.0..1.0; step 0.1);
+        assert_eq!(v.len(), 10);
+        assert_eq!(v[3], 0.30000000000000004);
+        assert_eq!(seq!(1.0..=0.0; step -0.5), vec![1.0, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn range_values() {
+        let range = 0..=9;
+        assert_eq!(seq!(range; step 3), vec![0, 3, 6, 9]);
+        assert_eq!(seq!(2..=4), seq!(2..5));
+    }
+
+    #[test]
+    fn lazy_iterator() {
+        let mut it = seq!(iter 0..; step 10);
+        assert_eq!(it.next(), Some(0));
+        assert_eq!(it.nth(2), Some(30));
+    }
+
+    #[test]
+    #[should_panic(expected = "must not be zero")]
+    fn zero_step_panics() {
+        seq!(iter 0..10; step 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "must not be the minimum value")]
+    fn min_step_panics() {
+        seq!(iter 0..10; step i32::MIN);
+    }
+
+    #[test]
+    fn near_min_step() {
+        assert_eq!(seq!(0i8..=-128; step -127), vec![0, -127]);
+        assert_eq!(seq!(0i8..=-128; step i8::MIN + 1), vec![0, -127]);
+    }
+}
@@ -0,0 +1,147 @@
+//! Global state utilities.
+//!
+//! The `singleton!` macro declares a lazily initialized, thread-safe global
+//! behind a [`Singleton`], standardizing the `LazyLock<RwLock<T>>` pattern
+//! used for registries and other process-wide state.
+
+use std::sync::{LazyLock, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// A lazily initialized global value behind a read-write lock.
+///
+/// Declared with [`singleton!`](macro@crate::singleton). The value is created on
+/// first access. A panic while holding a write guard doesn't make the value
+/// unusable: the lock's poisoning is ignored, as the state is typically a
+/// registry that is still valid.
+pub struct Singleton<T> {
+    lock: LazyLock<RwLock<T>>,
+}
+
+impl<T> Singleton<T> {
+    #[doc(hidden)]
+    pub const fn new(lock: LazyLock<RwLock<T>>) -> Self {
+        Singleton { lock }
+    }
+
+    /// Locks the value for reading, blocking while a writer holds the lock.
+    pub fn read(&self) -> RwLockReadGuard<'_, T> {
+        self.lock.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the value for writing, blocking while anyone holds the lock.
+    pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+        self.lock.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A convenience macro for declaring global mutable state.
+///
+/// Each `static NAME: Type = init;` declares a [`Singleton`] whose value is
+/// computed by `init` on first access. The value is then reached through
+/// `NAME.read()` and `NAME.write()`, which return lock guards directly.
+/// Attributes and visibility are written as usual, and several statics can
+/// be declared at once. The `;` after the last one is optional, so a single
+/// static fits on one line as `singleton!(static NAME: Type = init)`.
+///
+/// # Examples
+///
+/// ## A global registry
+/// ```
+/// # use smacro::singleton;
+/// use std::collections::HashMap;
+///
+/// type Handler = fn(&str) -> String;
+///
+/// singleton! {
+///     /// Command handlers, registered by plugins at startup.
+///     pub static HANDLERS: HashMap<String, Handler> = HashMap::new();
+/// }
+///
+/// HANDLERS.write().insert("echo".to_string(), |arg| arg.to_string());
+///
+/// let handlers = HANDLERS.read();
+/// assert_eq!(handlers["echo"]("hi"), "hi");
+/// ```
+///
+/// ## Several statics
+/// ```
+/// # use smacro::singleton;
+/// singleton! {
+///     static COUNTER: u64 = 0;
+///     static NAMES: Vec<&'static str> = vec!["default"];
+/// }
+///
+/// *COUNTER.write() += 1;
+/// NAMES.write().push("extra");
+///
+/// assert_eq!(*COUNTER.read(), 1);
+/// assert_eq!(NAMES.read().len(), 2);
+/// ```
+#[macro_export]
+macro_rules! singleton {
+    ($($(#[$meta:meta])* $vis:vis static $name:ident : $ty:ty = $init:expr);+ $(;)?) => {
+        $(
+            $(#[$meta])*
+            $vis static $name: $crate::singleton::Singleton<$ty> = $crate::singleton::Singleton::new(
+                std::sync::LazyLock::new(|| std::sync::RwLock::new($init)),
+            );
+        )+
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::thread;
+
+    singleton! {
+        static REGISTRY: HashMap<String, u32> = HashMap::from([("init".to_string(), 0)]);
+        pub(crate) static TOTAL: u64 = 0;
+        static POISONED: Vec<u8> = Vec::new();
+    }
+
+    #[test]
+    fn initialized_on_first_access() {
+        REGISTRY.write().insert("a".to_string(), 1);
+        let registry = REGISTRY.read();
+        assert_eq!(registry["init"], 0);
+        assert_eq!(registry["a"], 1);
+    }
+
+    #[test]
+    fn shared_between_threads() {
+        let handles: Vec<_> = (0..8)
+            .map(|_| thread::spawn(|| *TOTAL.write() += 5))
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*TOTAL.read(), 40);
+    }
+
+    #[cfg(feature = "map")]
+    #[test]
+    fn single_static_without_semicolon() {
+        use crate::map;
+
+        type Handler = fn(&str) -> String;
+        singleton!(static REGISTRY: HashMap<String, Handler> = map![]);
+
+        REGISTRY
+            .write()
+            .insert("upper".to_string(), str::to_uppercase);
+        assert_eq!(REGISTRY.read()["upper"]("ok"), "OK");
+    }
+
+    #[test]
+    fn survives_poisoning() {
+        let _ = thread::spawn(|| {
+            let mut guard = POISONED.write();
+            guard.push(1);
+            panic!("poison the lock");
+        })
+        .join();
+
+        POISONED.write().push(2);
+        assert_eq!(*POISONED.read(), [1, 2]);
+    }
+}
@@ -0,0 +1,251 @@
+//! Deterministic collection output utilities.
+//!
+//! `HashMap` and `HashSet` iterate in a random order, so printing them
+//! gives different output on every run. The `sdebug_sorted!` macro formats
+//! them with their keys sorted and `to_sorted_vec!` dumps them into a sorted
+//! `Vec`, keeping assertions and snapshots stable.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Debug};
+
+/// Collections whose `Debug` output can be produced in sorted order.
+///
+/// Implemented for `HashMap` and `HashSet` with `Ord` keys, and for
+/// references to them. Used by [`sdebug_sorted!`](crate::sdebug_sorted).
+pub trait SortedDebug {
+    /// Writes the collection like its `Debug` impl, with keys sorted.
+    fn fmt_sorted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+impl<K: Ord + Debug, V: Debug, S> SortedDebug for HashMap<K, V, S> {
+    fn fmt_sorted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut entries: Vec<(&K, &V)> = self.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        f.debug_map().entries(entries).finish()
+    }
+}
+
+impl<T: Ord + Debug, S> SortedDebug for HashSet<T, S> {
+    fn fmt_sorted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut items: Vec<&T> = self.iter().collect();
+        items.sort();
+        f.debug_set().entries(items).finish()
+    }
+}
+
+impl<C: SortedDebug + ?Sized> SortedDebug for &C {
+    fn fmt_sorted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt_sorted(f)
+    }
+}
+
+/// Collections that can be converted into a `Vec` sorted by key.
+///
+/// Implemented for `HashMap` and `HashSet` with `Ord` keys, both by value
+/// and by reference. Used by [`to_sorted_vec!`](crate::to_sorted_vec).
+pub trait IntoSortedVec {
+    /// The element type of the resulting `Vec`.
+    type Item;
+
+    /// Returns the elements of the collection sorted by key.
+    fn into_sorted_vec(self) -> Vec<Self::Item>;
+}
+
+impl<K: Ord, V, S> IntoSortedVec for HashMap<K, V, S> {
+    type Item = (K, V);
+
+    fn into_sorted_vec(self) -> Vec<(K, V)> {
+        let mut entries: Vec<(K, V)> = self.into_iter().collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+}
+
+impl<'a, K: Ord, V, S> IntoSortedVec for &'a HashMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+
+    fn into_sorted_vec(self) -> Vec<(&'a K, &'a V)> {
+        let mut entries: Vec<(&K, &V)> = self.iter().collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        entries
+    }
+}
+
+impl<T: Ord, S> IntoSortedVec for HashSet<T, S> {
+    type Item = T;
+
+    fn into_sorted_vec(self) -> Vec<T> {
+        let mut items: Vec<T> = self.into_iter().collect();
+        items.sort_unstable();
+        items
+    }
+}
+
+impl<'a, T: Ord, S> IntoSortedVec for &'a HashSet<T, S> {
+    type Item = &'a T;
+
+    fn into_sorted_vec(self) -> Vec<&'a T> {
+        let mut items: Vec<&T> = self.iter().collect();
+        items.sort_unstable();
+        items
+    }
+}
+
+#[doc(hidden)]
+pub struct Sorted<'a, C: ?Sized>(pub &'a C);
+
+impl<C: SortedDebug + ?Sized> Debug for Sorted<'_, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_sorted(f)
+    }
+}
+
+/// A convenience macro for `Debug`-formatting a map or set with sorted keys.
+///
+/// This macro provides two ways to format a collection:
+/// - On one line, like `{:?}`
+/// - Pretty-printed over several lines, like `{:#?}`, with `pretty;`
+///
+/// It accepts a `HashMap` or `HashSet` whose keys implement `Ord`, or a
+/// reference to one, and returns a `String` with the entries in key order.
+/// Only the outer collection is sorted: unordered collections nested in the
+/// values still print in their own order.
+///
+/// # Examples
+///
+/// ## Stable assertions
+/// ```
+/// # use smacro::sdebug_sorted;
+/// use std::collections::{HashMap, HashSet};
+///
+/// let scores = HashMap::from([("carol", 7), ("alice", 9), ("bob", 3)]);
+/// let tags = HashSet::from(["rust", "async", "macros"]);
+///
+/// assert_eq!(sdebug_sorted!(scores), r#"{"alice": 9, "bob": 3, "carol": 7}"#);
+/// assert_eq!(sdebug_sorted!(&tags), r#"{"async", "macros", "rust"}"#);
+/// ```
+///
+/// ## Pretty-printing
+/// ```
+/// # use smacro::sdebug_sorted;
+/// use std::collections::HashMap;
+///
+/// let ports = HashMap::from([("https", 443), ("http", 80)]);
+///
+/// assert_eq!(sdebug_sorted!(pretty; ports), "{\n    \"http\": 80,\n    \"https\": 443,\n}");
+/// ```
+#[macro_export]
+macro_rules! sdebug_sorted {
+    (pretty; $collection:expr) => {
+        format!("{:#?}", $crate::sorted::Sorted(&$collection))
+    };
+    ($collection:expr) => {
+        format!("{:?}", $crate::sorted::Sorted(&$collection))
+    };
+}
+
+/// A convenience macro for converting a map or set into a sorted `Vec`.
+///
+/// This macro provides two ways to convert a collection:
+/// - By value, consuming it and returning owned elements
+/// - By reference, borrowing it and returning references
+///
+/// A `HashMap<K, V>` becomes a `Vec<(K, V)>` sorted by key and a
+/// `HashSet<T>` becomes a sorted `Vec<T>`. Keys must implement `Ord`.
+///
+/// # Examples
+///
+/// ## Consuming a map
+/// ```
+/// # use smacro::to_sorted_vec;
+/// use std::collections::HashMap;
+///
+/// let stock = HashMap::from([("pears", 4), ("apples", 7)]);
+///
+/// assert_eq!(to_sorted_vec!(stock), vec![("apples", 7), ("pears", 4)]);
+/// ```
+///
+/// ## Borrowing a set
+/// ```
+/// # use smacro::to_sorted_vec;
+/// use std::collections::HashSet;
+///
+/// let ids = HashSet::from([30, 10, 20]);
+///
+/// assert_eq!(to_sorted_vec!(&ids), vec![&10, &20, &30]);
+/// assert_eq!(ids.len(), 3);
+/// ```
+#[macro_export]
+macro_rules! to_sorted_vec {
+    ($collection:expr) => {
+        $crate::sorted::IntoSortedVec::into_sorted_vec($collection)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn sorted_map() {
+        let m: HashMap<u32, &str> = (0..20).rev().map(|i| (i, "x")).collect();
+        let expected = format!(
+            "{{{}}}",
+            (0..20)
+                .map(|i| format!("{}: \"x\"", i))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        assert_eq!(sdebug_sorted!(m), expected);
+    }
+
+    #[test]
+    fn sorted_set_and_references() {
+        let s: HashSet<char> = "hello world".chars().collect();
+        assert_eq!(
+            sdebug_sorted!(&s),
+            "{' ', 'd', 'e', 'h', 'l', 'o', 'r', 'w'}"
+        );
+        assert_eq!(sdebug_sorted!(&&s), sdebug_sorted!(s));
+    }
+
+    #[test]
+    fn empty_collections() {
+        assert_eq!(sdebug_sorted!(HashMap::<u8, u8>::new()), "{}");
+        assert_eq!(sdebug_sorted!(pretty; HashSet::<u8>::new()), "{}");
+    }
+
+    #[test]
+    fn pretty_nested_values() {
+        let m = HashMap::from([("b", vec![2]), ("a", vec![1])]);
+        assert_eq!(
+            sdebug_sorted!(pretty; m),
+            "{\n    \"a\": [\n        1,\n    ],\n    \"b\": [\n        2,\n    ],\n}"
+        );
+    }
+
+    #[test]
+    fn to_sorted_vec_map() {
+        let m: HashMap<String, usize> = ["c", "a", "b"]
+            .iter()
+            .map(|k| (k.to_string(), k.len()))
+            .collect();
+        assert_eq!(
+            to_sorted_vec!(&m),
+            vec![
+                (&"a".to_string(), &1),
+                (&"b".to_string(), &1),
+                (&"c".to_string(), &1)
+            ]
+        );
+        let keys: Vec<String> = to_sorted_vec!(m).into_iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn to_sorted_vec_set() {
+        let s: HashSet<i32> = (-5..5).rev().collect();
+        assert_eq!(to_sorted_vec!(s), (-5..5).collect::<Vec<_>>());
+        assert!(to_sorted_vec!(HashSet::<u8>::new()).is_empty());
+    }
+}
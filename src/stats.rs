@@ -0,0 +1,183 @@
+//! Aggregation utilities.
+//!
+//! The `sum_of!` and `avg!` macros add up or average a list of expressions or
+//! an iterator without hand-written folds. Averages are always computed as
+//! `f64`, so integer inputs never go through truncating integer division.
+
+/// Numeric types that can be losslessly-enough widened to `f64` for averaging.
+///
+/// This is what [`avg!`](crate::avg) uses to accept integers and floats
+/// alike. It is implemented for all primitive numeric types and for
+/// references to them, so iterators produced by `.iter()` work directly.
+pub trait ToF64 {
+    /// Converts the value to an `f64`, possibly rounding very large integers.
+    fn to_f64(&self) -> f64;
+}
+
+macro_rules! impl_to_f64 {
+    ($($t:ty),+) => {
+        $(
+            impl ToF64 for $t {
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )+
+    };
+}
+
+impl_to_f64!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+impl<T: ToF64 + ?Sized> ToF64 for &T {
+    fn to_f64(&self) -> f64 {
+        (**self).to_f64()
+    }
+}
+
+/// A convenience macro for adding up several values.
+///
+/// This macro provides two ways to compute a sum:
+/// - Over a list of expressions, expanding to `a + b + c`
+/// - Over an iterator (`in` prefix), using `Iterator::sum`
+///
+/// # Examples
+///
+/// ## Sum of a list of values
+/// ```
+/// # use smacro::sum_of;
+/// let total = sum_of!(1, 2, 3, 4);
+/// assert_eq!(total, 10);
+///
+/// let x = 2.5;
+/// assert_eq!(sum_of!(x, x * 2.0), 7.5);
+/// ```
+///
+/// ## Sum of an iterator
+/// ```
+/// # use smacro::sum_of;
+/// let sizes = vec![512u64, 1024, 256];
+///
+/// let total: u64 = sum_of!(in sizes.iter());
+/// assert_eq!(total, 1792);
+/// ```
+///
+/// # Type Inference
+///
+/// Like `Iterator::sum`, the iterator form may need a type annotation.
+#[macro_export]
+macro_rules! sum_of {
+    (in $iter:expr) => {
+        core::iter::Iterator::sum(core::iter::IntoIterator::into_iter($iter))
+    };
+    ($first:expr $(, $rest:expr)* $(,)?) => {
+        ($first) $(+ ($rest))*
+    };
+}
+
+/// A convenience macro for computing the arithmetic mean of several values.
+///
+/// The result is always an `f64`, so averaging integers never truncates.
+///
+/// This macro provides two ways to compute an average:
+/// - Over a list of two or more expressions, returning an `f64`
+/// - Over an iterator, returning `Option<f64>` (`None` when it is empty)
+///
+/// # Examples
+///
+/// ## Average of a list of values
+/// ```
+/// # use smacro::avg;
+/// let mean = avg!(1, 2);
+/// assert_eq!(mean, 1.5);
+/// ```
+///
+/// ## Average of an iterator
+/// ```
+/// # use smacro::avg;
+/// let samples = vec![3, 4, 4, 5];
+///
+/// assert_eq!(avg!(samples.iter()), Some(4.0));
+/// assert_eq!(avg!(in samples), Some(4.0));
+/// assert_eq!(avg!(Vec::<u32>::new()), None);
+/// ```
+///
+/// Two or more values return an `f64`, while a single argument must be an
+/// iterable and returns `Option<f64>`, which is `None` when it is empty. The
+/// `in` prefix is accepted as well, matching [`sum_of!`](crate::sum_of).
+///
+/// ## Average of a single value
+/// A lone scalar such as `avg!(3)` does not compile because it is read as
+/// an iterable; add a trailing comma to use the list form instead:
+/// ```
+/// # use smacro::avg;
+/// assert_eq!(avg!(3,), 3.0);
+/// ```
+#[macro_export]
+macro_rules! avg {
+    (in $iter:expr) => {
+        {
+            let (sum, count) = core::iter::Iterator::fold(
+                core::iter::IntoIterator::into_iter($iter),
+                (0.0f64, 0usize),
+                |(sum, count), item| (sum + $crate::stats::ToF64::to_f64(&item), count + 1),
+            );
+            if count == 0 { None } else { Some(sum / count as f64) }
+        }
+    };
+    ($iter:expr) => {
+        $crate::avg!(in $iter)
+    };
+    ($($e:expr),+ $(,)?) => {
+        $crate::avg!(in [$($crate::stats::ToF64::to_f64(&$e)),+]).unwrap()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn sum_of_values() {
+        assert_eq!(sum_of!(1, 2, 3), 6);
+        assert_eq!(sum_of!(7), 7);
+        assert_eq!(sum_of!(1.5, 2.5,), 4.0);
+    }
+
+    #[test]
+    fn sum_of_respects_precedence() {
+        let a = 3;
+        assert_eq!(sum_of!(a, a * 2, a - 1) * 2, 22);
+    }
+
+    #[test]
+    fn sum_of_iterator() {
+        let v = vec![1u32, 2, 3];
+        let by_ref: u32 = sum_of!(in v.iter());
+        let owned: u32 = sum_of!(in v);
+        assert_eq!(by_ref, 6);
+        assert_eq!(owned, 6);
+    }
+
+    #[test]
+    fn avg_of_integers_does_not_truncate() {
+        assert_eq!(avg!(1, 2), 1.5);
+        assert_eq!(avg!(1u8, 2u8, 4u8), 7.0 / 3.0);
+    }
+
+    #[test]
+    fn avg_of_single_value_with_trailing_comma() {
+        let mean: f64 = avg!(3,);
+        assert_eq!(mean, 3.0);
+    }
+
+    #[test]
+    fn avg_of_iterator() {
+        let v = vec![2.0, 4.0, 9.0];
+        assert_eq!(avg!(v.iter()), Some(5.0));
+        assert_eq!(avg!(in v), Some(5.0));
+    }
+
+    #[test]
+    fn avg_of_empty_iterator() {
+        assert_eq!(avg!(Vec::<i64>::new()), None);
+        assert_eq!(avg!(in core::iter::empty::<f32>()), None);
+    }
+}
@@ -0,0 +1,132 @@
+//! Text table utilities.
+//!
+//! The `table_s!` macro formats a header and rows of values into an aligned
+//! plain text table, for terminal output without a table rendering
+//! dependency.
+
+use std::fmt::Display;
+
+#[doc(hidden)]
+pub fn table(header: &[&dyn Display], rows: &[&[&dyn Display]], max: Option<usize>) -> String {
+    let cell = |value: &dyn Display| match max {
+        Some(max) => crate::s::truncate(value.to_string(), max, "…"),
+        None => value.to_string(),
+    };
+    let mut lines: Vec<Vec<String>> = Vec::with_capacity(rows.len() + 2);
+    if !header.is_empty() {
+        lines.push(header.iter().map(|value| cell(*value)).collect());
+    }
+    lines.extend(
+        rows.iter()
+            .map(|row| row.iter().map(|value| cell(*value)).collect()),
+    );
+
+    let columns = lines.iter().map(Vec::len).max().unwrap_or(0);
+    let mut widths = vec![0; columns];
+    for line in &lines {
+        for (width, cell) in widths.iter_mut().zip(line) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    if !header.is_empty() {
+        let underline = widths[..header.len()]
+            .iter()
+            .map(|width| "-".repeat(*width));
+        lines.insert(1, underline.collect());
+    }
+
+    let mut out = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        let start = out.len();
+        for (column, cell) in line.iter().enumerate() {
+            if column > 0 {
+                out.push_str("  ");
+            }
+            out.push_str(cell);
+            let padding = widths[column] - cell.chars().count();
+            out.extend(std::iter::repeat_n(' ', padding));
+        }
+        let end = out[start..].trim_end().len();
+        out.truncate(start + end);
+    }
+    out
+}
+
+/// A convenience macro for formatting rows of values into an aligned text table.
+///
+/// An optional header in brackets is followed by `;` and the rows, each
+/// written as a tuple of values formatted with `Display`. Columns are
+/// left-aligned and separated by two spaces, and the header is underlined
+/// with dashes. Rows may have different lengths, and the table is returned
+/// as a `String` without a trailing newline or trailing spaces.
+///
+/// With the `max n;` prefix, cells longer than `n` characters are truncated
+/// with an ellipsis, as with the `max` mode of `s!`.
+///
+/// # Examples
+///
+/// ## Header and rows
+/// ```
+/// # use smacro::table_s;
+/// let table = table_s![
+///     ["Name", "Age", "Role"];
+///     ("Ada", 36, "engineer"),
+///     ("Alan", 41, "mathematician"),
+/// ];
+///
+/// assert_eq!(table, "\
+/// Name  Age  Role
+/// ----  ---  -------------
+/// Ada   36   engineer
+/// Alan  41   mathematician");
+/// ```
+///
+/// ## Truncated columns
+/// ```
+/// # use smacro::table_s;
+/// let table = table_s![max 6; ("id", "description"), (1, "short")];
+///
+/// assert_eq!(table, "\
+/// id  descr…
+/// 1   short");
+/// ```
+#[macro_export]
+macro_rules! table_s {
+    (@table $max:expr; [$($header:expr),* $(,)?]; $(($($cell:expr),* $(,)?)),* $(,)?) => {
+        $crate::table::table(&[$(&$header),*], &[$(&[$(&$cell),*]),*], $max)
+    };
+    (@table $max:expr; $(($($cell:expr),* $(,)?)),* $(,)?) => {
+        $crate::table::table(&[], &[$(&[$(&$cell),*]),*], $max)
+    };
+    (max $max:expr; $($rest:tt)*) => {
+        $crate::table_s!(@table Some($max); $($rest)*)
+    };
+    ($($rest:tt)*) => {
+        $crate::table_s!(@table None; $($rest)*)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn ragged_rows_and_unicode() {
+        let table = table_s![["k", "v"]; ("é", 1, "extra"), ("longer",)];
+        assert_eq!(table, "k       v\n------  -\né       1  extra\nlonger");
+    }
+
+    #[test]
+    fn empty_tables() {
+        assert_eq!(table_s![], "");
+        assert_eq!(table_s![["a", "b"];], "a  b\n-  -");
+    }
+
+    #[test]
+    fn truncation_applies_to_header() {
+        let name = String::from("a very long name");
+        let table = table_s![max 5; ["heading"]; (name,), ("ok",)];
+        assert_eq!(table, "head…\n-----\na ve…\nok");
+    }
+}
@@ -0,0 +1,148 @@
+//! Parameterized test utilities.
+//!
+//! The `table_test!` macro expands a table of named cases into one `#[test]`
+//! function per case, so a failure reports the name of the case that broke
+//! instead of a single test looping over every input.
+
+/// A convenience macro for generating one test per row of a table.
+///
+/// The macro takes a module name, a check function and a table of cases.
+/// It expands to a module containing a `#[test]` function per case, named
+/// after the case, which runs the check function's body with the case's
+/// arguments bound to its parameters.
+///
+/// This macro provides two ways to declare the check function:
+/// - With typed parameters, generating a real function called by each test
+/// - With untyped parameters, binding the arguments with `let` in each test
+///
+/// Each case can be preceded by attributes such as `#[ignore]` or
+/// `#[should_panic]`. Items from the enclosing module are imported into the
+/// generated module.
+///
+/// # Examples
+///
+/// ## Untyped parameters
+/// ```
+/// # use smacro::table_test;
+/// fn count_words(s: &str) -> usize {
+///     s.split_whitespace().count()
+/// }
+///
+/// table_test!(word_counts: fn check(input, expected) {
+///     assert_eq!(count_words(input), expected, "input: {:?}", input);
+/// } {
+///     empty: ("", 0),
+///     simple: ("a", 1),
+///     spaces: ("  a  b ", 2),
+/// });
+/// ```
+///
+/// ## Typed parameters and attributes
+/// ```
+/// # use smacro::table_test;
+/// table_test!(parsing: fn check(input: &str, expected: Option<u8>) {
+///     assert_eq!(input.parse().ok(), expected);
+/// } {
+///     number: ("42", Some(42)),
+///     overflow: ("256", None),
+///     #[should_panic]
+///     wrong_expectation: ("1", None),
+/// });
+/// ```
+///
+/// Running `cargo test` then reports `parsing::number`, `parsing::overflow`
+/// and `parsing::wrong_expectation` as separate tests.
+#[macro_export]
+macro_rules! table_test {
+    (@untyped $(#[$meta:meta])* $case:ident ($($param:ident),*) $body:block ($($arg:expr),* $(,)?)) => {
+        #[test]
+        $(#[$meta])*
+        pub fn $case() {
+            #[allow(unused_parens)]
+            let ($($param),*) = ($($arg),*);
+            $body
+        }
+    };
+    (
+        $name:ident : fn $check:ident ($($param:ident : $ty:ty),* $(,)?) $body:block
+        { $($(#[$meta:meta])* $case:ident : ($($arg:expr),* $(,)?)),* $(,)? }
+    ) => {
+        pub mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            fn $check($($param: $ty),*) $body
+
+            $(
+                #[test]
+                $(#[$meta])*
+                pub fn $case() {
+                    $check($($arg),*);
+                }
+            )*
+        }
+    };
+    (
+        $name:ident : fn $check:ident $params:tt $body:block
+        { $($(#[$meta:meta])* $case:ident : $args:tt),* $(,)? }
+    ) => {
+        pub mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            $(
+                $crate::table_test!(@untyped $(#[$meta])* $case $params $body $args);
+            )*
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    fn double(x: i32) -> i32 {
+        x * 2
+    }
+
+    thread_local! {
+        static RUNS: Cell<u32> = const { Cell::new(0) };
+    }
+
+    table_test!(untyped: fn check(input, expected) {
+        assert_eq!(double(input), expected);
+    } {
+        zero: (0, 0),
+        positive: (21, 42),
+        negative: (-3, -6),
+    });
+
+    table_test!(typed: fn check(input: &str, expected: usize,) {
+        assert_eq!(input.len(), expected);
+    } {
+        empty: ("", 0),
+        ascii: ("abc", 3),
+        multibyte: ("é", 2),
+    });
+
+    table_test!(single_param: fn check(value) {
+        assert!(value % 2 == 0);
+        RUNS.with(|runs| runs.set(runs.get() + 1));
+    } {
+        two: (2),
+        four: (4,),
+        #[should_panic]
+        three: (3),
+        #[ignore]
+        ignored: (5),
+    });
+
+    #[test]
+    fn generated_functions_are_callable() {
+        untyped::positive();
+        typed::multibyte();
+        single_param::two();
+        single_param::four();
+        assert_eq!(RUNS.with(Cell::get), 2);
+    }
+}
@@ -0,0 +1,109 @@
+//! Inspect-and-pass-through utilities.
+//!
+//! The `tap!` macro runs a side effect on a value and then returns the value
+//! itself, so logging or assertions can be inserted into an expression
+//! without restructuring it.
+
+#[doc(hidden)]
+pub fn tap<T, R>(value: T, f: impl FnOnce(&T) -> R) -> T {
+    f(&value);
+    value
+}
+
+#[doc(hidden)]
+pub fn tap_mut<T, R>(mut value: T, f: impl FnOnce(&mut T) -> R) -> T {
+    f(&mut value);
+    value
+}
+
+/// A convenience macro for inspecting a value without consuming it.
+///
+/// This macro provides two ways to tap into a value:
+/// - Pass a shared reference to a closure, then return the value
+/// - Pass a mutable reference to a closure (`mut` prefix), then return the value
+///
+/// # Examples
+///
+/// ## Observing a value
+/// ```
+/// # use smacro::tap;
+/// let mut seen = Vec::new();
+///
+/// let total = tap!(2 + 3, |v| seen.push(*v)) * 10;
+///
+/// assert_eq!(total, 50);
+/// assert_eq!(seen, vec![5]);
+/// ```
+///
+/// ## Logging inside a chain
+/// ```
+/// # use smacro::tap;
+/// let words: Vec<&str> = tap!("a b c".split(' ').collect::<Vec<_>>(), |v| {
+///     println!("split into {} words: {:?}", v.len(), v)
+/// });
+///
+/// assert_eq!(words, vec!["a", "b", "c"]);
+/// ```
+///
+/// ## Mutating in place
+/// ```
+/// # use smacro::tap;
+/// let sorted = tap!(mut vec![3, 1, 2], |v| v.sort());
+///
+/// assert_eq!(sorted, vec![1, 2, 3]);
+/// ```
+///
+/// # Evaluation
+///
+/// The expression is evaluated once. Whatever the closure returns is discarded.
+#[macro_export]
+macro_rules! tap {
+    (mut $value:expr, $f:expr $(,)?) => {
+        $crate::tap::tap_mut($value, $f)
+    };
+    ($value:expr, $f:expr $(,)?) => {
+        $crate::tap::tap($value, $f)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn tap_returns_value() {
+        let v = tap!(41 + 1, |_| ());
+        assert_eq!(v, 42);
+    }
+
+    #[test]
+    fn tap_runs_side_effect_once() {
+        let mut calls = 0;
+        let v = tap!(String::from("hi"), |s| {
+            calls += 1;
+            s.len()
+        });
+        assert_eq!(v, "hi");
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn tap_does_not_move_non_copy_values() {
+        let mut lengths = vec![];
+        let v = tap!(vec![1, 2, 3], |v| lengths.push(v.len()));
+        assert_eq!(v, vec![1, 2, 3]);
+        assert_eq!(lengths, vec![3]);
+    }
+
+    #[test]
+    fn tap_mut_modifies_value() {
+        let s = tap!(mut String::from("log"), |s| s.push_str(".txt"));
+        assert_eq!(s, "log.txt");
+    }
+
+    #[test]
+    fn tap_in_pipe() {
+        let mut seen = 0;
+        let v = crate::pipe!(3 => |x| x * 2 => |x| tap!(x, |v| seen = *v) => |x| x + 1);
+        assert_eq!(v, 7);
+        assert_eq!(seen, 6);
+    }
+}
@@ -0,0 +1,208 @@
+//! Text layout utilities.
+//!
+//! The `wrap_text!` macro word-wraps `s!` formatted text to a width, for
+//! help text and reports rendered in a terminal, and `pad_left!`,
+//! `pad_right!` and `center!` align values to widths only known at runtime.
+
+use std::fmt::Display;
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct WrapOptions {
+    pub indent: usize,
+}
+
+#[doc(hidden)]
+pub fn wrap(text: &str, width: usize, options: WrapOptions) -> String {
+    let indent = " ".repeat(options.indent);
+    let mut out = String::with_capacity(text.len());
+    let mut column = 0;
+    for (i, paragraph) in text.lines().enumerate() {
+        if i > 0 {
+            out.push('\n');
+            column = 0;
+        }
+        for word in paragraph.split_whitespace() {
+            let len = word.chars().count();
+            if column > 0 && column + 1 + len > width {
+                out.push('\n');
+                column = 0;
+            }
+            if column == 0 {
+                if !out.is_empty() && !paragraph.is_empty() {
+                    out.push_str(&indent);
+                    column = options.indent;
+                }
+            } else {
+                out.push(' ');
+                column += 1;
+            }
+            out.push_str(word);
+            column += len;
+        }
+    }
+    out
+}
+
+#[doc(hidden)]
+pub fn pad(value: impl Display, width: usize, fill: char, left: usize, right: usize) -> String {
+    let value = value.to_string();
+    let missing = width.saturating_sub(value.chars().count());
+    let before = missing * left / (left + right);
+    let mut out = String::with_capacity(value.len() + missing * fill.len_utf8());
+    out.extend(std::iter::repeat_n(fill, before));
+    out.push_str(&value);
+    out.extend(std::iter::repeat_n(fill, missing - before));
+    out
+}
+
+/// A convenience macro for word-wrapping formatted text to a width.
+///
+/// The width comes first, followed by `;` and the text, which accepts
+/// anything `s!` does. Lines are broken at whitespace so that they fit in
+/// `width` characters, runs of whitespace are collapsed, and words longer
+/// than the width are kept whole on their own line. Line breaks in the text
+/// are kept, so paragraphs stay separated.
+///
+/// Options can follow the width, separated by commas:
+/// - `indent = n` indents every line after the first by `n` spaces, a hang
+///   indent for lists and option descriptions
+///
+/// # Examples
+///
+/// ## Wrapping a paragraph
+/// ```
+/// # use smacro::wrap_text;
+/// let tool = "smacro";
+/// let text = wrap_text!(24; "{} is a collection of convenient Rust macros.", tool);
+///
+/// assert_eq!(text, "smacro is a collection\nof convenient Rust\nmacros.");
+/// ```
+///
+/// ## Hang indent
+/// ```
+/// # use smacro::wrap_text;
+/// let help = wrap_text!(30, indent = 8; "--force overwrite existing files without asking");
+///
+/// assert_eq!(help, "--force overwrite existing\n        files without asking");
+/// ```
+#[macro_export]
+macro_rules! wrap_text {
+    ($width:expr $(, $option:ident = $value:expr)* ; $($text:tt)+) => {{
+        #[allow(unused_mut)]
+        let mut options = $crate::text::WrapOptions::default();
+        $(options.$option = $value;)*
+        $crate::text::wrap(&$crate::s!($($text)+), $width, options)
+    }};
+}
+
+/// A convenience macro for right-aligning a value to a runtime width.
+///
+/// The value is formatted with `Display` and padded on the left with the
+/// fill character, a space by default, until it is `width` characters
+/// wide. Longer values are returned unchanged. Unlike `format!("{:>5}")`,
+/// both the width and the fill character can be runtime values.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::pad_left;
+/// let width = 6;
+///
+/// assert_eq!(pad_left!(42, width), "    42");
+/// assert_eq!(pad_left!(42, width, '0'), "000042");
+/// assert_eq!(pad_left!("too long", width), "too long");
+/// ```
+#[macro_export]
+macro_rules! pad_left {
+    ($value:expr, $width:expr $(,)?) => {
+        $crate::pad_left!($value, $width, ' ')
+    };
+    ($value:expr, $width:expr, $fill:expr $(,)?) => {
+        $crate::text::pad($value, $width, $fill, 1, 0)
+    };
+}
+
+/// A convenience macro for left-aligning a value to a runtime width.
+///
+/// The value is formatted with `Display` and padded on the right with the
+/// fill character, a space by default, until it is `width` characters
+/// wide. Longer values are returned unchanged.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::pad_right;
+/// let width = 8;
+///
+/// assert_eq!(pad_right!("rent", width), "rent    ");
+/// assert_eq!(pad_right!("rent", width, '.'), "rent....");
+/// ```
+#[macro_export]
+macro_rules! pad_right {
+    ($value:expr, $width:expr $(,)?) => {
+        $crate::pad_right!($value, $width, ' ')
+    };
+    ($value:expr, $width:expr, $fill:expr $(,)?) => {
+        $crate::text::pad($value, $width, $fill, 0, 1)
+    };
+}
+
+/// A convenience macro for centering a value in a runtime width.
+///
+/// The value is formatted with `Display` and padded on both sides with the
+/// fill character, a space by default, until it is `width` characters
+/// wide. When the padding cannot be split evenly, the extra character goes
+/// on the right, as with `format!("{:^5}")`. Longer values are returned
+/// unchanged.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::center;
+/// let width = 11;
+///
+/// assert_eq!(center!("total", width), "   total   ");
+/// assert_eq!(center!(" total ", width + 1, '='), "== total ===");
+/// ```
+#[macro_export]
+macro_rules! center {
+    ($value:expr, $width:expr $(,)?) => {
+        $crate::center!($value, $width, ' ')
+    };
+    ($value:expr, $width:expr, $fill:expr $(,)?) => {
+        $crate::text::pad($value, $width, $fill, 1, 1)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn keeps_paragraphs_and_long_words() {
+        let text = wrap_text!(10; "one two  three\n\nsupercalifragilistic word");
+        assert_eq!(text, "one two\nthree\n\nsupercalifragilistic\nword");
+    }
+
+    #[test]
+    fn hang_indent() {
+        let text = wrap_text!(12, indent = 2; "{} b c d e f g h i", "a");
+        assert_eq!(text, "a b c d e f\n  g h i");
+        assert_eq!(wrap_text!(5, indent = 2; ""), "");
+    }
+
+    #[test]
+    fn padding_matches_format() {
+        for width in 0..8 {
+            assert_eq!(pad_left!("abc", width), format!("{:>width$}", "abc"));
+            assert_eq!(pad_right!("abc", width), format!("{:<width$}", "abc"));
+            assert_eq!(center!("abc", width), format!("{:^width$}", "abc"));
+        }
+    }
+
+    #[test]
+    fn fill_chars() {
+        assert_eq!(pad_left!(-7, 4, '0'), "00-7");
+        assert_eq!(pad_right!("é", 3, '·'), "é··");
+        assert_eq!(center!('x', 4, '-'), "-x--");
+    }
+}
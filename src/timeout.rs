@@ -0,0 +1,153 @@
+//! Async sleep and timeout utilities.
+//!
+//! Requires the `tokio` or the `async-std` feature to be enabled.
+//!
+//! The `sleep_ms!` and `timeout!` macros give the same syntax regardless of
+//! the async runtime, which is selected by feature. When both features are
+//! enabled, `tokio` is used.
+
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+
+/// The error returned by [`timeout!`](macro@crate::timeout) when the deadline elapses.
+///
+/// This type is shared by all runtimes, so code using `timeout!` doesn't
+/// depend on which backend feature is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed(());
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("deadline has elapsed")
+    }
+}
+
+impl std::error::Error for Elapsed {}
+
+#[cfg(feature = "tokio")]
+#[doc(hidden)]
+pub async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await
+}
+
+#[cfg(feature = "tokio")]
+#[doc(hidden)]
+pub async fn timeout<F: Future>(duration: Duration, future: F) -> Result<F::Output, Elapsed> {
+    tokio::time::timeout(duration, future)
+        .await
+        .map_err(|_| Elapsed(()))
+}
+
+#[cfg(all(feature = "async-std", not(feature = "tokio")))]
+#[doc(hidden)]
+pub async fn sleep(duration: Duration) {
+    async_std::task::sleep(duration).await
+}
+
+#[cfg(all(feature = "async-std", not(feature = "tokio")))]
+#[doc(hidden)]
+pub async fn timeout<F: Future>(duration: Duration, future: F) -> Result<F::Output, Elapsed> {
+    async_std::future::timeout(duration, future)
+        .await
+        .map_err(|_| Elapsed(()))
+}
+
+/// A convenience macro for sleeping a number of milliseconds in async code.
+///
+/// Requires the `tokio` or the `async-std` feature to be enabled.
+///
+/// `sleep_ms!(n)` expands to the runtime's sleep function, awaited, and must
+/// be used inside an `async` context.
+///
+/// # Examples
+///
+/// ## Pausing between requests
+/// ```
+/// # use smacro::sleep_ms;
+/// use std::time::{Duration, Instant};
+///
+/// # tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap().block_on(async {
+/// let start = Instant::now();
+/// sleep_ms!(20);
+///
+/// assert!(start.elapsed() >= Duration::from_millis(20));
+/// # });
+/// ```
+#[macro_export]
+macro_rules! sleep_ms {
+    ($ms:expr $(,)?) => {
+        $crate::timeout::sleep(std::time::Duration::from_millis($ms)).await
+    };
+}
+
+/// A convenience macro for awaiting a future with a deadline.
+///
+/// Requires the `tokio` or the `async-std` feature to be enabled.
+///
+/// The macro evaluates to `Ok` with the future's output if it completes in
+/// time, or to `Err(Elapsed)` otherwise, in which case the future is
+/// dropped. It must be used inside an `async` context.
+///
+/// # Examples
+///
+/// ## Bounding a slow operation
+/// ```
+/// # use smacro::{sleep_ms, timeout};
+/// use std::time::Duration;
+///
+/// async fn fetch(delay: u64) -> &'static str {
+///     sleep_ms!(delay);
+///     "done"
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap().block_on(async {
+/// let fast = timeout!(Duration::from_millis(200), fetch(1));
+/// let slow = timeout!(Duration::from_millis(10), fetch(1_000));
+///
+/// assert_eq!(fast, Ok("done"));
+/// assert_eq!(slow.unwrap_err().to_string(), "deadline has elapsed");
+/// # });
+/// ```
+#[macro_export]
+macro_rules! timeout {
+    ($duration:expr, $future:expr $(,)?) => {
+        $crate::timeout::timeout($duration, $future).await
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    #[tokio::test]
+    async fn sleep_ms_waits() {
+        let start = Instant::now();
+        sleep_ms!(15);
+        assert!(start.elapsed() >= Duration::from_millis(15));
+    }
+
+    #[tokio::test]
+    async fn timeout_completes_in_time() {
+        let r = timeout!(Duration::from_secs(1), async { 7 });
+        assert_eq!(r, Ok(7));
+    }
+
+    #[tokio::test]
+    async fn timeout_elapses() {
+        let r = timeout!(Duration::from_millis(5), async {
+            sleep_ms!(1_000);
+        });
+        let err = r.unwrap_err();
+        assert_eq!(err.to_string(), "deadline has elapsed");
+    }
+
+    #[tokio::test]
+    async fn timeout_with_question_mark() {
+        async fn run() -> Result<u8, Box<dyn std::error::Error>> {
+            let v = timeout!(Duration::from_millis(5), std::future::pending::<u8>())?;
+            Ok(v)
+        }
+        assert!(run().await.is_err());
+    }
+}
@@ -0,0 +1,161 @@
+//! Timestamp formatting utilities.
+//!
+//! Requires the `chrono` feature to be enabled.
+//!
+//! The `timestamp_s!` macro formats the current time, or a given instant,
+//! as an RFC 3339 string or with a `strftime` style format, so log prefixes
+//! and file names get consistent timestamps.
+
+use std::fmt::{Display, Write};
+use std::time::SystemTime;
+
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+
+/// Instants that can be formatted by [`timestamp_s!`](crate::timestamp_s).
+///
+/// Requires the `chrono` feature to be enabled.
+///
+/// Implemented for `chrono::DateTime` in any time zone, `SystemTime`, which
+/// is formatted in UTC, and references to them.
+pub trait Timestamp {
+    /// Formats the instant as an RFC 3339 string with second precision.
+    fn rfc3339(&self) -> String;
+
+    /// Formats the instant with a `strftime` style format string.
+    fn format_with(&self, format: &str) -> String;
+}
+
+impl<Tz: TimeZone> Timestamp for DateTime<Tz>
+where
+    Tz::Offset: Display,
+{
+    fn rfc3339(&self) -> String {
+        self.to_rfc3339_opts(SecondsFormat::Secs, true)
+    }
+
+    fn format_with(&self, format: &str) -> String {
+        let mut out = String::new();
+        write!(out, "{}", self.format(format))
+            .unwrap_or_else(|_| panic!("invalid timestamp format {:?}", format));
+        out
+    }
+}
+
+impl Timestamp for SystemTime {
+    fn rfc3339(&self) -> String {
+        DateTime::<Utc>::from(*self).rfc3339()
+    }
+
+    fn format_with(&self, format: &str) -> String {
+        DateTime::<Utc>::from(*self).format_with(format)
+    }
+}
+
+impl<T: Timestamp + ?Sized> Timestamp for &T {
+    fn rfc3339(&self) -> String {
+        T::rfc3339(self)
+    }
+
+    fn format_with(&self, format: &str) -> String {
+        T::format_with(self, format)
+    }
+}
+
+#[doc(hidden)]
+pub fn now() -> DateTime<Utc> {
+    Utc::now()
+}
+
+/// A convenience macro for formatting timestamps.
+///
+/// Requires the `chrono` feature to be enabled.
+///
+/// This macro provides four ways to format a timestamp:
+/// - The current UTC time as RFC 3339, such as `2024-05-01T09:30:00Z`
+/// - The current UTC time with a `strftime` style format string
+/// - A given instant as RFC 3339
+/// - A given instant with a format string, written before the instant
+///
+/// Instants can be any [`Timestamp`], such as a `chrono::DateTime` in any
+/// time zone or a `SystemTime`. RFC 3339 output has second precision and
+/// uses `Z` for UTC.
+///
+/// # Panics
+///
+/// Panics if the format string contains an invalid specifier.
+///
+/// # Examples
+///
+/// ## Current time
+/// ```
+/// # use smacro::timestamp_s;
+/// let now = timestamp_s!();
+/// assert!(now.ends_with('Z'));
+///
+/// let log_file = format!("app-{}.log", timestamp_s!("%Y-%m-%d"));
+/// assert_eq!(log_file.len(), "app-2024-05-01.log".len());
+/// ```
+///
+/// ## Given instant
+/// ```
+/// # use smacro::timestamp_s;
+/// use chrono::{FixedOffset, TimeZone};
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let release = UNIX_EPOCH + Duration::from_secs(1_714_555_800);
+/// assert_eq!(timestamp_s!(release), "2024-05-01T09:30:00Z");
+///
+/// let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+/// let meeting = offset.with_ymd_and_hms(2024, 5, 1, 14, 0, 0).unwrap();
+/// assert_eq!(timestamp_s!(meeting), "2024-05-01T14:00:00+02:00");
+/// assert_eq!(timestamp_s!("%d/%m %H:%M", meeting), "01/05 14:00");
+/// ```
+#[macro_export]
+macro_rules! timestamp_s {
+    () => {
+        $crate::timestamp::Timestamp::rfc3339(&$crate::timestamp::now())
+    };
+    ($format:literal $(,)?) => {
+        $crate::timestamp::Timestamp::format_with(&$crate::timestamp::now(), $format)
+    };
+    ($format:expr, $instant:expr $(,)?) => {
+        $crate::timestamp::Timestamp::format_with(&$instant, $format)
+    };
+    ($instant:expr $(,)?) => {
+        $crate::timestamp::Timestamp::rfc3339(&$instant)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeZone, Utc};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn instants() {
+        let instant = Utc.with_ymd_and_hms(2000, 2, 29, 23, 59, 59).unwrap();
+        assert_eq!(timestamp_s!(instant), "2000-02-29T23:59:59Z");
+        assert_eq!(timestamp_s!(&instant), "2000-02-29T23:59:59Z");
+        assert_eq!(
+            timestamp_s!(UNIX_EPOCH + Duration::from_millis(1_500)),
+            "1970-01-01T00:00:01Z"
+        );
+
+        let format = String::from("%Y%m%d-%H%M%S");
+        assert_eq!(timestamp_s!(&format, instant), "20000229-235959");
+    }
+
+    #[test]
+    fn current_time() {
+        let before = Utc::now().timestamp();
+        let now = DateTime::parse_from_rfc3339(&timestamp_s!()).unwrap();
+        assert!((before..=Utc::now().timestamp()).contains(&now.timestamp()));
+        assert_eq!(timestamp_s!("%Y").len(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid timestamp format \"%Q\"")]
+    fn invalid_format() {
+        timestamp_s!("%Q");
+    }
+}
@@ -0,0 +1,223 @@
+//! Temporary file and directory utilities.
+//!
+//! Requires the `tempfile` feature to be enabled.
+//!
+//! The `tmpfile!` and `tmpdir!` macros create temporary fixtures for tests,
+//! optionally filled with content using the `s!` syntax. Everything is
+//! deleted when the returned value is dropped.
+
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// A file with a chosen name inside its own temporary directory.
+///
+/// Returned by [`tmpfile!`](crate::tmpfile). The file and its directory are
+/// removed when this value is dropped. It dereferences to the file's
+/// [`Path`], so it can be passed directly to anything taking `AsRef<Path>`.
+#[derive(Debug)]
+pub struct TmpFile {
+    path: PathBuf,
+    dir: TempDir,
+}
+
+impl TmpFile {
+    /// Returns the path of the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the path of the temporary directory containing the file.
+    pub fn dir(&self) -> &Path {
+        self.dir.path()
+    }
+}
+
+impl Deref for TmpFile {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for TmpFile {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+fn write(root: &Path, name: &str, content: &str) -> PathBuf {
+    let path = root.join(name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .unwrap_or_else(|e| panic!("failed to create {}: {}", parent.display(), e));
+    }
+    fs::write(&path, content)
+        .unwrap_or_else(|e| panic!("failed to write {}: {}", path.display(), e));
+    path
+}
+
+#[doc(hidden)]
+pub fn dir(entries: &[(&str, String)]) -> TempDir {
+    let dir =
+        TempDir::new().unwrap_or_else(|e| panic!("failed to create temporary directory: {}", e));
+    for (name, content) in entries {
+        write(dir.path(), name, content);
+    }
+    dir
+}
+
+#[doc(hidden)]
+pub fn file(name: &str, content: &str) -> TmpFile {
+    let dir = dir(&[]);
+    let path = write(dir.path(), name, content);
+    TmpFile { path, dir }
+}
+
+/// A convenience macro for creating a named temporary file.
+///
+/// Requires the `tempfile` feature to be enabled.
+///
+/// This macro provides two ways to create a file:
+/// - Empty, with just a name
+/// - With content, using any form accepted by `s!` after the name
+///
+/// The file is created in a fresh temporary directory, so its name is
+/// exactly the one given, which matters for code that looks at extensions
+/// or expects a `config.toml`. Names may contain subdirectories. The
+/// returned [`TmpFile`] deletes everything when dropped.
+///
+/// # Examples
+///
+/// ## File with formatted content
+/// ```
+/// # use smacro::tmpfile;
+/// let port = 8080;
+/// let config = tmpfile!("config.toml", "port = {}", port);
+///
+/// assert_eq!(config.file_name().unwrap(), "config.toml");
+/// assert_eq!(std::fs::read_to_string(&config).unwrap(), "port = 8080");
+/// ```
+///
+/// ## Empty file
+/// ```
+/// # use smacro::tmpfile;
+/// let log = tmpfile!("logs/app.log");
+///
+/// assert!(log.exists());
+/// assert_eq!(std::fs::metadata(&log).unwrap().len(), 0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the directory or the file cannot be created.
+#[macro_export]
+macro_rules! tmpfile {
+    ($name:expr $(,)?) => {
+        $crate::tmp::file(&$crate::s!($name), "")
+    };
+    ($name:expr, $($content:tt)+) => {
+        $crate::tmp::file(&$crate::s!($name), &$crate::s!($($content)+))
+    };
+}
+
+/// A convenience macro for creating a temporary directory of fixture files.
+///
+/// Requires the `tempfile` feature to be enabled.
+///
+/// This macro provides two ways to create a directory:
+/// - Empty
+/// - With files, given as `name => content` pairs
+///
+/// Contents can be anything implementing `ToString`, such as `s!(...)`.
+/// Names may contain subdirectories, which are created as needed. The
+/// returned [`tempfile::TempDir`] deletes everything when dropped.
+///
+/// # Examples
+///
+/// ## Directory with files
+/// ```
+/// # use smacro::{s, tmpdir};
+/// let user = "ada";
+/// let dir = tmpdir!(
+///     "users.json" => s!("[\"{}\"]", user),
+///     "nested/readme.md" => "# Fixtures",
+/// );
+///
+/// let users = std::fs::read_to_string(dir.path().join("users.json")).unwrap();
+/// assert_eq!(users, "[\"ada\"]");
+/// assert!(dir.path().join("nested/readme.md").is_file());
+/// ```
+///
+/// ## Empty directory
+/// ```
+/// # use smacro::tmpdir;
+/// let out = tmpdir!();
+///
+/// assert_eq!(std::fs::read_dir(out.path()).unwrap().count(), 0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the directory or any of the files cannot be created.
+#[macro_export]
+macro_rules! tmpdir {
+    () => {
+        $crate::tmp::dir(&[])
+    };
+    ($($name:expr => $content:expr),+ $(,)?) => {
+        $crate::tmp::dir(&[$((&*$crate::s!($name), $crate::s!($content))),+])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    #[test]
+    fn file_with_content() {
+        let name = "data";
+        let f = tmpfile!(crate::s!("{}.csv", name), "a,b\n{},{}", 1, 2);
+        assert!(f.path().ends_with("data.csv"));
+        assert_eq!(fs::read_to_string(&f).unwrap(), "a,b\n1,2");
+    }
+
+    #[test]
+    fn file_is_removed_on_drop() {
+        let f = tmpfile!("gone.txt", "bye");
+        let (path, dir) = (f.path().to_path_buf(), f.dir().to_path_buf());
+        assert!(path.is_file());
+        drop(f);
+        assert!(!path.exists());
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn files_get_separate_directories() {
+        let a = tmpfile!("same.txt", "a");
+        let b = tmpfile!("same.txt", "b");
+        assert_ne!(a.path(), b.path());
+        assert_eq!(fs::read_to_string(&a).unwrap(), "a");
+    }
+
+    #[test]
+    fn dir_with_files() {
+        let dir = tmpdir!("a.txt" => "one", "sub/dir/b.txt" => 2);
+        assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "one");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("sub/dir/b.txt")).unwrap(),
+            "2"
+        );
+    }
+
+    #[test]
+    fn empty_dir_is_removed_on_drop() {
+        let dir = tmpdir!();
+        let path = dir.path().to_path_buf();
+        assert!(path.is_dir());
+        drop(dir);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,253 @@
+//! Struct to map conversion utilities.
+//!
+//! Requires the `derive` feature to be enabled.
+//!
+//! The [`ToMap`] and [`FromMap`] traits convert structs to and from a
+//! `HashMap<String, String>` keyed by field name, which is handy for
+//! configuration, logging context and other string-based formats. Both can
+//! be derived for structs with named fields.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Derives [`ToMap`], converting a struct into a `HashMap<String, String>`.
+///
+/// Every field becomes an entry keyed by the field name, with the value
+/// formatted through `Display`, so all field types must implement it.
+/// Raw identifiers such as `r#type` are keyed without the `r#` prefix.
+///
+/// Only structs with named fields are supported.
+///
+/// # Examples
+///
+/// ## Logging a config
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use smacro::ToMap;
+///
+/// #[derive(ToMap)]
+/// struct Config {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// let config = Config { host: "localhost".into(), port: 8080 };
+/// let map = config.to_map();
+///
+/// assert_eq!(map["host"], "localhost");
+/// assert_eq!(map["port"], "8080");
+/// # }
+/// ```
+pub use smacro_derive::ToMap;
+
+/// Derives [`FromMap`], building a struct from a `HashMap<String, String>`.
+///
+/// Every field is looked up by its name and parsed with `FromStr`, so all
+/// field types must implement it. A missing key or a value that fails to
+/// parse is reported as an `Err` naming the field. Extra keys are ignored.
+///
+/// Only structs with named fields are supported.
+///
+/// # Examples
+///
+/// ## Reading a config
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use smacro::FromMap;
+/// use std::collections::HashMap;
+///
+/// #[derive(FromMap)]
+/// struct Config {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// let map = HashMap::from([
+///     ("host".to_string(), "localhost".to_string()),
+///     ("port".to_string(), "8080".to_string()),
+/// ]);
+/// let config = Config::from_map(&map).unwrap();
+///
+/// assert_eq!(config.host, "localhost");
+/// assert_eq!(config.port, 8080);
+/// # }
+/// ```
+pub use smacro_derive::FromMap;
+
+/// Types that can be converted into a map of strings.
+///
+/// Derive it with `#[derive(ToMap)]` to use field names as keys and each
+/// field's `Display` output as values.
+///
+/// # Examples
+///
+/// ## Deriving
+/// ```
+/// use smacro::ToMap;
+///
+/// #[derive(ToMap)]
+/// struct Config {
+///     host: String,
+///     port: u16,
+///     r#type: &'static str,
+/// }
+///
+/// let config = Config { host: "localhost".into(), port: 8080, r#type: "dev" };
+/// let map = config.to_map();
+///
+/// assert_eq!(map.len(), 3);
+/// assert_eq!(map["port"], "8080");
+/// assert_eq!(map["type"], "dev");
+/// ```
+pub trait ToMap {
+    /// Returns a map with an entry per field.
+    fn to_map(&self) -> HashMap<String, String>;
+}
+
+/// Types that can be built from a map of strings.
+///
+/// Derive it with `#[derive(FromMap)]` to look up each field by name and
+/// parse its value with `FromStr`. Missing keys and unparsable values are
+/// reported as an `Err` naming the field; extra keys are ignored.
+///
+/// # Examples
+///
+/// ## Round trip
+/// ```
+/// use smacro::{FromMap, ToMap};
+///
+/// #[derive(ToMap, FromMap, Debug, PartialEq)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let map = Point { x: 1, y: -2 }.to_map();
+/// assert_eq!(Point::from_map(&map), Ok(Point { x: 1, y: -2 }));
+/// ```
+///
+/// ## Errors
+/// ```
+/// use smacro::FromMap;
+/// use std::collections::HashMap;
+///
+/// #[derive(FromMap, Debug)]
+/// struct Limits {
+///     max_connections: u32,
+/// }
+///
+/// let mut map = HashMap::new();
+/// assert_eq!(
+///     Limits::from_map(&map).unwrap_err(),
+///     "missing field `max_connections`"
+/// );
+///
+/// map.insert("max_connections".to_string(), "lots".to_string());
+/// assert_eq!(
+///     Limits::from_map(&map).unwrap_err(),
+///     "invalid value for field `max_connections`: invalid digit found in string"
+/// );
+/// ```
+pub trait FromMap: Sized {
+    /// Builds a value from the entries of `map`.
+    fn from_map(map: &HashMap<String, String>) -> Result<Self, String>;
+}
+
+#[doc(hidden)]
+pub fn field<T>(map: &HashMap<String, String>, name: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = map
+        .get(name)
+        .ok_or_else(|| format!("missing field `{}`", name))?;
+    value
+        .parse()
+        .map_err(|e| format!("invalid value for field `{}`: {}", name, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FromMap, ToMap};
+    use std::collections::HashMap;
+    use std::fmt::Display;
+
+    #[derive(ToMap, FromMap, Debug, PartialEq)]
+    pub(crate) struct Server {
+        pub name: String,
+        port: u16,
+        verbose: bool,
+    }
+
+    #[derive(ToMap)]
+    struct Labeled<'a, T: Display, const N: usize = 1>
+    where
+        T: Clone,
+    {
+        label: &'a str,
+        value: T,
+    }
+
+    #[derive(ToMap)]
+    struct Empty {}
+
+    fn server() -> Server {
+        Server {
+            name: "api".to_string(),
+            port: 443,
+            verbose: false,
+        }
+    }
+
+    #[test]
+    fn to_map_uses_field_names() {
+        let map = server().to_map();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map["name"], "api");
+        assert_eq!(map["port"], "443");
+        assert_eq!(map["verbose"], "false");
+    }
+
+    #[test]
+    fn from_map_round_trip() {
+        let mut map = server().to_map();
+        map.insert("unused".to_string(), "ignored".to_string());
+        assert_eq!(Server::from_map(&map), Ok(server()));
+    }
+
+    #[test]
+    fn from_map_errors() {
+        let mut map = server().to_map();
+        map.insert("port".to_string(), "99999".to_string());
+        assert_eq!(
+            Server::from_map(&map),
+            Err(
+                "invalid value for field `port`: number too large to fit in target type"
+                    .to_string()
+            )
+        );
+
+        map.remove("name");
+        assert_eq!(
+            Server::from_map(&map),
+            Err("missing field `name`".to_string())
+        );
+    }
+
+    #[test]
+    fn generics_and_empty_structs() {
+        let labeled: Labeled<'_, f64> = Labeled {
+            label: "pi",
+            value: 3.5,
+        };
+        let map = labeled.to_map();
+        assert_eq!(map["label"], "pi");
+        assert_eq!(map["value"], "3.5");
+
+        assert_eq!(Empty {}.to_map(), HashMap::new());
+    }
+}
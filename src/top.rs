@@ -0,0 +1,164 @@
+//! Top-N selection utilities.
+//!
+//! The `top_n!` macro picks the largest entries of a map or iterable with a
+//! bounded `BinaryHeap`, so only `n` elements are kept in memory and the
+//! input is never fully sorted.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+/// An element ranked by `key`, with earlier elements winning ties.
+struct Ranked<O, T> {
+    key: O,
+    seq: usize,
+    item: T,
+}
+
+impl<O: Ord, T> Ord for Ranked<O, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key
+            .cmp(&other.key)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl<O: Ord, T> PartialOrd for Ranked<O, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<O: Ord, T> PartialEq for Ranked<O, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<O: Ord, T> Eq for Ranked<O, T> {}
+
+/// Returns the `n` largest `(key, item)` pairs by key, largest first.
+fn top<O: Ord, T>(ranked: impl Iterator<Item = (O, T)>, n: usize) -> Vec<(O, T)> {
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut heap = BinaryHeap::with_capacity(n.saturating_add(1).min(ranked.size_hint().0));
+    for (seq, (key, item)) in ranked.enumerate() {
+        heap.push(Reverse(Ranked { key, seq, item }));
+        if heap.len() > n {
+            heap.pop();
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(ranked)| (ranked.key, ranked.item))
+        .collect()
+}
+
+#[doc(hidden)]
+pub fn top_values<I, K, V>(entries: I, n: usize) -> Vec<(K, V)>
+where
+    I: IntoIterator<Item = (K, V)>,
+    V: Ord,
+{
+    top(entries.into_iter().map(|(k, v)| (v, k)), n)
+        .into_iter()
+        .map(|(v, k)| (k, v))
+        .collect()
+}
+
+#[doc(hidden)]
+pub fn top_by<I, O, F>(items: I, n: usize, mut key: F) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    O: Ord,
+    F: FnMut(&I::Item) -> O,
+{
+    top(items.into_iter().map(|item| (key(&item), item)), n)
+        .into_iter()
+        .map(|(_, item)| item)
+        .collect()
+}
+
+/// A convenience macro for selecting the `n` largest entries.
+///
+/// This macro provides two ways to rank entries:
+/// - By value, for maps or other iterables of `(key, value)` pairs
+/// - By a key computed from each element with a closure, for any iterable
+///
+/// The result is a `Vec` of at most `n` elements, largest first, with ties
+/// going to the element seen first. Passing a `HashMap` by reference gives
+/// `(&K, &V)` pairs and leaves the map untouched. Only `n` elements are
+/// kept in memory while scanning the input.
+///
+/// # Examples
+///
+/// ## Leaderboard
+/// ```
+/// # use smacro::top_n;
+/// use std::collections::HashMap;
+///
+/// let scores = HashMap::from([("ada", 90), ("alan", 75), ("grace", 98), ("linus", 60)]);
+///
+/// assert_eq!(top_n!(&scores, 2), [(&"grace", &98), (&"ada", &90)]);
+/// ```
+///
+/// ## Key closure
+/// ```
+/// # use smacro::top_n;
+/// let words = ["to", "be", "or", "not", "to", "be", "questionable"];
+/// let longest = top_n!(words, 2, |w| w.len());
+///
+/// assert_eq!(longest, ["questionable", "not"]);
+/// ```
+#[macro_export]
+macro_rules! top_n {
+    ($entries:expr, $n:expr $(,)?) => {
+        $crate::top::top_values($entries, $n)
+    };
+    ($items:expr, $n:expr, $key:expr $(,)?) => {
+        $crate::top::top_by($items, $n, $key)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Reverse;
+    use std::collections::HashMap;
+
+    #[test]
+    fn top_values_of_map() {
+        let m: HashMap<u32, u32> = (0..100).map(|i| (i, (i * 37) % 101)).collect();
+        let top = top_n!(m.clone(), 3);
+        assert_eq!(top.len(), 3);
+        assert!(top.windows(2).all(|w| w[0].1 >= w[1].1));
+
+        let mut values: Vec<u32> = m.values().copied().collect();
+        values.sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(top.iter().map(|e| e.1).collect::<Vec<_>>(), values[..3]);
+    }
+
+    #[test]
+    fn fewer_items_than_n() {
+        assert_eq!(top_n!(vec![("a", 1), ("b", 2)], 5), [("b", 2), ("a", 1)]);
+        assert!(top_n!(vec![("a", 1)], 0).is_empty());
+        assert!(top_n!(Vec::<(u8, u8)>::new(), 3).is_empty());
+        assert_eq!(
+            top_n!(vec![("a", 1), ("b", 2)], usize::MAX),
+            [("b", 2), ("a", 1)]
+        );
+        assert_eq!(top_n!(0..3, usize::MAX, |&x| x), [2, 1, 0]);
+    }
+
+    #[test]
+    fn ties_keep_first_seen() {
+        let items = vec![("x", 1), ("y", 2), ("z", 2), ("w", 2)];
+        assert_eq!(top_n!(items, 2,), [("y", 2), ("z", 2)]);
+    }
+
+    #[test]
+    fn key_closure() {
+        let smallest = top_n!(vec![5, 3, 9, 1], 2, |&n| Reverse(n));
+        assert_eq!(smallest, [1, 3]);
+        assert_eq!(top_n!(1..=10, 3, |n| n % 4), [3, 7, 2]);
+    }
+}
@@ -0,0 +1,163 @@
+//! Struct mutation utilities.
+//!
+//! The `update!` and `swap_fields!` macros write out the repetitive field
+//! assignments and swaps of state-mutation code.
+
+/// A convenience macro for assigning several fields of a value at once.
+///
+/// `update!(target; a = 1, b = 2)` expands to `target.a = 1; target.b = 2;`.
+/// Fields can be nested, as in `server.port = 9000`, and the target can be
+/// a value or a mutable reference. The target expression is repeated for
+/// every assignment, so it should be a place such as a variable or a field.
+///
+/// # Examples
+///
+/// ## Updating fields
+/// ```
+/// # use smacro::update;
+/// #[derive(Default)]
+/// struct Config {
+///     port: u16,
+///     debug: bool,
+/// }
+///
+/// let mut config = Config::default();
+/// update!(config; port = 9000, debug = true);
+///
+/// assert_eq!(config.port, 9000);
+/// assert!(config.debug);
+/// ```
+///
+/// ## Nested fields through a reference
+/// ```
+/// # use smacro::update;
+/// struct Server {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// struct App {
+///     server: Server,
+///     workers: usize,
+/// }
+///
+/// fn configure(app: &mut App) {
+///     update!(app; server.port = 443, server.host = "example.com".into(), workers = 8);
+/// }
+///
+/// let mut app = App { server: Server { host: String::new(), port: 80 }, workers: 1 };
+/// configure(&mut app);
+///
+/// assert_eq!(app.server.port, 443);
+/// assert_eq!(app.server.host, "example.com");
+/// assert_eq!(app.workers, 8);
+/// ```
+#[macro_export]
+macro_rules! update {
+    ($target:expr; $($($field:ident).+ = $value:expr),+ $(,)?) => {
+        {
+            $(
+                $target.$($field).+ = $value;
+            )+
+        }
+    };
+}
+
+/// A convenience macro for swapping fields between two values.
+///
+/// `swap_fields!(a, b; x, y)` swaps `a.x` with `b.x` and `a.y` with `b.y`
+/// using `core::mem::swap`, leaving the other fields untouched. Both values
+/// can be values or mutable references, and are repeated for every field
+/// like the target of [`update!`]. Fields can be nested, as in `pos.x`.
+///
+/// # Examples
+///
+/// ## Swapping fields
+/// ```
+/// # use smacro::swap_fields;
+/// #[derive(Debug, PartialEq)]
+/// struct Player {
+///     name: &'static str,
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let mut a = Player { name: "a", x: 1, y: 2 };
+/// let mut b = Player { name: "b", x: 3, y: 4 };
+///
+/// swap_fields!(a, b; x, y);
+///
+/// assert_eq!(a, Player { name: "a", x: 3, y: 4 });
+/// assert_eq!(b, Player { name: "b", x: 1, y: 2 });
+/// ```
+#[macro_export]
+macro_rules! swap_fields {
+    ($a:expr, $b:expr; $($($field:ident).+),+ $(,)?) => {
+        {
+            $(
+                core::mem::swap(&mut $a.$($field).+, &mut $b.$($field).+);
+            )+
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[derive(Debug, Default, Clone, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq)]
+    struct Entity {
+        name: String,
+        pos: Point,
+        hp: u32,
+    }
+
+    #[test]
+    fn update_fields() {
+        let mut e = Entity::default();
+        update!(e; name = "orc".to_string(), hp = 30,);
+        assert_eq!(e.name, "orc");
+        assert_eq!(e.hp, 30);
+        assert_eq!(e.pos, Point::default());
+    }
+
+    #[test]
+    fn update_nested_through_reference() {
+        let mut entities = [Entity::default(), Entity::default()];
+        let second = &mut entities[1];
+        update!(second; pos.x = 5, pos.y = -5);
+        assert_eq!(entities[1].pos, Point { x: 5, y: -5 });
+        assert_eq!(entities[0], Entity::default());
+    }
+
+    #[test]
+    fn swap_listed_fields() {
+        let mut a = Entity {
+            name: "a".to_string(),
+            pos: Point { x: 1, y: 2 },
+            hp: 10,
+        };
+        let mut b = Entity {
+            name: "b".to_string(),
+            pos: Point { x: 3, y: 4 },
+            hp: 20,
+        };
+        swap_fields!(a, b; pos, hp);
+        assert_eq!((a.name.as_str(), a.pos.x, a.hp), ("a", 3, 20));
+        assert_eq!((b.name.as_str(), b.pos.x, b.hp), ("b", 1, 10));
+    }
+
+    #[test]
+    fn swap_nested_fields_through_references() {
+        let mut a = Point { x: 1, y: 2 };
+        let mut b = Entity::default();
+        let (ra, rb) = (&mut a, &mut b);
+        swap_fields!(ra, rb.pos; x);
+        assert_eq!(a, Point { x: 0, y: 2 });
+        assert_eq!(b.pos, Point { x: 1, y: 0 });
+    }
+}
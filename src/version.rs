@@ -0,0 +1,79 @@
+//! Build version utilities.
+//!
+//! The `version_s!` macro assembles the version string shown by `--version`
+//! flags from the package version and the git commit and build date that a
+//! build script may provide.
+
+#[doc(hidden)]
+pub fn version(package: &str, sha: Option<&str>, date: Option<&str>) -> String {
+    let sha = sha
+        .filter(|sha| !sha.is_empty())
+        .map(|sha| sha.get(..7).unwrap_or(sha));
+    let date = date.filter(|date| !date.is_empty());
+    match (sha, date) {
+        (Some(sha), Some(date)) => format!("{} ({} {})", package, sha, date),
+        (Some(detail), None) | (None, Some(detail)) => format!("{} ({})", package, detail),
+        (None, None) => package.to_string(),
+    }
+}
+
+/// A convenience macro for assembling a build version string.
+///
+/// Produces `"{version} ({git_sha} {build_date})"` from the compile-time
+/// environment of the calling crate:
+/// - The version is `CARGO_PKG_VERSION`
+/// - The commit is `GIT_SHA`, or `VERGEN_GIT_SHA` as set by `vergen`,
+///   shortened to seven characters
+/// - The build date is `BUILD_DATE`, or `VERGEN_BUILD_DATE`
+///
+/// The commit and build date are usually exported by a build script with
+/// `cargo:rustc-env`. Whichever of them is missing or empty is left out,
+/// along with the parentheses when both are, so the macro works unchanged
+/// in builds without git metadata.
+///
+/// # Examples
+///
+/// ```
+/// # use smacro::version_s;
+/// // In `build.rs`: println!("cargo:rustc-env=GIT_SHA={}", sha);
+/// let version = version_s!();
+///
+/// assert!(version.starts_with(env!("CARGO_PKG_VERSION")));
+/// println!("mytool {}", version);
+/// ```
+#[macro_export]
+macro_rules! version_s {
+    () => {
+        $crate::version::version(
+            env!("CARGO_PKG_VERSION"),
+            option_env!("GIT_SHA").or(option_env!("VERGEN_GIT_SHA")),
+            option_env!("BUILD_DATE").or(option_env!("VERGEN_BUILD_DATE")),
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::version;
+
+    #[test]
+    fn fallbacks() {
+        let sha = Some("3f2c9a1b7e");
+        assert_eq!(
+            version("1.2.0", sha, Some("2024-05-01")),
+            "1.2.0 (3f2c9a1 2024-05-01)"
+        );
+        assert_eq!(version("1.2.0", sha, Some("")), "1.2.0 (3f2c9a1)");
+        assert_eq!(
+            version("1.2.0", None, Some("2024-05-01")),
+            "1.2.0 (2024-05-01)"
+        );
+        assert_eq!(version("1.2.0", Some("abc"), None), "1.2.0 (abc)");
+        assert_eq!(version("1.2.0", None, None), "1.2.0");
+    }
+
+    #[test]
+    fn macro_uses_package_version() {
+        assert!(version_s!().starts_with(env!("CARGO_PKG_VERSION")));
+    }
+}
@@ -0,0 +1,168 @@
+//! Weak reference utilities.
+//!
+//! The `weak!` and `weak_map!` macros downgrade `Arc` and `Rc` pointers to
+//! weak references, for registries and observers that must not keep their
+//! entries alive.
+
+use std::rc::{self, Rc};
+use std::sync::{self, Arc};
+
+/// Reference-counted pointers that can be downgraded to a weak reference.
+///
+/// Implemented for [`Arc`], [`Rc`] and references to them, and used by [`weak!`](macro@crate::weak)
+/// and [`weak_map!`](crate::weak_map) to accept either.
+pub trait Downgrade {
+    /// The weak reference type.
+    type Weak;
+
+    /// Creates a new weak pointer to the same allocation.
+    fn downgrade(&self) -> Self::Weak;
+}
+
+impl<T: ?Sized> Downgrade for Arc<T> {
+    type Weak = sync::Weak<T>;
+
+    fn downgrade(&self) -> Self::Weak {
+        Arc::downgrade(self)
+    }
+}
+
+impl<T: ?Sized> Downgrade for Rc<T> {
+    type Weak = rc::Weak<T>;
+
+    fn downgrade(&self) -> Self::Weak {
+        Rc::downgrade(self)
+    }
+}
+
+impl<P: Downgrade + ?Sized> Downgrade for &P {
+    type Weak = P::Weak;
+
+    fn downgrade(&self) -> Self::Weak {
+        P::downgrade(self)
+    }
+}
+
+/// A convenience macro for downgrading an `Arc` or `Rc` to a weak reference.
+///
+/// `weak!(ptr)` is equivalent to `Arc::downgrade(&ptr)` or
+/// `Rc::downgrade(&ptr)`. The pointer is borrowed, not moved, and can also
+/// be given as a reference.
+///
+/// # Examples
+///
+/// ## Downgrading an `Arc`
+/// ```
+/// # use smacro::weak;
+/// use std::sync::Arc;
+///
+/// let config = Arc::new("production");
+/// let handle = weak!(config);
+///
+/// assert_eq!(handle.upgrade().as_deref(), Some(&"production"));
+///
+/// drop(config);
+/// assert!(handle.upgrade().is_none());
+/// ```
+///
+/// ## Downgrading an `Rc`
+/// ```
+/// # use smacro::weak;
+/// use std::rc::Rc;
+///
+/// let parent = Rc::new(vec![1, 2, 3]);
+/// let back_ref = weak!(&parent);
+///
+/// assert_eq!(Rc::weak_count(&parent), 1);
+/// assert_eq!(back_ref.upgrade().unwrap().len(), 3);
+/// ```
+#[macro_export]
+macro_rules! weak {
+    ($ptr:expr) => {
+        $crate::weak::Downgrade::downgrade(&$ptr)
+    };
+}
+
+/// A convenience macro for creating a `HashMap` of weak references.
+///
+/// The syntax is the same as `map!`, but every value must be an `Arc` or
+/// an `Rc`, which is downgraded with [`weak!`](macro@crate::weak). The result is
+/// a `HashMap<K, Weak<V>>` that doesn't keep its values alive, as needed by
+/// registries and observer lists.
+///
+/// # Examples
+///
+/// ## A registry that doesn't leak
+/// ```
+/// # use smacro::weak_map;
+/// use std::sync::Arc;
+///
+/// let db = Arc::new("postgres");
+/// let cache = Arc::new("redis");
+///
+/// let services = weak_map!["db" => db, "cache" => cache];
+/// drop(cache);
+///
+/// let alive: Vec<_> = services.iter().filter(|(_, s)| s.upgrade().is_some()).map(|(k, _)| *k).collect();
+/// assert_eq!(alive, ["db"]);
+/// assert_eq!(Arc::strong_count(&db), 1);
+/// ```
+#[macro_export]
+macro_rules! weak_map {
+    [] => {
+        std::collections::HashMap::new()
+    };
+    [$($key:expr => $value:expr),+ $(,)?] => {
+        {
+            let mut map = std::collections::HashMap::new();
+            $(
+                map.insert($key, $crate::weak!($value));
+            )+
+            map
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::rc::{self, Rc};
+    use std::sync::{Arc, Weak};
+
+    #[test]
+    fn weak_from_arc_and_reference() {
+        let value = Arc::new(5);
+        let a = weak!(value);
+        let b = weak!(&value);
+        assert_eq!(Arc::strong_count(&value), 1);
+        assert_eq!(Arc::weak_count(&value), 2);
+        assert_eq!(*a.upgrade().unwrap(), 5);
+        drop(value);
+        assert!(b.upgrade().is_none());
+    }
+
+    #[test]
+    fn weak_from_rc() {
+        let value: Rc<str> = Rc::from("shared");
+        let w: rc::Weak<str> = weak!(value);
+        assert_eq!(&*w.upgrade().unwrap(), "shared");
+    }
+
+    #[test]
+    fn weak_map_downgrades_values() {
+        let a = Arc::new(String::from("a"));
+        let b = Arc::new(String::from("b"));
+        let m = weak_map![1 => a, 2 => b,];
+        assert_eq!(Arc::strong_count(&a), 1);
+
+        drop(b);
+        assert_eq!(m[&1].upgrade().as_deref().map(String::as_str), Some("a"));
+        assert!(m[&2].upgrade().is_none());
+    }
+
+    #[test]
+    fn empty_weak_map() {
+        let m: HashMap<&str, Weak<u8>> = weak_map![];
+        assert!(m.is_empty());
+    }
+}
@@ -0,0 +1,111 @@
+//! Wide string utilities.
+//!
+//! The `wstr!` and `utf16!` macros encode a string as UTF-16 code units,
+//! which is what Win32 `W` functions and other wide-character APIs expect.
+//! They accept the same input as `s!`.
+
+#[doc(hidden)]
+pub fn encode(s: &str, nul: bool) -> Vec<u16> {
+    let mut wide = Vec::with_capacity(s.len() + nul as usize);
+    wide.extend(s.encode_utf16());
+    if nul {
+        wide.push(0);
+    }
+    wide
+}
+
+/// A convenience macro for creating NUL-terminated UTF-16 strings.
+///
+/// Accepts anything `s!` accepts and returns a `Vec<u16>` ending with a
+/// `0` code unit, ready to be passed to Win32 APIs with `.as_ptr()`.
+///
+/// # Examples
+///
+/// ## From a literal
+/// ```
+/// # use smacro::wstr;
+/// let title = wstr!("Hi");
+///
+/// assert_eq!(title, vec![0x48, 0x69, 0]);
+/// ```
+///
+/// ## From a format string
+/// ```
+/// # use smacro::wstr;
+/// let n = 3;
+/// let path = wstr!("C:\\logs\\{}.txt", n);
+///
+/// assert_eq!(String::from_utf16_lossy(&path[..path.len() - 1]), "C:\\logs\\3.txt");
+/// assert_eq!(path.last(), Some(&0));
+/// ```
+///
+/// The string is not checked for interior `0` code units. If it contains
+/// one, C APIs will see it as the end of the string.
+#[macro_export]
+macro_rules! wstr {
+    () => {
+        $crate::wide::encode("", true)
+    };
+    ($($arg:tt)+) => {
+        $crate::wide::encode(&$crate::s!($($arg)+), true)
+    };
+}
+
+/// A convenience macro for encoding a string as UTF-16 without a terminator.
+///
+/// Accepts anything `s!` accepts and returns a `Vec<u16>`. Use [`wstr!`]
+/// instead when the receiving API expects a NUL-terminated string.
+///
+/// # Examples
+///
+/// ## Counting code units
+/// ```
+/// # use smacro::utf16;
+/// let units = utf16!("a🦀");
+///
+/// // The crab is outside the Basic Multilingual Plane, so it takes two units
+/// assert_eq!(units.len(), 3);
+/// assert_eq!(String::from_utf16(&units).unwrap(), "a🦀");
+/// ```
+#[macro_export]
+macro_rules! utf16 {
+    () => {
+        $crate::wide::encode("", false)
+    };
+    ($($arg:tt)+) => {
+        $crate::wide::encode(&$crate::s!($($arg)+), false)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn wstr_is_nul_terminated() {
+        assert_eq!(wstr!("ab"), vec![97, 98, 0]);
+        assert_eq!(wstr!(), vec![0]);
+    }
+
+    #[test]
+    fn utf16_has_no_terminator() {
+        assert_eq!(utf16!("ab"), vec![97, 98]);
+        assert!(utf16!().is_empty());
+    }
+
+    #[test]
+    fn surrogate_pairs() {
+        assert_eq!(utf16!("🦀"), vec![0xD83E, 0xDD80]);
+    }
+
+    #[test]
+    fn format_and_modes() {
+        let name = "kernel32";
+        assert_eq!(
+            wstr!("{}.dll", name),
+            "kernel32.dll\0".encode_utf16().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            utf16!(upper name),
+            "KERNEL32".encode_utf16().collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Error context utilities.
+//!
+//! The `wrap_err!` macro adds a formatted context message to the error of a
+//! `Result`, using the `s!` syntax, so failures say what was being done when
+//! they happened. With the `anyhow` feature it can also attach the context to
+//! an `anyhow::Error`.
+
+#[doc(hidden)]
+pub fn wrap<T, E: core::fmt::Display>(
+    result: Result<T, E>,
+    context: impl FnOnce() -> String,
+) -> Result<T, String> {
+    result.map_err(|e| format!("{}: {}", context(), e))
+}
+
+#[cfg(feature = "anyhow")]
+#[doc(hidden)]
+pub fn context<T, E>(result: Result<T, E>, context: impl FnOnce() -> String) -> anyhow::Result<T>
+where
+    Result<T, E>: anyhow::Context<T, E>,
+{
+    anyhow::Context::with_context(result, context)
+}
+
+/// A convenience macro for adding context to the error of a `Result`.
+///
+/// This macro provides two ways to add context:
+/// - Prefix the error's `Display` output with a message, producing a
+///   `Result<T, String>`
+/// - Attach the message as `anyhow` context with `anyhow;`, producing an
+///   `anyhow::Result<T>` (requires the `anyhow` feature)
+///
+/// The message accepts anything `s!` does and is only built if the result
+/// is an error. The `String` form joins the message and the error with
+/// `": "`, so nested contexts read from the outermost operation inwards.
+///
+/// # Examples
+///
+/// ## Adding context
+/// ```
+/// # use smacro::wrap_err;
+/// fn read_port(path: &str) -> Result<u16, String> {
+///     let text = wrap_err!(std::fs::read_to_string(path), "reading config at {}", path)?;
+///     wrap_err!(text.trim().parse(), "parsing port in {}", path)
+/// }
+///
+/// assert_eq!(
+///     wrap_err!("http".parse::<u16>(), "parsing port"),
+///     Err("parsing port: invalid digit found in string".to_string())
+/// );
+/// assert!(read_port("/does/not/exist").unwrap_err().starts_with("reading config at /does/not/exist: "));
+/// ```
+///
+/// ## With `anyhow`
+/// ```
+/// # #[cfg(feature = "anyhow")]
+/// # {
+/// # use smacro::wrap_err;
+/// let id = 7;
+/// let result: anyhow::Result<u32> = wrap_err!(anyhow; "x".parse::<u32>(), "parsing user {}", id);
+///
+/// let error = result.unwrap_err();
+/// assert_eq!(error.to_string(), "parsing user 7");
+/// assert_eq!(error.root_cause().to_string(), "invalid digit found in string");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! wrap_err {
+    (anyhow; $result:expr, $($msg:tt)+) => {
+        $crate::wrap_err::context($result, || $crate::s!($($msg)+))
+    };
+    ($result:expr, $($msg:tt)+) => {
+        $crate::wrap_err::wrap($result, || $crate::s!($($msg)+))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::ParseIntError;
+
+    fn parse(input: &str) -> Result<i32, ParseIntError> {
+        input.parse()
+    }
+
+    #[test]
+    fn ok_passes_through() {
+        assert_eq!(wrap_err!(parse("42"), "parsing"), Ok(42));
+    }
+
+    #[test]
+    fn error_gets_context() {
+        let input = "abc";
+        assert_eq!(
+            wrap_err!(parse(input), "parsing {:?}", input),
+            Err("parsing \"abc\": invalid digit found in string".to_string())
+        );
+    }
+
+    #[test]
+    fn nested_context() {
+        let inner = wrap_err!(parse(""), "reading field");
+        assert_eq!(
+            wrap_err!(inner, "loading {}", "config.toml"),
+            Err(
+                "loading config.toml: reading field: cannot parse integer from empty string"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn message_is_lazy() {
+        let mut built = false;
+        let _ = wrap_err!(parse("1"), {
+            built = true;
+            "unused"
+        });
+        assert!(!built);
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[test]
+    fn anyhow_context() {
+        let result = wrap_err!(anyhow; parse("?"), "field {}", 3);
+        let chain: Vec<String> = result.unwrap_err().chain().map(|e| e.to_string()).collect();
+        assert_eq!(chain, ["field 3", "invalid digit found in string"]);
+    }
+}
@@ -0,0 +1,78 @@
+//! Expansion tests for the macros embedding data at compile time, run
+//! against the public `smacro` paths they expand to.
+
+#[cfg(feature = "derive")]
+#[test]
+fn include_map_reads_pairs() {
+    use smacro::include_map;
+    use std::collections::HashMap;
+
+    let mime: HashMap<&'static str, &'static str> = include_map!("data/mime.types");
+
+    assert_eq!(mime.len(), 3);
+    assert_eq!(mime["json"], "application/json");
+    assert_eq!(mime["css"], "text/css");
+    assert_eq!(mime["html"], "application/xhtml+xml");
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn malformed_pairs_fail_to_compile() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/include_map_malformed.rs");
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn toml_map_nested_tables() {
+    use smacro::toml_map;
+
+    let config = toml_map!(
+        r#"
+        name = "smacro"
+
+        [server]
+        port = 8080
+        hosts = ["a", "b"]
+
+        [server.tls]
+        enabled = true
+        "#
+    );
+
+    assert_eq!(config.len(), 2);
+    assert_eq!(config["name"].as_str(), Some("smacro"));
+    assert_eq!(config["server"]["port"].as_integer(), Some(8080));
+    assert_eq!(config["server"]["hosts"][1].as_str(), Some("b"));
+    assert_eq!(config["server"]["tls"]["enabled"].as_bool(), Some(true));
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn yaml_map_nested_mappings() {
+    use smacro::yaml_map;
+
+    let user = yaml_map!(
+        "
+name: Ada
+roles: [admin, dev]
+address:
+  city: London
+  zip: 12345
+"
+    );
+
+    assert_eq!(user.len(), 3);
+    assert_eq!(user["name"].as_str(), Some("Ada"));
+    assert_eq!(user["roles"][1].as_str(), Some("dev"));
+    assert_eq!(user["address"]["city"].as_str(), Some("London"));
+    assert_eq!(user["address"]["zip"].as_u64(), Some(12345));
+}
+
+#[cfg(all(feature = "toml", feature = "yaml"))]
+#[test]
+fn invalid_documents_fail_to_compile() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/toml_map_invalid.rs");
+    cases.compile_fail("tests/ui/yaml_map_invalid.rs");
+}
@@ -0,0 +1,5 @@
+use smacro::include_map;
+
+fn main() {
+    let _ = include_map!("include_map_malformed.types");
+}
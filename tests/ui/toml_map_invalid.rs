@@ -0,0 +1,10 @@
+use smacro::toml_map;
+
+fn main() {
+    let _ = toml_map!(
+        r#"
+        [server
+        port = 8080
+        "#
+    );
+}
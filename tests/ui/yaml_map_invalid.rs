@@ -0,0 +1,6 @@
+use smacro::yaml_map;
+
+fn main() {
+    let _ = yaml_map!("name: [unclosed");
+    let _ = yaml_map!("- a list\n- not a mapping");
+}